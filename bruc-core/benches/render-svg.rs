@@ -76,7 +76,7 @@ fn bench_render_svg(b: &mut Bencher) {
     let mut view = create_view(1000);
     b.iter(move || {
         futures::executor::block_on(async {
-            let mut result = view.render(SvgRenderer).await;
+            let mut result = view.render(SvgRenderer::new()).await.unwrap();
             result.next().await
         })
     })
@@ -86,7 +86,7 @@ fn create_view(data_amount: usize) -> View {
     let specification_json = specification(&create_data_json(data_amount));
     let spec: Specification = serde_json::from_str(&specification_json).unwrap();
 
-    View::build(spec)
+    View::build(spec).unwrap()
 }
 
 fn create_data_json(amount: usize) -> String {
@@ -94,12 +94,12 @@ fn create_data_json(amount: usize) -> String {
     let mut rng = thread_rng();
 
     for i in 0..(amount - 1) {
-        let x = i as f32;
-        let y: f32 = rng.gen_range(0.0..50.0);
+        let x = i as f64;
+        let y: f64 = rng.gen_range(0.0..50.0);
         data.push_str(&format!(r#"{{ "x": {x}, "y": {y} }},"#));
     }
     let x = amount;
-    let y: f32 = rng.gen_range(0.0..50.0);
+    let y: f64 = rng.gen_range(0.0..50.0);
     data.push_str(&format!(r#"{{ "x": {x}, "y": {y} }}"#));
 
     format!("[{data}]")
@@ -71,8 +71,8 @@ async fn main() {
     )
     .unwrap();
 
-    let mut view = View::build(specification);
-    let mut render_result = view.render(SvgRenderer).await;
+    let mut view = View::build(specification).unwrap();
+    let mut render_result = view.render(SvgRenderer::new()).await.unwrap();
 
     let svg = render_result.next().await.unwrap();
 
@@ -1,6 +1,8 @@
 use std::{collections::HashMap, fmt::Display};
 
 use bruc_expression::data::{DataItem, DataSource};
+use bruc_expression::types::{DataType, Schema};
+use bruc_expression::{date, path};
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
@@ -39,18 +41,32 @@ impl DataValue {
             .collect()
     }
 
-    pub fn get_number(&self, field: &str) -> Option<&f32> {
-        self.instance.get(field).and_then(|item| item.get_number())
+    pub fn get_number(&self, field: &str) -> Option<&f64> {
+        self.get(field).and_then(|item| item.get_number())
     }
 
     pub fn get_text(&self, field: &str) -> Option<&String> {
-        self.instance.get(field).and_then(|item| item.get_text())
+        self.get(field).and_then(|item| item.get_text())
+    }
+
+    /// Parse the text of the fields declared as dates by a certain `schema` as ISO-8601 dates.
+    /// Text that isn't a valid date is replaced by null.
+    pub fn parse_dates(&mut self, schema: &Schema) {
+        for (key, item) in &mut self.instance {
+            if schema.find(key) != Some(DataType::Date) {
+                continue;
+            }
+
+            if let DataItem::Text(text) = item {
+                *item = date::parse(text).map_or(DataItem::Null, DataItem::Date);
+            }
+        }
     }
 }
 
 impl DataSource for DataValue {
     fn get(&self, key: &str) -> Option<&DataItem> {
-        self.instance.get(key)
+        path::resolve(key, |field| self.instance.get(field))
     }
 }
 
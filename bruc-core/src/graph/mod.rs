@@ -1,10 +1,10 @@
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::iter::FromIterator;
 
 use crate::graph::node::{Node, Operator};
 use crate::scene::SceneItem;
 
-use self::pulse::{MultiPulse, Pulse, SinglePulse};
+use self::pulse::{Changeset, MultiPulse, Pulse, SinglePulse};
 
 pub mod node;
 pub mod pulse;
@@ -61,8 +61,6 @@ impl Graph {
             .into_iter()
             .for_each(|source| self.add_edge(source, id));
 
-        self.order = self.sort_order();
-
         id
     }
 
@@ -85,6 +83,9 @@ impl Graph {
         self.sources.insert(index, BTreeSet::new());
         self.targets.insert(index, BTreeSet::new());
 
+        // A node without connections can be evaluated at any point
+        self.order.push(index);
+
         index
     }
 
@@ -108,6 +109,7 @@ impl Graph {
         source.insert(from);
 
         self.edges.push(Edge::new(from, to));
+        self.order = self.sort_order();
     }
 
     /// Sort the graph in topological order and return the order list of the nodes.
@@ -182,6 +184,14 @@ impl Graph {
         outputs.into_iter().filter_map(SceneItem::build).collect()
     }
 
+    /// Builds the scene items by applying a changeset to the values of a data `node`, and
+    /// building the scene items out of the pulse value of the outputs, see `evaluate_changes`.
+    pub async fn build_changes(&mut self, node: usize, changes: Changeset) -> Vec<SceneItem> {
+        let outputs = self.evaluate_changes(node, changes).await;
+
+        outputs.into_iter().filter_map(SceneItem::build).collect()
+    }
+
     /// Evaluates the current graph iterating through all the edges of the graph in topological
     /// order, and keeps track of the values by using `Pulse` instances. Once the evaluation
     /// has completed, it returns the leave nodes.
@@ -221,6 +231,63 @@ impl Graph {
         self.leaves()
     }
 
+    /// Evaluates a sub-tree starting with a data `node`, by applying a changeset to its values and
+    /// propagating the changes in topological order. Nodes with a single source process only the
+    /// changes of their source where possible, while the rest of nodes are evaluated again with
+    /// their full input. The sub-tree is evaluated again, if the data node's values are still to
+    /// be loaded.
+    async fn evaluate_changes(&mut self, node: usize, changes: Changeset) -> Vec<&Node> {
+        let Some(Node {
+            operator: Operator::Data(data),
+            pulse,
+        }) = self.nodes.get_mut(node)
+        else {
+            return self.leaves();
+        };
+        if !data.apply_changes(&changes) {
+            return self.evaluate_tree(node).await;
+        }
+        pulse.apply_changes(&changes);
+
+        let mut descendants = BTreeSet::from([node]);
+        let mut node_changes = HashMap::from([(node, changes)]);
+
+        for index in self.order.clone() {
+            let Some(sources) = self.sources.get(&index) else {
+                continue;
+            };
+            if !sources.iter().any(|source| descendants.contains(source)) {
+                continue;
+            }
+            descendants.insert(index);
+
+            let source_changes = match sources.first() {
+                Some(source) if sources.len() == 1 => node_changes.get(source),
+                _ => None,
+            };
+
+            let propagation = match source_changes {
+                // Nothing changes in the node, if its source didn't change
+                Some(changes) if changes.is_empty() => Propagation::Changes(Changeset::new()),
+                Some(changes) => {
+                    let node = self.nodes.get_mut(index).unwrap();
+                    node.operator.evaluate_changes(&mut node.pulse, changes)
+                }
+                None => Propagation::Unsupported,
+            };
+
+            match propagation {
+                Propagation::Changes(changes) => {
+                    node_changes.insert(index, changes);
+                }
+                Propagation::Updated => {}
+                Propagation::Unsupported => self.evaluate_node(index).await,
+            }
+        }
+
+        self.leaves()
+    }
+
     /// Evaluate a single node of a given index in the graph.
     async fn evaluate_node(&mut self, index: usize) {
         let pulse = self.get_pulse(index).unwrap_or(Pulse::init());
@@ -262,6 +329,24 @@ trait Evaluation {
 
     /// Evaluates a multi `Pulse` instance.
     async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse;
+
+    /// Evaluates the changes of the values of the operator's single source, by updating the
+    /// operator's previous `output` in place. The output mustn't be updated, if the changes are
+    /// not supported.
+    fn evaluate_changes(&self, _output: &mut Pulse, _changes: &Changeset) -> Propagation {
+        Propagation::Unsupported
+    }
+}
+
+/// `Propagation` is the outcome of evaluating the changes of a node's source.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Propagation {
+    /// The node's output is updated, and its changes are passed on to its targets.
+    Changes(Changeset),
+    /// The node's output is updated, but its targets need to be evaluated again.
+    Updated,
+    /// The node doesn't process changes, so it's evaluated again with its full input.
+    Unsupported,
 }
 
 /// `Edge` represents an edge between two nodes in the graph
@@ -280,9 +365,14 @@ impl Edge {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use crate::graph::node::data::DataUrl;
     use crate::graph::node::shape::SceneWindow;
+    use crate::loader::MemoryLoader;
     use crate::scale::Scale;
     use crate::spec::axis::{Axis, AxisOrientation};
+    use crate::spec::format::Format;
     use crate::spec::scale::domain::Domain;
 
     use crate::spec::shape::line::{LinePropertiesBuilder, LineShape};
@@ -441,4 +531,64 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn evaluates_changes_as_full_evaluation() {
+        let mut incremental = graph();
+        incremental.evaluate().await;
+
+        let outputs = incremental
+            .evaluate_changes(
+                0,
+                Changeset::new()
+                    .with_add(vec![DataValue::from_pairs(vec![("a", 8.0.into())])])
+                    .with_rem(vec![DataValue::from_pairs(vec![("a", 5.0.into())])]),
+            )
+            .await;
+        let changed: Vec<Pulse> = outputs.into_iter().map(|node| node.pulse.clone()).collect();
+
+        let mut expected = graph();
+        expected.replace_node(
+            0,
+            Node::init(Operator::data(vec![
+                DataValue::from_pairs(vec![("a", 13.0.into())]),
+                DataValue::from_pairs(vec![("a", 8.0.into())]),
+            ])),
+        );
+        let outputs = expected.evaluate().await;
+        let evaluated: Vec<Pulse> = outputs.into_iter().map(|node| node.pulse.clone()).collect();
+
+        assert_eq!(changed, evaluated);
+    }
+
+    #[tokio::test]
+    async fn evaluates_changes_before_url_is_loaded() {
+        let mut graph = Graph::new();
+        let data = graph.add_node(Operator::data_url(DataUrl::new(
+            "data.json".to_string(),
+            Format::json(),
+            None,
+            Arc::new(MemoryLoader::new().with_source("data.json", r#"[{ "a": 1 }]"#)),
+        )));
+        graph.add(
+            Operator::map(MapPipe::new("a + 1", "b").unwrap()),
+            vec![data],
+        );
+
+        // Values inserted before loading are dropped, as the loaded values replace them
+        let outputs = graph
+            .evaluate_changes(
+                data,
+                Changeset::new().with_add(vec![DataValue::from_pairs(vec![("a", 8.0.into())])]),
+            )
+            .await;
+
+        assert_eq!(
+            outputs[0].pulse,
+            Pulse::data(vec![DataValue::from_pairs(vec![
+                ("a", 1.0.into()),
+                ("b", 2.0.into())
+            ])])
+        );
+    }
 }
@@ -23,12 +23,12 @@ impl AxisOperator {
         }
     }
 
-    fn apply_interval(&self, domain: (f32, f32)) -> SinglePulse {
+    fn apply_interval(&self, domain: (f64, f64)) -> SinglePulse {
         let scene_item = self.create_axis(domain);
         SinglePulse::Shapes(vec![scene_item])
     }
 
-    fn create_axis(&self, domain: (f32, f32)) -> SceneItem {
+    fn create_axis(&self, domain: (f64, f64)) -> SceneItem {
         let ticks = self.scale.ticks(domain);
         SceneItem::axis(
             self.create_ruler(),
@@ -56,7 +56,7 @@ impl AxisOperator {
         }
     }
 
-    fn orientation_position(&self, position: f32) -> (f32, f32) {
+    fn orientation_position(&self, position: f64) -> (f64, f64) {
         match self.axis.orientation {
             AxisOrientation::Top => (position, self.window.height),
             AxisOrientation::Bottom => (position, 0.0),
@@ -140,7 +140,7 @@ mod tests {
                         label: Some("20.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (60.000004, 100.0),
+                        position: (60.0, 100.0),
                         label: Some("30.00".to_string())
                     },
                     SceneAxisTick {
@@ -152,7 +152,7 @@ mod tests {
                         label: Some("50.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (120.00001, 100.0),
+                        position: (120.0, 100.0),
                         label: Some("60.00".to_string())
                     },
                     SceneAxisTick {
@@ -210,7 +210,7 @@ mod tests {
                         label: Some("20.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (60.000004, 0.0),
+                        position: (60.0, 0.0),
                         label: Some("30.00".to_string())
                     },
                     SceneAxisTick {
@@ -222,7 +222,7 @@ mod tests {
                         label: Some("50.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (120.00001, 0.0),
+                        position: (120.0, 0.0),
                         label: Some("60.00".to_string())
                     },
                     SceneAxisTick {
@@ -280,7 +280,7 @@ mod tests {
                         label: Some("20.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (0.0, 60.000004),
+                        position: (0.0, 60.0),
                         label: Some("30.00".to_string())
                     },
                     SceneAxisTick {
@@ -292,7 +292,7 @@ mod tests {
                         label: Some("50.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (0.0, 120.00001),
+                        position: (0.0, 120.0),
                         label: Some("60.00".to_string())
                     },
                     SceneAxisTick {
@@ -350,7 +350,7 @@ mod tests {
                         label: Some("20.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (200.0, 60.000004),
+                        position: (200.0, 60.0),
                         label: Some("30.00".to_string())
                     },
                     SceneAxisTick {
@@ -362,7 +362,7 @@ mod tests {
                         label: Some("50.00".to_string())
                     },
                     SceneAxisTick {
-                        position: (200.0, 120.00001),
+                        position: (200.0, 120.0),
                         label: Some("60.00".to_string())
                     },
                     SceneAxisTick {
@@ -30,7 +30,7 @@ const BASE_PALETTE: [(u8, u8, u8); 30] = [
     (255, 69, 0),    // #ff4500 (Orange Red)
     (0, 191, 255),   // #00bfff (Deep Sky Blue)
 ];
-const FACTOR: f32 = 0.2;
+const FACTOR: f64 = 0.2;
 
 // Function to generate color variants
 pub(crate) fn generate_colors(amount: usize) -> Vec<String> {
@@ -41,9 +41,9 @@ pub(crate) fn generate_colors(amount: usize) -> Vec<String> {
         for (i, _) in chunk.iter().enumerate() {
             let (r, g, b) = BASE_PALETTE[i % BASE_PALETTE.len()];
 
-            let variant_r = ((r as f32 + factor * 255.0) % 256.0) as u8;
-            let variant_g = ((g as f32 + factor * 255.0) % 256.0) as u8;
-            let variant_b = ((b as f32 + factor * 255.0) % 256.0) as u8;
+            let variant_r = ((r as f64 + factor * 255.0) % 256.0) as u8;
+            let variant_g = ((g as f64 + factor * 255.0) % 256.0) as u8;
+            let variant_b = ((b as f64 + factor * 255.0) % 256.0) as u8;
 
             colors.push(rgb_to_hex(variant_r, variant_g, variant_b));
         }
@@ -1,28 +1,168 @@
+use std::sync::{Arc, Mutex};
+
+use bruc_expression::types::Schema;
+
+use crate::graph::pulse::Changeset;
+use crate::loader::{DataLoader, LoadError};
+use crate::spec::format::Format;
+use crate::spec::transform::error::Error;
 use crate::{
     data::DataValue,
     graph::{Evaluation, MultiPulse, Pulse, SinglePulse},
 };
 
-/// `DataOperator` represents an operator of the graph, which has a list of `DataValue` as source.
+/// `DataOperator` represents an operator of the graph, which has a list of `DataValue` as source,
+/// either given or loaded from an url.
 #[derive(Debug, PartialEq)]
 pub struct DataOperator {
     data: Vec<DataValue>,
+    url: Option<DataUrl>,
 }
 
 impl DataOperator {
     /// Create a new `DataOperator` instance with a list of values.
     pub(crate) fn new(data: Vec<DataValue>) -> Self {
-        DataOperator { data }
+        DataOperator { data, url: None }
+    }
+
+    /// Create a new `DataOperator` instance, whose values are loaded from an url.
+    pub(crate) fn url(url: DataUrl) -> Self {
+        DataOperator {
+            data: Vec::new(),
+            url: Some(url),
+        }
+    }
+
+    /// Apply a changeset to the operator's values, and return whether it was applied. Changes to
+    /// values still to be loaded from an url are ignored.
+    pub(crate) fn apply_changes(&mut self, changes: &Changeset) -> bool {
+        match &mut self.url {
+            Some(url) => match url.loaded.get_mut().unwrap() {
+                Some(values) => {
+                    changes.apply(values);
+                    true
+                }
+                None => false,
+            },
+            None => {
+                changes.apply(&mut self.data);
+                true
+            }
+        }
+    }
+
+    /// Select the operator's values matching a certain `predicate`.
+    pub(crate) fn select(&self, predicate: impl Fn(&DataValue) -> bool) -> Vec<DataValue> {
+        let select = |values: &[DataValue]| {
+            values
+                .iter()
+                .filter(|value| predicate(value))
+                .cloned()
+                .collect()
+        };
+
+        match &self.url {
+            Some(url) => url
+                .loaded
+                .lock()
+                .unwrap()
+                .as_deref()
+                .map_or(Vec::new(), select),
+            None => select(&self.data),
+        }
+    }
+
+    /// Error of the last attempt to load the operator's values from an url, if it failed.
+    pub(crate) fn error(&self) -> Option<LoadError> {
+        self.url
+            .as_ref()
+            .and_then(|url| url.error.lock().unwrap().clone())
+    }
+
+    async fn values(&self) -> Vec<DataValue> {
+        match &self.url {
+            Some(url) => url.load().await,
+            None => self.data.clone(),
+        }
     }
 }
 
 impl Evaluation for DataOperator {
     async fn evaluate_single(&self, _single: SinglePulse) -> Pulse {
-        Pulse::data(self.data.clone())
+        Pulse::data(self.values().await)
     }
 
     async fn evaluate_multi(&self, _multi: MultiPulse) -> Pulse {
-        Pulse::data(self.data.clone())
+        Pulse::data(self.values().await)
+    }
+}
+
+/// `DataUrl` loads the values of a data operator from an url, and reads them in a certain
+/// format. Values are loaded once, and kept for later evaluations. The error of a failed attempt
+/// is kept until the next one.
+#[derive(Debug)]
+pub(crate) struct DataUrl {
+    url: String,
+    format: Format,
+    schema: Option<Schema>,
+    loader: Arc<dyn DataLoader>,
+    loaded: Mutex<Option<Vec<DataValue>>>,
+    error: Mutex<Option<LoadError>>,
+}
+
+impl DataUrl {
+    pub(crate) fn new(
+        url: String,
+        format: Format,
+        schema: Option<Schema>,
+        loader: Arc<dyn DataLoader>,
+    ) -> Self {
+        DataUrl {
+            url,
+            format,
+            schema,
+            loader,
+            loaded: Mutex::new(None),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// Load the values of the url, unless they are already loaded. Values that can't be loaded
+    /// or read result in no values, and are loaded again on the next evaluation.
+    async fn load(&self) -> Vec<DataValue> {
+        if let Some(values) = self.loaded.lock().unwrap().as_ref() {
+            return values.clone();
+        }
+
+        let loaded = match self.loader.load(&self.url).await {
+            Ok(text) => self.format.read(&text).map_err(|error| {
+                LoadError::Invalid(self.url.clone(), Error::from(error).to_string())
+            }),
+            Err(error) => Err(error),
+        };
+        let mut values = match loaded {
+            Ok(values) => values,
+            Err(error) => {
+                *self.error.lock().unwrap() = Some(error);
+                return Vec::new();
+            }
+        };
+
+        if let Some(schema) = &self.schema {
+            for value in &mut values {
+                value.parse_dates(schema);
+            }
+        }
+
+        *self.error.lock().unwrap() = None;
+        *self.loaded.lock().unwrap() = Some(values.clone());
+        values
+    }
+}
+
+impl PartialEq for DataUrl {
+    fn eq(&self, other: &Self) -> bool {
+        self.url == other.url && self.format == other.format && self.schema == other.schema
     }
 }
 
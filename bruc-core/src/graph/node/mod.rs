@@ -12,17 +12,26 @@ use crate::spec::shape::line::LineShape;
 use crate::spec::shape::pie::PieShape;
 use crate::{
     data::DataValue,
-    spec::transform::{filter::FilterPipe, group::GroupPipe, map::MapPipe, pipe::Pipe},
+    spec::transform::{
+        aggregate::AggregatePipe, bin::BinPipe, filter::FilterPipe, fold::FoldPipe,
+        group::GroupPipe, loess::LoessPipe, lookup::LookupPipe, map::MapPipe, pipe::Pipe,
+        pivot::PivotPipe, regression::RegressionPipe, sort::SortPipe, stack::StackPipe,
+        window::WindowPipe,
+    },
 };
 
 use self::axis::AxisOperator;
-use self::data::ConstantOperator;
+use self::data::{ConstantOperator, DataUrl};
 use self::scale::{BandOperator, DomainIntervalOperator};
 use self::shape::BarOperator;
-use self::transform::{FilterOperator, MapOperator};
+use self::transform::{
+    AggregateOperator, BinOperator, FilterOperator, FoldOperator, LoessOperator, LookupOperator,
+    MapOperator, PivotOperator, RegressionOperator, SortOperator, StackOperator, WindowOperator,
+};
 use self::{data::DataOperator, transform::GroupOperator};
 
-use super::{Evaluation, Pulse};
+use super::pulse::Changeset;
+use super::{Evaluation, Propagation, Pulse};
 
 pub(crate) mod axis;
 pub(crate) mod color;
@@ -62,6 +71,16 @@ pub enum Operator {
     Map(MapOperator),
     Filter(FilterOperator),
     Group(GroupOperator),
+    Aggregate(AggregateOperator),
+    Sort(SortOperator),
+    Window(WindowOperator),
+    Stack(StackOperator),
+    Bin(BinOperator),
+    Lookup(LookupOperator),
+    Fold(FoldOperator),
+    Pivot(PivotOperator),
+    Regression(RegressionOperator),
+    Loess(LoessOperator),
     Line(LineOperator),
     Bar(BarOperator),
     Pie(PieOperator),
@@ -80,6 +99,11 @@ impl Operator {
         Operator::Data(DataOperator::new(data))
     }
 
+    /// Create a new data `Operator` instance, whose values are loaded from an url.
+    pub(crate) fn data_url(url: DataUrl) -> Self {
+        Operator::Data(DataOperator::url(url))
+    }
+
     pub fn constant(data: DataValue) -> Self {
         Operator::Constant(ConstantOperator::new(data))
     }
@@ -90,6 +114,17 @@ impl Operator {
             Pipe::Map(map) => Operator::map(map),
             Pipe::Filter(filter) => Operator::filter(filter),
             Pipe::Group(group) => Operator::group(group),
+            Pipe::Aggregate(aggregate) => Operator::aggregate(aggregate),
+            Pipe::Sort(sort) => Operator::sort(sort),
+            Pipe::Window(window) => Operator::window(window),
+            Pipe::Stack(stack) => Operator::stack(stack),
+            Pipe::Bin(bin) => Operator::bin(bin),
+            // Values of the `from` entry are expected in the first source, see `Operator::lookup`
+            Pipe::Lookup(lookup) => Operator::lookup(lookup, 0),
+            Pipe::Fold(fold) => Operator::fold(fold),
+            Pipe::Pivot(pivot) => Operator::pivot(pivot),
+            Pipe::Regression(regression) => Operator::regression(regression),
+            Pipe::Loess(loess) => Operator::loess(loess),
         }
     }
 
@@ -108,6 +143,57 @@ impl Operator {
         Operator::Group(GroupOperator::new(pipe))
     }
 
+    /// Create a new aggregate `Operator` instance.
+    pub fn aggregate(pipe: AggregatePipe) -> Self {
+        Operator::Aggregate(AggregateOperator::new(pipe))
+    }
+
+    /// Create a new sort `Operator` instance.
+    pub fn sort(pipe: SortPipe) -> Self {
+        Operator::Sort(SortOperator::new(pipe))
+    }
+
+    /// Create a new window `Operator` instance.
+    pub fn window(pipe: WindowPipe) -> Self {
+        Operator::Window(WindowOperator::new(pipe))
+    }
+
+    /// Create a new stack `Operator` instance.
+    pub fn stack(pipe: StackPipe) -> Self {
+        Operator::Stack(StackOperator::new(pipe))
+    }
+
+    /// Create a new bin `Operator` instance.
+    pub fn bin(pipe: BinPipe) -> Self {
+        Operator::Bin(BinOperator::new(pipe))
+    }
+
+    /// Create a new lookup `Operator` instance, which reads the values of the `from` entry from
+    /// its source at a certain `position`, in the order of the source nodes.
+    pub fn lookup(pipe: LookupPipe, position: usize) -> Self {
+        Operator::Lookup(LookupOperator::new(pipe, position))
+    }
+
+    /// Create a new fold `Operator` instance.
+    pub fn fold(pipe: FoldPipe) -> Self {
+        Operator::Fold(FoldOperator::new(pipe))
+    }
+
+    /// Create a new pivot `Operator` instance.
+    pub fn pivot(pipe: PivotPipe) -> Self {
+        Operator::Pivot(PivotOperator::new(pipe))
+    }
+
+    /// Create a new regression `Operator` instance.
+    pub fn regression(pipe: RegressionPipe) -> Self {
+        Operator::Regression(RegressionOperator::new(pipe))
+    }
+
+    /// Create a new loess `Operator` instance.
+    pub fn loess(pipe: LoessPipe) -> Self {
+        Operator::Loess(LoessOperator::new(pipe))
+    }
+
     /// Create a new line `Operator` instance.
     pub(crate) fn line(shape: LineShape, window: SceneWindow) -> Self {
         Operator::Line(LineOperator::new(shape, window))
@@ -140,17 +226,17 @@ impl Operator {
 
     /// Create a new linear `Operator` instance for a certain `range`, with a given `field` reference and an `output`
     /// field name.
-    pub(crate) fn linear(range: (f32, f32), field: &str, output: &str) -> Self {
+    pub(crate) fn linear(range: (f64, f64), field: &str, output: &str) -> Self {
         Operator::Linear(LinearOperator::new(range, field, output))
     }
 
     /// Create a new logarithmic `Operator` instance for a certain `range`, with a given `field` reference and an
     /// `output` field name.
-    pub(crate) fn log(range: (f32, f32), field: &str, output: &str) -> Self {
+    pub(crate) fn log(range: (f64, f64), field: &str, output: &str) -> Self {
         Operator::Log(LogOperator::new(range, field, output))
     }
 
-    pub(crate) fn band(range: (f32, f32), field: &str, output: &str) -> Self {
+    pub(crate) fn band(range: (f64, f64), field: &str, output: &str) -> Self {
         Operator::Band(BandOperator::new(range, field, output))
     }
 
@@ -166,6 +252,16 @@ impl Operator {
             Operator::Map(map) => map.evaluate(pulse).await,
             Operator::Filter(filter) => filter.evaluate(pulse).await,
             Operator::Group(group) => group.evaluate(pulse).await,
+            Operator::Aggregate(aggregate) => aggregate.evaluate(pulse).await,
+            Operator::Sort(sort) => sort.evaluate(pulse).await,
+            Operator::Window(window) => window.evaluate(pulse).await,
+            Operator::Stack(stack) => stack.evaluate(pulse).await,
+            Operator::Bin(bin) => bin.evaluate(pulse).await,
+            Operator::Lookup(lookup) => lookup.evaluate(pulse).await,
+            Operator::Fold(fold) => fold.evaluate(pulse).await,
+            Operator::Pivot(pivot) => pivot.evaluate(pulse).await,
+            Operator::Regression(regression) => regression.evaluate(pulse).await,
+            Operator::Loess(loess) => loess.evaluate(pulse).await,
             Operator::Line(line) => line.evaluate(pulse).await,
             Operator::Bar(bar) => bar.evaluate(pulse).await,
             Operator::Pie(pie) => pie.evaluate(pulse).await,
@@ -178,4 +274,38 @@ impl Operator {
             Operator::Identity(identity) => identity.evaluate(pulse).await,
         }
     }
+
+    /// Evaluate the changes of the values of the operator's single source, by updating the
+    /// operator's previous `output` in place.
+    pub(crate) fn evaluate_changes(&self, output: &mut Pulse, changes: &Changeset) -> Propagation {
+        match self {
+            Operator::Data(data) => data.evaluate_changes(output, changes),
+            Operator::Constant(constant) => constant.evaluate_changes(output, changes),
+            Operator::Map(map) => map.evaluate_changes(output, changes),
+            Operator::Filter(filter) => filter.evaluate_changes(output, changes),
+            Operator::Group(group) => group.evaluate_changes(output, changes),
+            Operator::Aggregate(aggregate) => aggregate.evaluate_changes(output, changes),
+            Operator::Sort(sort) => sort.evaluate_changes(output, changes),
+            Operator::Window(window) => window.evaluate_changes(output, changes),
+            Operator::Stack(stack) => stack.evaluate_changes(output, changes),
+            Operator::Bin(bin) => bin.evaluate_changes(output, changes),
+            Operator::Lookup(lookup) => lookup.evaluate_changes(output, changes),
+            Operator::Fold(fold) => fold.evaluate_changes(output, changes),
+            Operator::Pivot(pivot) => pivot.evaluate_changes(output, changes),
+            Operator::Regression(regression) => regression.evaluate_changes(output, changes),
+            Operator::Loess(loess) => loess.evaluate_changes(output, changes),
+            Operator::Line(line) => line.evaluate_changes(output, changes),
+            Operator::Bar(bar) => bar.evaluate_changes(output, changes),
+            Operator::Pie(pie) => pie.evaluate_changes(output, changes),
+            Operator::Point(point) => point.evaluate_changes(output, changes),
+            Operator::Axis(axis) => axis.evaluate_changes(output, changes),
+            Operator::DomainInterval(domain_interval) => {
+                domain_interval.evaluate_changes(output, changes)
+            }
+            Operator::Linear(linear) => linear.evaluate_changes(output, changes),
+            Operator::Log(log) => log.evaluate_changes(output, changes),
+            Operator::Band(band) => band.evaluate_changes(output, changes),
+            Operator::Identity(identity) => identity.evaluate_changes(output, changes),
+        }
+    }
 }
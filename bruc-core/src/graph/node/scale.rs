@@ -6,7 +6,8 @@ use crate::graph::pulse::ResolvedDomain;
 use crate::scale::Scale;
 use crate::spec::scale::domain::Domain;
 
-use crate::graph::{Evaluation, MultiPulse, Pulse, SinglePulse};
+use crate::graph::pulse::Changeset;
+use crate::graph::{Evaluation, MultiPulse, Propagation, Pulse, SinglePulse};
 
 pub(crate) const SCALE_BAND_BANDWIDTH_FIELD_NAME: &str = "bandwidth";
 
@@ -20,7 +21,7 @@ impl DomainIntervalOperator {
         DomainIntervalOperator { domain }
     }
 
-    fn resolve_domain(&self, values: &[DataValue]) -> Option<(f32, f32)> {
+    fn resolve_domain(&self, values: &[DataValue]) -> Option<(f64, f64)> {
         match &self.domain {
             Domain::Literal(values) => Some((values[0], values[1])),
             Domain::DataField { field, .. } => {
@@ -28,12 +29,13 @@ impl DomainIntervalOperator {
                     return None;
                 }
 
-                let mut min: f32 = f32::MAX;
-                let mut max: f32 = 0.0;
+                let mut min: f64 = f64::MAX;
+                let mut max: f64 = 0.0;
 
+                // Values that aren't numbers, e.g. null values, don't extend the domain
                 for value in values {
                     let Some(value) = value.get_number(field).copied() else {
-                        break;
+                        continue;
                     };
 
                     min = min.min(value);
@@ -45,7 +47,7 @@ impl DomainIntervalOperator {
         }
     }
 
-    fn apply(&self, pulse: &SinglePulse) -> Option<(f32, f32)> {
+    fn apply(&self, pulse: &SinglePulse) -> Option<(f64, f64)> {
         let SinglePulse::Data(values) = pulse else {
             return None;
         };
@@ -66,6 +68,40 @@ impl Evaluation for DomainIntervalOperator {
     async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
         self.evaluate_single(multi.aggregate()).await
     }
+
+    /// Extend the domain by the added values. The domain is only resolved again from all values,
+    /// if one of its bounds is removed.
+    fn evaluate_changes(&self, output: &mut Pulse, changes: &Changeset) -> Propagation {
+        let Domain::DataField { field, .. } = &self.domain else {
+            return Propagation::Updated;
+        };
+        let Pulse::Single(SinglePulse::Domain(ResolvedDomain::Interval(min, max))) = output else {
+            return Propagation::Unsupported;
+        };
+
+        let removed = changes
+            .rem
+            .iter()
+            .chain(changes.modified.iter().map(|(old, _)| old))
+            .filter_map(|value| value.get_number(field));
+        for value in removed {
+            if value == min || value == max {
+                return Propagation::Unsupported;
+            }
+        }
+
+        let added = changes
+            .add
+            .iter()
+            .chain(changes.modified.iter().map(|(_, new)| new))
+            .filter_map(|value| value.get_number(field));
+        for value in added {
+            *min = min.min(*value);
+            *max = max.max(*value);
+        }
+
+        Propagation::Updated
+    }
 }
 
 /// `LinearOperator` represents an operator of the graph, which linearly scales data values from a
@@ -79,7 +115,7 @@ pub struct LinearOperator {
 
 impl LinearOperator {
     /// Create a new `LinearOperator` instance.
-    pub(crate) fn new(range: (f32, f32), field: &str, output: &str) -> Self {
+    pub(crate) fn new(range: (f64, f64), field: &str, output: &str) -> Self {
         LinearOperator {
             scale: Scale::linear(range),
             field: field.to_string(),
@@ -89,21 +125,21 @@ impl LinearOperator {
 
     /// Apply the operator's logic by linearly scaling the referenced `field` and creating a new
     /// `output` field.
-    fn apply(&self, values: &[DataValue], domain: (f32, f32)) -> Vec<DataValue> {
+    fn apply(&self, values: &[DataValue], domain: (f64, f64)) -> Vec<DataValue> {
         let mut result = values.to_vec();
 
         // Iterate over the current series
         for value in &mut result {
-            // Apply scale to field
-            let scale_result = value
+            // Apply scale to field, values that aren't numbers are scaled to null
+            let scale_item = value
                 .get_number(&self.field)
-                .map(|value| self.scale.apply(*value, domain));
+                .map_or(DataItem::Null, |value| {
+                    DataItem::Number(self.scale.apply(*value, domain))
+                });
 
-            if let Some(scale_item) = scale_result {
-                // Add scale result to value with the scale's name
-                value.instance.clear();
-                value.insert(&self.output, DataItem::Number(scale_item));
-            }
+            // Add scale result to value with the scale's name
+            value.instance.clear();
+            value.insert(&self.output, scale_item);
         }
 
         result
@@ -117,7 +153,7 @@ impl Evaluation for LinearOperator {
 
     async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
         let mut values = Vec::new();
-        let mut domain: Option<(f32, f32)> = None;
+        let mut domain: Option<(f64, f64)> = None;
 
         for pulse in multi.pulses {
             match pulse {
@@ -148,7 +184,7 @@ pub struct LogOperator {
 
 impl LogOperator {
     /// Create a new `LinearOperator` instance.
-    pub(crate) fn new(range: (f32, f32), field: &str, output: &str) -> Self {
+    pub(crate) fn new(range: (f64, f64), field: &str, output: &str) -> Self {
         LogOperator {
             scale: Scale::log(range),
             field: field.to_string(),
@@ -158,21 +194,21 @@ impl LogOperator {
 
     /// Apply the operator's logic by linearly scaling the referenced `field` and creating a new
     /// `output` field.
-    fn apply(&self, values: &[DataValue], domain: (f32, f32)) -> Vec<DataValue> {
+    fn apply(&self, values: &[DataValue], domain: (f64, f64)) -> Vec<DataValue> {
         let mut result = values.to_vec();
 
         // Iterate over the current series
         for value in &mut result {
-            // Apply scale to field
-            let scale_result = value
+            // Apply scale to field, values that aren't numbers are scaled to null
+            let scale_item = value
                 .get_number(&self.field)
-                .map(|value| self.scale.apply(*value, domain));
+                .map_or(DataItem::Null, |value| {
+                    DataItem::Number(self.scale.apply(*value, domain))
+                });
 
-            if let Some(scale_item) = scale_result {
-                // Add scale result to value with the scale's name
-                value.instance.clear();
-                value.insert(&self.output, DataItem::Number(scale_item));
-            }
+            // Add scale result to value with the scale's name
+            value.instance.clear();
+            value.insert(&self.output, scale_item);
         }
 
         result
@@ -186,7 +222,7 @@ impl Evaluation for LogOperator {
 
     async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
         let mut values = Vec::new();
-        let mut domain: Option<(f32, f32)> = None;
+        let mut domain: Option<(f64, f64)> = None;
 
         for pulse in multi.pulses {
             match pulse {
@@ -212,14 +248,14 @@ impl Evaluation for LogOperator {
 /// of the new field with the result of the operator.
 #[derive(Debug, PartialEq)]
 pub struct BandOperator {
-    range: (f32, f32),
+    range: (f64, f64),
     field: String,
     output: String,
 }
 
 impl BandOperator {
     /// Create a new `BandOperator` instance.
-    pub(crate) fn new(range: (f32, f32), field: &str, output: &str) -> Self {
+    pub(crate) fn new(range: (f64, f64), field: &str, output: &str) -> Self {
         BandOperator {
             range,
             field: field.to_string(),
@@ -229,7 +265,7 @@ impl BandOperator {
 
     // Apply the operator's logic to map the discrete domain into the range. The result is assigned
     // to a variable in the data value with the `output` name.
-    fn apply(&self, values: &[DataValue], domain: (f32, f32)) -> Vec<DataValue> {
+    fn apply(&self, values: &[DataValue], domain: (f64, f64)) -> Vec<DataValue> {
         let mut result = values.to_vec();
 
         let values_count = domain.1 - domain.0 + 1.0;
@@ -247,11 +283,14 @@ impl BandOperator {
                 .and_then(|value| value.get_number())
                 .map(|value| scale.apply(*value, domain));
 
+            // Add scale result to value with the scale's name, values that aren't numbers are
+            // scaled to null
+            value.instance.clear();
             if let Some(scale_item) = scale_result {
-                // Add scale result to value with the scale's name
-                value.instance.clear();
                 value.insert(&self.output, DataItem::Number(scale_item));
                 value.insert(&bandwidth_name, DataItem::Number(bandwidth));
+            } else {
+                value.insert(&self.output, DataItem::Null);
             }
         }
 
@@ -266,7 +305,7 @@ impl Evaluation for BandOperator {
 
     async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
         let mut values = Vec::new();
-        let mut interval: Option<(f32, f32)> = None;
+        let mut interval: Option<(f64, f64)> = None;
 
         for pulse in multi.pulses {
             match pulse {
@@ -311,14 +350,14 @@ impl IdentityOperator {
 
         // Iterate over the current series
         for value in values {
-            // Find field in data value
-            if let Some(item) = value.get(&self.field) {
-                // Add result to value with the output's name
-                let mut new_value = DataValue::new();
-                new_value.insert(&self.output, item.clone());
+            // Find field in data value, missing fields are copied as null
+            let item = value.get(&self.field).cloned().unwrap_or(DataItem::Null);
 
-                result.push(new_value);
-            }
+            // Add result to value with the output's name
+            let mut new_value = DataValue::new();
+            new_value.insert(&self.output, item);
+
+            result.push(new_value);
         }
 
         result
@@ -342,15 +381,17 @@ impl Evaluation for IdentityOperator {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
 
+    use bruc_expression::data::DataItem;
     use futures::FutureExt;
 
     use crate::{
         data::DataValue,
         graph::{
             node::scale::{IdentityOperator, LogOperator},
-            pulse::ResolvedDomain,
-            Evaluation, Pulse, SinglePulse,
+            pulse::{Changeset, ResolvedDomain},
+            Evaluation, Propagation, Pulse, SinglePulse,
         },
         spec::scale::domain::Domain,
     };
@@ -383,6 +424,45 @@ mod tests {
         assert_eq!(pulse, Pulse::domain(ResolvedDomain::Interval(-2.0, 15.0)));
     }
 
+    #[tokio::test]
+    async fn domain_applies_for_nested_data_field() {
+        let series = vec![
+            DataValue::from_pairs(vec![(
+                "a",
+                DataItem::Object(BTreeMap::from([("b".to_string(), (-2.0).into())])),
+            )]),
+            DataValue::from_pairs(vec![(
+                "a",
+                DataItem::Object(BTreeMap::from([("b".to_string(), 15.0.into())])),
+            )]),
+        ];
+
+        let operator = DomainIntervalOperator::new(Domain::DataField {
+            data: "primary".to_string(),
+            field: "a.b".to_string(),
+        });
+        let pulse = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(pulse, Pulse::domain(ResolvedDomain::Interval(-2.0, 15.0)));
+    }
+
+    #[tokio::test]
+    async fn domain_skips_null_values() {
+        let series = vec![
+            DataValue::from_pairs(vec![("a", 2.0.into())]),
+            DataValue::from_pairs(vec![("a", DataItem::Null)]),
+            DataValue::from_pairs(vec![("a", 15.0.into())]),
+        ];
+
+        let operator = DomainIntervalOperator::new(Domain::DataField {
+            data: "primary".to_string(),
+            field: "a".to_string(),
+        });
+        let pulse = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(pulse, Pulse::domain(ResolvedDomain::Interval(2.0, 15.0)));
+    }
+
     #[tokio::test]
     async fn domain_handles_empty_data() {
         let operator = DomainIntervalOperator::new(Domain::DataField {
@@ -453,13 +533,35 @@ mod tests {
         assert_eq!(
             pulse,
             Pulse::data(vec![
-                DataValue::from_pairs(vec![("a", true.into()), ("b", 1.0.into())]),
-                DataValue::from_pairs(vec![("a", false.into()), ("b", 1.0.into())]),
+                DataValue::from_pairs(vec![("x", DataItem::Null)]),
+                DataValue::from_pairs(vec![("x", DataItem::Null)]),
                 DataValue::from_pairs(vec![("x", 0.2.into())]),
             ])
         );
     }
 
+    #[tokio::test]
+    async fn linear_scales_null_values_to_null() {
+        let data = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![("a", 5.0.into())]),
+            DataValue::from_pairs(vec![("a", DataItem::Null)]),
+            DataValue::from_pairs(vec![("b", 1.0.into())]),
+        ]);
+        let domain = SinglePulse::Domain(ResolvedDomain::Interval(0.0, 10.0));
+
+        let operator = LinearOperator::new((0.0, 1.0), "a", "x");
+        let pulse = operator.evaluate(Pulse::multi(vec![data, domain])).await;
+
+        assert_eq!(
+            pulse,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![("x", 0.5.into())]),
+                DataValue::from_pairs(vec![("x", DataItem::Null)]),
+                DataValue::from_pairs(vec![("x", DataItem::Null)]),
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn linear_handles_empty_data() {
         let operator = LinearOperator::new((0.0, 1.0), "a", "x");
@@ -554,4 +656,29 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn domain_evaluates_changes() {
+        let value = |a: f64| DataValue::from_pairs(vec![("a", a.into())]);
+        let operator = DomainIntervalOperator::new(Domain::DataField {
+            data: "primary".to_string(),
+            field: "a".to_string(),
+        });
+        let mut output = Pulse::domain(ResolvedDomain::Interval(-2.0, 15.0));
+
+        let propagation = operator.evaluate_changes(
+            &mut output,
+            &Changeset::new()
+                .with_add(vec![value(20.0)])
+                .with_rem(vec![value(5.0)])
+                .with_modified(vec![(value(10.0), value(-4.0))]),
+        );
+        assert_eq!(propagation, Propagation::Updated);
+        assert_eq!(output, Pulse::domain(ResolvedDomain::Interval(-4.0, 20.0)));
+
+        let propagation =
+            operator.evaluate_changes(&mut output, &Changeset::new().with_rem(vec![value(20.0)]));
+        assert_eq!(propagation, Propagation::Unsupported);
+        assert_eq!(output, Pulse::domain(ResolvedDomain::Interval(-4.0, 20.0)));
+    }
 }
@@ -1,4 +1,6 @@
-use core::f32;
+use core::f64;
+
+use bruc_expression::data::DataSource;
 
 use crate::data::DataValue;
 use crate::graph::node::scale::SCALE_BAND_BANDWIDTH_FIELD_NAME;
@@ -6,7 +8,8 @@ use crate::graph::{Evaluation, MultiPulse, Pulse, SinglePulse};
 use crate::scene::SceneItem;
 use crate::spec::shape::bar::BarShape;
 use crate::spec::shape::base::{
-    HEIGHT_FIELD_NAME, WIDTH_FIELD_NAME, X_AXIS_FIELD_NAME, Y_AXIS_FIELD_NAME,
+    HEIGHT_FIELD_NAME, WIDTH_FIELD_NAME, X2_AXIS_FIELD_NAME, X_AXIS_FIELD_NAME, Y2_AXIS_FIELD_NAME,
+    Y_AXIS_FIELD_NAME,
 };
 use crate::spec::shape::line::LineShape;
 use crate::spec::shape::pie::PieShape;
@@ -14,19 +17,29 @@ use crate::util::radians_to_degrees;
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct SceneWindow {
-    pub(crate) width: f32,
-    pub(crate) height: f32,
+    pub(crate) width: f64,
+    pub(crate) height: f64,
 }
 
 impl SceneWindow {
     pub fn new(width: usize, height: usize) -> Self {
         SceneWindow {
-            width: width as f32,
-            height: height as f32,
+            width: width as f64,
+            height: height as f64,
         }
     }
 }
 
+/// Read a position `field` out of a data pulse value. Missing positions default to `0.0`, while
+/// positions that are null, or not a valid number, can't be read.
+fn read_position(value: &DataValue, field: &str) -> Option<f64> {
+    match value.get(field) {
+        Some(item) if item.is_valid() => item.get_number().copied(),
+        Some(_) => None,
+        None => Some(0.0),
+    }
+}
+
 /// `LineOperator` represents an operator of the graph, which generates a `LineShape` instance from
 /// the incoming `Pulse` instance.
 #[derive(Debug, PartialEq)]
@@ -48,11 +61,6 @@ impl LineOperator {
             return Vec::new();
         };
 
-        let points = values
-            .iter()
-            .map(|value| LineOperator::read_point(value, &self.window))
-            .collect();
-
         let stroke = self
             .shape
             .props
@@ -62,18 +70,37 @@ impl LineOperator {
 
         let stroke_width = self.shape.props.stroke_width.unwrap_or(1.0);
 
-        vec![SceneItem::line(points, stroke, stroke_width)]
+        // Break the line into segments on points that can't be read, e.g. null positions
+        let mut segments = Vec::new();
+        let mut points = Vec::new();
+
+        for value in values {
+            if let Some(point) = LineOperator::read_point(value, &self.window) {
+                points.push(point);
+            } else if !points.is_empty() {
+                segments.push(std::mem::take(&mut points));
+            }
+        }
+
+        if !points.is_empty() || segments.is_empty() {
+            segments.push(points);
+        }
+
+        segments
+            .into_iter()
+            .map(|points| SceneItem::line(points, stroke.clone(), stroke_width))
+            .collect()
     }
 
     /// Read a point out of a data pulse value
-    fn read_point(value: &DataValue, window: &SceneWindow) -> (f32, f32) {
+    fn read_point(value: &DataValue, window: &SceneWindow) -> Option<(f64, f64)> {
         // Read "x" field
-        let x = value.get_number(X_AXIS_FIELD_NAME).copied().unwrap_or(0.0);
+        let x = read_position(value, X_AXIS_FIELD_NAME)?;
 
         // Read "y" field
-        let y = value.get_number(Y_AXIS_FIELD_NAME).copied().unwrap_or(0.0);
+        let y = read_position(value, Y_AXIS_FIELD_NAME)?;
 
-        (x, window.height - y)
+        Some((x, window.height - y))
     }
 }
 
@@ -106,12 +133,16 @@ impl BarOperator {
             return Vec::new();
         };
 
-        values.iter().map(|value| self.read_rect(value)).collect()
+        values
+            .iter()
+            .filter_map(|value| self.read_rect(value))
+            .collect()
     }
 
-    fn read_rect(&self, value: &DataValue) -> SceneItem {
-        let x = value.get_number(X_AXIS_FIELD_NAME).copied().unwrap_or(0.0);
-        let y = value.get_number(Y_AXIS_FIELD_NAME).copied().unwrap_or(0.0);
+    /// Read a rect out of a data pulse value. Values with null positions are skipped.
+    fn read_rect(&self, value: &DataValue) -> Option<SceneItem> {
+        let x = read_position(value, X_AXIS_FIELD_NAME)?;
+        let y = read_position(value, Y_AXIS_FIELD_NAME)?;
         let width = value.get_number(WIDTH_FIELD_NAME).copied();
         let height = value.get_number(HEIGHT_FIELD_NAME).copied();
         let fill = self.shape.props.fill.clone();
@@ -124,14 +155,37 @@ impl BarOperator {
             format!("{}_{}", Y_AXIS_FIELD_NAME, SCALE_BAND_BANDWIDTH_FIELD_NAME);
         let y_bandwidth = value.get_number(&vertical_bandwidth_name).copied();
 
-        let width = Self::calculate_dimension_with_bandwidth(width, x_bandwidth);
-        let height = Self::calculate_dimension_with_bandwidth(height, y_bandwidth);
-        let y = (self.window.height - y - height).max(0.0);
+        // Bars with an `x2` position span from `x` to `x2`, instead of growing from `x` by their
+        // width
+        let (x, width) = match value.get(X2_AXIS_FIELD_NAME) {
+            Some(_) => {
+                let x2 = read_position(value, X2_AXIS_FIELD_NAME)?;
+                (x.min(x2), (x2 - x).abs())
+            }
+            None => (
+                x,
+                Self::calculate_dimension_with_bandwidth(width, x_bandwidth),
+            ),
+        };
 
-        SceneItem::rect(width, height, x, y, fill)
+        // Bars with a `y2` position span from `y` to `y2`, instead of growing from `y` by their
+        // height
+        let (top, height) = match value.get(Y2_AXIS_FIELD_NAME) {
+            Some(_) => {
+                let y2 = read_position(value, Y2_AXIS_FIELD_NAME)?;
+                (y.max(y2), (y2 - y).abs())
+            }
+            None => {
+                let height = Self::calculate_dimension_with_bandwidth(height, y_bandwidth);
+                (y + height, height)
+            }
+        };
+        let y = (self.window.height - top).max(0.0);
+
+        Some(SceneItem::rect(width, height, x, y, fill))
     }
 
-    fn calculate_dimension_with_bandwidth(dimension: Option<f32>, bandwidth: Option<f32>) -> f32 {
+    fn calculate_dimension_with_bandwidth(dimension: Option<f64>, bandwidth: Option<f64>) -> f64 {
         match (dimension, bandwidth) {
             (Some(dimension), Some(bandwidth)) => dimension.min(bandwidth),
             (Some(dimension), None) => dimension,
@@ -155,14 +209,14 @@ pub(crate) const PIE_VALUE_FIELD_NAME: &str = "__pie_value";
 pub(crate) const PIE_OUTER_RADIUS_FIELD_NAME: &str = "__pie_outer_radius";
 
 struct ArcDataValue {
-    value: f32,
-    outer_radius: Option<f32>,
+    value: f64,
+    outer_radius: Option<f64>,
 }
 
 struct ArcPosition {
-    start: f32,
-    end: f32,
-    outer_radius: Option<f32>,
+    start: f64,
+    end: f64,
+    outer_radius: Option<f64>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -206,7 +260,7 @@ impl PieOperator {
             })
             .collect();
 
-        let total: f32 = arc_values.iter().map(|arc_value| arc_value.value).sum();
+        let total: f64 = arc_values.iter().map(|arc_value| arc_value.value).sum();
 
         let mut angles: Vec<ArcPosition> = Vec::with_capacity(values.len());
         let mut previous = 0.0;
@@ -287,15 +341,19 @@ impl PointOperator {
             return Vec::new();
         };
 
-        values.iter().map(|value| self.read_point(value)).collect()
+        values
+            .iter()
+            .filter_map(|value| self.read_point(value))
+            .collect()
     }
 
-    fn read_point(&self, value: &DataValue) -> SceneItem {
+    /// Read a point out of a data pulse value. Values with null positions are skipped.
+    fn read_point(&self, value: &DataValue) -> Option<SceneItem> {
         // Read "x" field
-        let x = value.get_number(X_AXIS_FIELD_NAME).copied().unwrap_or(0.0);
+        let x = read_position(value, X_AXIS_FIELD_NAME)?;
 
         // Read "y" field
-        let y = value.get_number(Y_AXIS_FIELD_NAME).copied().unwrap_or(0.0);
+        let y = read_position(value, Y_AXIS_FIELD_NAME)?;
         let y = self.window.height - y;
 
         // Read "color" field
@@ -310,7 +368,7 @@ impl PointOperator {
             .copied()
             .unwrap_or(3.0);
 
-        SceneItem::point(x, y, size, color)
+        Some(SceneItem::point(x, y, size, color))
     }
 }
 
@@ -326,6 +384,8 @@ impl Evaluation for PointOperator {
 
 #[cfg(test)]
 mod tests {
+    use bruc_expression::data::DataItem;
+
     use crate::data::DataValue;
     use crate::graph::node::shape::{
         BarOperator, LineOperator, PieOperator, PointOperator, SceneWindow,
@@ -377,6 +437,32 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn computes_line_with_null_values() {
+        let pulse = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![("x", 2.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", DataItem::Null)]),
+            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", DataItem::Null), ("y", 1.0.into())]),
+        ]);
+
+        let operator = LineOperator::new(
+            LineShape::new(LinePropertiesBuilder::new().build()),
+            SceneWindow::new(20, 2),
+        );
+
+        let pulse = operator.evaluate(Pulse::Single(pulse)).await;
+
+        assert_eq!(
+            pulse,
+            Pulse::shapes(vec![
+                SceneItem::line(vec![(2.0, 1.0), (5.0, 1.0)], "black".to_string(), 1.0),
+                SceneItem::line(vec![(15.0, 1.0)], "black".to_string(), 1.0)
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn computes_bar() {
         let pulse = SinglePulse::Data(vec![
@@ -418,6 +504,131 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn computes_bar_with_null_values() {
+        let pulse = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![
+                ("x", 0.0.into()),
+                ("y", 0.0.into()),
+                ("width", 5.0.into()),
+                ("height", 3.0.into()),
+            ]),
+            DataValue::from_pairs(vec![
+                ("x", DataItem::Null),
+                ("y", 0.0.into()),
+                ("width", 5.0.into()),
+                ("height", 7.0.into()),
+            ]),
+        ]);
+
+        let operator = BarOperator::new(
+            BarShape::new(
+                BarPropertiesBuilder::new()
+                    .with_width(DataSource::field("x", Some("xscale")))
+                    .with_height(DataSource::field("y", Some("yscale")))
+                    .with_fill("red")
+                    .build(),
+            ),
+            SceneWindow::new(20, 2),
+        );
+
+        let result = operator.evaluate(Pulse::Single(pulse)).await;
+
+        assert_eq!(
+            result,
+            Pulse::shapes(vec![SceneItem::rect(5.0, 3.0, 0.0, 0.0, "red".to_string())])
+        )
+    }
+
+    #[tokio::test]
+    async fn computes_bar_spanning_x2() {
+        let pulse = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![
+                ("x", 0.0.into()),
+                ("x2", 5.0.into()),
+                ("y", 0.0.into()),
+                ("height", 3.0.into()),
+            ]),
+            DataValue::from_pairs(vec![
+                ("x", 10.0.into()),
+                ("x2", 5.0.into()),
+                ("y", 0.0.into()),
+                ("width", 1.0.into()),
+                ("height", 4.0.into()),
+            ]),
+        ]);
+
+        let operator = BarOperator::new(
+            BarShape::new(
+                BarPropertiesBuilder::new()
+                    .with_x(DataSource::field("bin0", Some("xscale")))
+                    .with_x2(DataSource::field("bin1", Some("xscale")))
+                    .with_height(DataSource::field("count", Some("yscale")))
+                    .with_fill("red")
+                    .build(),
+            ),
+            SceneWindow::new(20, 20),
+        );
+
+        let result = operator.evaluate(Pulse::Single(pulse)).await;
+
+        assert_eq!(
+            result,
+            Pulse::shapes(vec![
+                SceneItem::rect(5.0, 3.0, 0.0, 17.0, "red".to_string()),
+                SceneItem::rect(5.0, 4.0, 5.0, 16.0, "red".to_string())
+            ])
+        )
+    }
+
+    #[tokio::test]
+    async fn computes_bar_spanning_y2() {
+        let pulse = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![
+                ("x", 0.0.into()),
+                ("y", 0.0.into()),
+                ("y2", 4.0.into()),
+                ("width", 5.0.into()),
+            ]),
+            DataValue::from_pairs(vec![
+                ("x", 0.0.into()),
+                ("y", 10.0.into()),
+                ("y2", 4.0.into()),
+                ("width", 5.0.into()),
+                ("height", 1.0.into()),
+            ]),
+            DataValue::from_pairs(vec![
+                ("x", 0.0.into()),
+                ("y", 4.0.into()),
+                ("y2", DataItem::Null),
+                ("width", 5.0.into()),
+            ]),
+        ]);
+
+        let operator = BarOperator::new(
+            BarShape::new(
+                BarPropertiesBuilder::new()
+                    .with_x(DataSource::field("x", Some("xscale")))
+                    .with_y(DataSource::field("y0", Some("yscale")))
+                    .with_y2(DataSource::field("y1", Some("yscale")))
+                    .with_width(DataSource::value(5.0.into()))
+                    .with_fill("red")
+                    .build(),
+            ),
+            SceneWindow::new(20, 20),
+        );
+
+        let result = operator.evaluate(Pulse::Single(pulse)).await;
+
+        assert_eq!(
+            result,
+            Pulse::shapes(vec![
+                SceneItem::rect(5.0, 4.0, 0.0, 16.0, "red".to_string()),
+                SceneItem::rect(5.0, 6.0, 0.0, 10.0, "red".to_string())
+            ])
+        )
+    }
+
     #[tokio::test]
     async fn computes_pie() {
         let pulse = SinglePulse::Data(vec![
@@ -440,8 +651,8 @@ mod tests {
             Pulse::shapes(vec![
                 SceneItem::arc(0.0, 0.0, 10.0, 0.0, None, "#1F77B4".to_string()),
                 SceneItem::arc(0.0, 36.0, 10.0, 0.0, None, "#FF7F0E".to_string()),
-                SceneItem::arc(36.0, 252.00002, 10.0, 0.0, None, "#2CA02C".to_string()),
-                SceneItem::arc(252.00002, 360.00003, 10.0, 0.0, None, "#D62728".to_string()),
+                SceneItem::arc(36.0, 252.0, 10.0, 0.0, None, "#2CA02C".to_string()),
+                SceneItem::arc(252.0, 360.0, 10.0, 0.0, None, "#D62728".to_string()),
             ])
         )
     }
@@ -458,7 +669,7 @@ mod tests {
         let operator = PieOperator::new(
             PieShape::new(
                 PiePropertiesBuilder::new(DataSource::field("y", None))
-                    .with_pad_angle(0.174_532_92) // 10 degrees
+                    .with_pad_angle(10.0_f64.to_radians())
                     .build(),
             ),
             "y",
@@ -472,8 +683,8 @@ mod tests {
             Pulse::shapes(vec![
                 SceneItem::arc(0.0, 0.0, 10.0, 0.0, None, "#1F77B4".to_string()),
                 SceneItem::arc(10.0, 26.0, 10.0, 0.0, None, "#FF7F0E".to_string()),
-                SceneItem::arc(46.0, 242.00002, 10.0, 0.0, None, "#2CA02C".to_string()),
-                SceneItem::arc(262.0, 350.00003, 10.0, 0.0, None, "#D62728".to_string()),
+                SceneItem::arc(46.0, 242.0, 10.0, 0.0, None, "#2CA02C".to_string()),
+                SceneItem::arc(262.0, 350.0, 10.0, 0.0, None, "#D62728".to_string()),
             ])
         )
     }
@@ -504,8 +715,8 @@ mod tests {
             Pulse::shapes(vec![
                 SceneItem::arc(0.0, 0.0, 10.0, 2.0, None, "#1F77B4".to_string()),
                 SceneItem::arc(0.0, 36.0, 10.0, 2.0, None, "#FF7F0E".to_string()),
-                SceneItem::arc(36.0, 252.00002, 10.0, 2.0, None, "#2CA02C".to_string()),
-                SceneItem::arc(252.00002, 360.00003, 10.0, 2.0, None, "#D62728".to_string()),
+                SceneItem::arc(36.0, 252.0, 10.0, 2.0, None, "#2CA02C".to_string()),
+                SceneItem::arc(252.0, 360.0, 10.0, 2.0, None, "#D62728".to_string()),
             ])
         )
     }
@@ -552,15 +763,8 @@ mod tests {
             Pulse::shapes(vec![
                 SceneItem::arc(0.0, 0.0, 10.0, 0.0, Some(2.0), "#1F77B4".to_string()),
                 SceneItem::arc(0.0, 36.0, 10.0, 0.0, Some(2.0), "#FF7F0E".to_string()),
-                SceneItem::arc(36.0, 252.00002, 10.0, 0.0, Some(2.0), "#2CA02C".to_string()),
-                SceneItem::arc(
-                    252.00002,
-                    360.00003,
-                    10.0,
-                    0.0,
-                    Some(2.0),
-                    "#D62728".to_string()
-                ),
+                SceneItem::arc(36.0, 252.0, 10.0, 0.0, Some(2.0), "#2CA02C".to_string()),
+                SceneItem::arc(252.0, 360.0, 10.0, 0.0, Some(2.0), "#D62728".to_string()),
             ])
         )
     }
@@ -596,4 +800,25 @@ mod tests {
             ])
         );
     }
+
+    #[tokio::test]
+    async fn computes_point_with_null_values() {
+        let pulse = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![("x", 2.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", DataItem::Null)]),
+            DataValue::from_pairs(vec![("x", 10.0.into())]),
+        ]);
+
+        let operator = PointOperator::new(SceneWindow::new(20, 2));
+
+        let pulse = operator.evaluate(Pulse::Single(pulse)).await;
+
+        assert_eq!(
+            pulse,
+            Pulse::shapes(vec![
+                SceneItem::point(2.0, 1.0, 3.0, "blue".to_string()),
+                SceneItem::point(10.0, 2.0, 3.0, "blue".to_string())
+            ])
+        );
+    }
 }
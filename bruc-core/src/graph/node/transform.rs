@@ -1,14 +1,26 @@
-use std::{collections::HashMap, ops::AddAssign};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::AddAssign;
 
 use bruc_expression::data::{DataItem, DataSource};
 
 use crate::{
     data::DataValue,
-    graph::{Evaluation, MultiPulse, Pulse, SinglePulse},
+    graph::{pulse::Changeset, Evaluation, MultiPulse, Propagation, Pulse, SinglePulse},
     spec::transform::{
+        aggregate::{AggregateOp, AggregatePipe},
+        bin::BinPipe,
         filter::FilterPipe,
+        fold::FoldPipe,
         group::{GroupOperator as GroupOperatorSpec, GroupPipe},
+        loess::LoessPipe,
+        lookup::LookupPipe,
         map::MapPipe,
+        pivot::PivotPipe,
+        regression::{RegressionPipe, COEFFICIENTS_FIELD_NAME, R_SQUARED_FIELD_NAME},
+        sort::SortPipe,
+        stack::{StackOffset, StackPipe},
+        window::{WindowField, WindowFunction, WindowOp, WindowPipe},
     },
 };
 
@@ -36,6 +48,12 @@ impl MapOperator {
         }
         result
     }
+
+    fn map(&self, value: &DataValue) -> DataValue {
+        let mut value = value.clone();
+        self.pipe.apply(&mut value);
+        value
+    }
 }
 
 impl Evaluation for MapOperator {
@@ -51,6 +69,30 @@ impl Evaluation for MapOperator {
 
         Pulse::data(values)
     }
+
+    /// Map only the changed values, as long as mapping is deterministic, so that removed values
+    /// map into the values present in the output.
+    fn evaluate_changes(&self, output: &mut Pulse, changes: &Changeset) -> Propagation {
+        if !self.pipe.is_deterministic() {
+            return Propagation::Unsupported;
+        }
+
+        let changes = Changeset {
+            add: changes.add.iter().map(|value| self.map(value)).collect(),
+            rem: changes.rem.iter().map(|value| self.map(value)).collect(),
+            modified: changes
+                .modified
+                .iter()
+                .map(|(old, new)| (self.map(old), self.map(new)))
+                .collect(),
+        };
+
+        if output.apply_changes(&changes) {
+            Propagation::Changes(changes)
+        } else {
+            Propagation::Unsupported
+        }
+    }
 }
 
 /// `FilterOperator` represents an operator of the graph, which filters out certain data values from
@@ -93,6 +135,41 @@ impl Evaluation for FilterOperator {
 
         Pulse::data(values)
     }
+
+    /// Filter only the changed values, as long as filtering is deterministic. Modified values
+    /// passing the filter only after the modification are added at the end of the output.
+    fn evaluate_changes(&self, output: &mut Pulse, changes: &Changeset) -> Propagation {
+        if !self.pipe.is_deterministic() {
+            return Propagation::Unsupported;
+        }
+
+        let filter = |values: &[DataValue]| {
+            values
+                .iter()
+                .filter(|value| self.pipe.apply(value))
+                .cloned()
+                .collect()
+        };
+
+        let mut result = Changeset::new()
+            .with_add(filter(&changes.add))
+            .with_rem(filter(&changes.rem));
+
+        for (old, new) in &changes.modified {
+            match (self.pipe.apply(old), self.pipe.apply(new)) {
+                (true, true) => result.modified.push((old.clone(), new.clone())),
+                (true, false) => result.rem.push(old.clone()),
+                (false, true) => result.add.push(new.clone()),
+                (false, false) => {}
+            }
+        }
+
+        if output.apply_changes(&result) {
+            Propagation::Changes(result)
+        } else {
+            Propagation::Unsupported
+        }
+    }
 }
 
 /// `GroupOperator` represents an operator of the graph, which applies a certain grouping logic to
@@ -125,6 +202,12 @@ impl Evaluation for GroupOperator {
             GroupOperator::Count(count) => count.evaluate_multi(multi).await,
         }
     }
+
+    fn evaluate_changes(&self, output: &mut Pulse, changes: &Changeset) -> Propagation {
+        match self {
+            GroupOperator::Count(count) => count.evaluate_changes(output, changes),
+        }
+    }
 }
 
 /// `CountOperator` represents a type of `GroupOperator`, which groups the incoming pulse data in
@@ -166,12 +249,19 @@ impl CountOperator {
         for (var, count) in counts {
             result.push(DataValue::from_pairs(vec![
                 (&self.by, var),
-                (&self.output, DataItem::Number(count as f32)),
+                (&self.output, DataItem::Number(count as f64)),
             ]));
         }
 
         result
     }
+
+    fn group(&self, var: DataItem, count: f64) -> DataValue {
+        DataValue::from_pairs(vec![
+            (&self.by, var),
+            (&self.output, DataItem::Number(count)),
+        ])
+    }
 }
 
 impl Evaluation for CountOperator {
@@ -187,167 +277,2230 @@ impl Evaluation for CountOperator {
 
         Pulse::data(values)
     }
+
+    /// Update only the counts of the groups of the changed values. Groups are added once their
+    /// first value is added, and removed once their last value is removed.
+    fn evaluate_changes(&self, output: &mut Pulse, changes: &Changeset) -> Propagation {
+        let Pulse::Single(SinglePulse::Data(groups)) = output else {
+            return Propagation::Unsupported;
+        };
+
+        // Collect the change of the count of each group, keeping the order of the changes
+        let mut deltas: Vec<(DataItem, f64)> = Vec::new();
+        let mut count = |value: &DataValue, delta: f64| {
+            let Some(target) = value.get(&self.by) else {
+                return;
+            };
+
+            match deltas.iter_mut().find(|(var, _)| var == target) {
+                Some((_, count)) => count.add_assign(delta),
+                None => deltas.push((target.clone(), delta)),
+            }
+        };
+
+        for value in &changes.add {
+            count(value, 1.0);
+        }
+        for value in &changes.rem {
+            count(value, -1.0);
+        }
+        for (old, new) in &changes.modified {
+            count(old, -1.0);
+            count(new, 1.0);
+        }
+
+        let mut result = Changeset::new();
+        for (var, delta) in deltas {
+            if delta == 0.0 {
+                continue;
+            }
+
+            let group = groups
+                .iter()
+                .find(|group| group.get(&self.by) == Some(&var))
+                .cloned();
+            let previous = group
+                .as_ref()
+                .and_then(|group| group.get_number(&self.output))
+                .copied()
+                .unwrap_or(0.0);
+
+            match group {
+                Some(group) if previous + delta <= 0.0 => result.rem.push(group),
+                Some(group) => result
+                    .modified
+                    .push((group, self.group(var, previous + delta))),
+                None if delta > 0.0 => result.add.push(self.group(var, delta)),
+                None => {}
+            }
+        }
+
+        result.apply(groups);
+        Propagation::Changes(result)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::spec::transform::group::GroupOperator as GroupOperatorSpec;
-    use crate::{
-        data::DataValue,
-        graph::{
-            node::transform::{FilterOperator, GroupOperator, MapOperator},
-            Evaluation, Pulse, SinglePulse,
-        },
-        spec::transform::{filter::FilterPipe, group::GroupPipe, map::MapPipe},
-    };
+/// `SortOperator` represents an operator of the graph, which orders the incoming pulse values by
+/// a given sort pipe.
+#[derive(Debug, PartialEq)]
+pub struct SortOperator {
+    pipe: SortPipe,
+}
 
-    #[tokio::test]
-    async fn applies_map_single_pulse() {
-        let series = vec![
-            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
-        ];
+impl SortOperator {
+    /// Create a new `SortOperator` instance with a certain sort pipe.
+    pub(crate) fn new(pipe: SortPipe) -> Self {
+        SortOperator { pipe }
+    }
+}
 
-        let operator = MapOperator::new(MapPipe::new("x + 2 * y", "z").unwrap());
+impl Evaluation for SortOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        let SinglePulse::Data(mut values) = single else {
+            return Pulse::data(Vec::new());
+        };
 
-        let result = operator.evaluate(Pulse::data(series)).await;
+        self.pipe.apply(&mut values);
+        Pulse::data(values)
+    }
 
-        assert_eq!(
-            result,
-            Pulse::data(vec![
-                DataValue::from_pairs(vec![
-                    ("x", (-2.0).into()),
-                    ("y", 1.0.into()),
-                    ("z", 0.0.into())
-                ]),
-                DataValue::from_pairs(vec![
-                    ("x", 5.0.into()),
-                    ("y", 1.0.into()),
-                    ("z", 7.0.into())
-                ]),
-                DataValue::from_pairs(vec![
-                    ("x", 10.0.into()),
-                    ("y", 1.0.into()),
-                    ("z", 12.0.into())
-                ]),
-                DataValue::from_pairs(vec![
-                    ("x", 15.0.into()),
-                    ("y", 1.0.into()),
-                    ("z", 17.0.into())
-                ]),
-            ])
-        );
+    /// Sort the values of all pulses together.
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let mut values = multi.pulses.into_iter().fold(Vec::new(), |mut acc, pulse| {
+            if let SinglePulse::Data(values) = pulse {
+                acc.extend(values);
+            }
+            acc
+        });
+
+        self.pipe.apply(&mut values);
+        Pulse::data(values)
     }
+}
 
-    #[tokio::test]
-    async fn applies_map_multi_pulse() {
-        let first = SinglePulse::Data(vec![
-            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
-        ]);
-        let second = SinglePulse::Data(vec![
-            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
-        ]);
+/// `WindowOperator` represents an operator of the graph, which computes window operations for
+/// each incoming pulse value over the sorted values of its group, by a given window pipe.
+#[derive(Debug, PartialEq)]
+pub struct WindowOperator {
+    pipe: WindowPipe,
+}
 
-        let operator = MapOperator::new(MapPipe::new("x + 2 * y", "z").unwrap());
+impl WindowOperator {
+    /// Create a new `WindowOperator` instance with a certain window pipe.
+    pub(crate) fn new(pipe: WindowPipe) -> Self {
+        WindowOperator { pipe }
+    }
 
-        let result = operator.evaluate(Pulse::multi(vec![first, second])).await;
+    /// Apply the operator's logic by grouping the pulse data values by the `groupby` fields,
+    /// sorting each group, and adding the results of the operations to each value. Values keep
+    /// their incoming order.
+    fn apply(&self, pulse: &SinglePulse) -> Vec<DataValue> {
+        let SinglePulse::Data(values) = pulse else {
+            return Vec::new();
+        };
+        let sort = SortPipe::new(self.pipe.sort.clone());
 
-        assert_eq!(
-            result,
-            Pulse::data(vec![
-                DataValue::from_pairs(vec![
-                    ("x", (-2.0).into()),
-                    ("y", 1.0.into()),
-                    ("z", 0.0.into())
-                ]),
-                DataValue::from_pairs(vec![
-                    ("x", 5.0.into()),
-                    ("y", 1.0.into()),
-                    ("z", 7.0.into())
-                ]),
-                DataValue::from_pairs(vec![
-                    ("x", 10.0.into()),
-                    ("y", 1.0.into()),
-                    ("z", 12.0.into())
-                ]),
-                DataValue::from_pairs(vec![
-                    ("x", 15.0.into()),
-                    ("y", 1.0.into()),
-                    ("z", 17.0.into())
-                ]),
-            ])
-        );
+        let mut result = values.to_vec();
+        for group in partition(values, &self.pipe.groupby, &sort) {
+            let sorted: Vec<&DataValue> = group.iter().map(|index| &values[*index]).collect();
+
+            for op in &self.pipe.ops {
+                let items = self.compute(op, &sort, &sorted);
+                for (index, item) in group.iter().zip(items) {
+                    result[*index].insert(&op.output, item);
+                }
+            }
+        }
+
+        result
     }
 
-    #[tokio::test]
-    async fn applies_filter_single_pulse() {
-        let series = vec![
-            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
-        ];
+    /// Compute a window operation for each value of a sorted group, in a single pass over the
+    /// group where possible.
+    fn compute(&self, op: &WindowField, sort: &SortPipe, group: &[&DataValue]) -> Vec<DataItem> {
+        let field = |value: &DataValue| {
+            op.field
+                .as_deref()
+                .and_then(|field| value.get(field))
+                .cloned()
+                .unwrap_or(DataItem::Null)
+        };
+        let frames = (0..group.len()).map(|position| self.pipe.frame.range(position, group.len()));
 
-        let operator = FilterOperator::new(FilterPipe::new("x > y").unwrap());
+        let function = match op.op {
+            WindowOp::Function(function) => function,
+            WindowOp::Aggregate(
+                aggregate_op @ (AggregateOp::Count | AggregateOp::Sum | AggregateOp::Mean),
+            ) => {
+                return self.accumulate(aggregate_op, op.field.as_deref(), group);
+            }
+            WindowOp::Aggregate(aggregate_op) => {
+                return frames
+                    .map(|frame| aggregate(aggregate_op, op.field.as_deref(), &group[frame]))
+                    .collect();
+            }
+        };
 
-        let result = operator.evaluate(Pulse::data(series)).await;
+        match function {
+            WindowFunction::RowNumber => (1..=group.len())
+                .map(|number| DataItem::Number(number as f64))
+                .collect(),
+            WindowFunction::Rank | WindowFunction::DenseRank => {
+                let (mut rank, mut dense_rank) = (0, 0);
+                let mut ranks = Vec::with_capacity(group.len());
 
-        assert_eq!(
-            result,
-            Pulse::data(vec![
-                DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
-                DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
-                DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
-            ])
-        );
+                for (position, value) in group.iter().enumerate() {
+                    // Peers share the rank of the first value they are equal to
+                    let is_peer = position > 0 && sort.compare(group[position - 1], value).is_eq();
+                    if !is_peer {
+                        rank = position + 1;
+                        dense_rank += 1;
+                    }
+
+                    let number = match function {
+                        WindowFunction::Rank => rank,
+                        _ => dense_rank,
+                    };
+                    ranks.push(DataItem::Number(number as f64));
+                }
+
+                ranks
+            }
+            WindowFunction::Lag => (0..group.len())
+                .map(|position| {
+                    position
+                        .checked_sub(op.param.unwrap_or(1))
+                        .map_or(DataItem::Null, |index| field(group[index]))
+                })
+                .collect(),
+            WindowFunction::Lead => (0..group.len())
+                .map(|position| {
+                    group
+                        .get(position + op.param.unwrap_or(1))
+                        .map_or(DataItem::Null, |value| field(value))
+                })
+                .collect(),
+            WindowFunction::FirstValue => frames
+                .map(|frame| {
+                    group[frame]
+                        .first()
+                        .map_or(DataItem::Null, |value| field(value))
+                })
+                .collect(),
+            WindowFunction::LastValue => frames
+                .map(|frame| {
+                    group[frame]
+                        .last()
+                        .map_or(DataItem::Null, |value| field(value))
+                })
+                .collect(),
+        }
     }
 
-    #[tokio::test]
-    async fn applies_filter_multi_pulse() {
-        let first = SinglePulse::Data(vec![
-            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
-        ]);
-        let second = SinglePulse::Data(vec![
-            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
-            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
-        ]);
+    /// Compute a `count`, `sum` or `mean` aggregate operation over the frame of each value of a
+    /// sorted group. Frames only slide forward, so the numbers entering and leaving the frame
+    /// are added to and removed from a running total.
+    fn accumulate(
+        &self,
+        op: AggregateOp,
+        field: Option<&str>,
+        group: &[&DataValue],
+    ) -> Vec<DataItem> {
+        let number = |value: &DataValue| field.and_then(|field| value.get_number(field)).copied();
 
-        let operator = FilterOperator::new(FilterPipe::new("x > y").unwrap());
+        let (mut total, mut count) = (0.0, 0);
+        let mut frame = 0..0;
+        let mut result = Vec::with_capacity(group.len());
 
-        let result = operator.evaluate(Pulse::multi(vec![first, second])).await;
+        for position in 0..group.len() {
+            let next = self.pipe.frame.range(position, group.len());
 
-        assert_eq!(
-            result,
-            Pulse::data(vec![
-                DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
-                DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
-                DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
-            ])
-        );
+            for value in &group[frame.end.max(next.start)..next.end] {
+                if let Some(number) = number(value) {
+                    total += number;
+                    count += 1;
+                }
+            }
+            for value in &group[frame.start..next.start.min(frame.end)] {
+                if let Some(number) = number(value) {
+                    total -= number;
+                    count -= 1;
+                }
+            }
+            frame = next;
+
+            // Drop the rounding errors left by the removed numbers
+            if count == 0 {
+                total = 0.0;
+            }
+
+            let item = match op {
+                AggregateOp::Count => DataItem::Number(frame.len() as f64),
+                AggregateOp::Sum => DataItem::Number(total),
+                _ if count == 0 => DataItem::Null,
+                _ => DataItem::Number(total / count as f64),
+            };
+            result.push(item);
+        }
+
+        result
     }
+}
 
-    #[tokio::test]
-    async fn applies_group_single_pulse() {
-        let series = vec![
-            DataValue::from_pairs(vec![("a", 1.0.into()), ("b", 2.0.into())]),
-            DataValue::from_pairs(vec![("a", 1.0.into()), ("b", 2.0.into())]),
-        ];
+impl Evaluation for WindowOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        Pulse::data(self.apply(&single))
+    }
 
-        let operator = GroupOperator::new(GroupPipe::new("a", GroupOperatorSpec::Count, "count"));
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.iter().fold(Vec::new(), |mut acc, pulse| {
+            acc.extend(self.apply(pulse));
+            acc
+        });
 
-        let result = operator.evaluate(Pulse::data(series)).await;
+        Pulse::data(values)
+    }
+}
 
-        assert_eq!(
-            result,
-            Pulse::data(vec![DataValue::from_pairs(vec![
-                ("a", 1.0.into()),
-                ("count", 2.0.into())
-            ])])
+/// `StackOperator` represents an operator of the graph, which stacks the numbers of a field for
+/// the incoming pulse values of each group, by a given stack pipe.
+#[derive(Debug, PartialEq)]
+pub struct StackOperator {
+    pipe: StackPipe,
+}
+
+impl StackOperator {
+    /// Create a new `StackOperator` instance with a certain stack pipe.
+    pub(crate) fn new(pipe: StackPipe) -> Self {
+        StackOperator { pipe }
+    }
+
+    /// Apply the operator's logic by adding the start and the end of each value in the stack of
+    /// its group. Values whose field isn't a number are stacked as zero, and values keep their
+    /// incoming order.
+    fn apply(&self, pulse: &SinglePulse) -> Vec<DataValue> {
+        let SinglePulse::Data(values) = pulse else {
+            return Vec::new();
+        };
+        let sort = SortPipe::new(self.pipe.sort.clone());
+        let groups = partition(values, &self.pipe.groupby, &sort);
+
+        let number = |index: usize| {
+            let number = values[index]
+                .get_number(&self.pipe.field)
+                .copied()
+                .unwrap_or(0.0);
+
+            match self.pipe.offset {
+                StackOffset::Zero => number,
+                StackOffset::Center | StackOffset::Normalize => number.abs(),
+            }
+        };
+        let total = |group: &[usize]| group.iter().map(|index| number(*index)).sum::<f64>();
+        let max_total = groups.iter().map(|group| total(group)).fold(0.0, f64::max);
+
+        let [start_output, end_output] = &self.pipe.output;
+        let mut result = values.to_vec();
+
+        for group in groups {
+            let (offset, scale) = match self.pipe.offset {
+                StackOffset::Zero => (0.0, 1.0),
+                StackOffset::Center => ((max_total - total(&group)) / 2.0, 1.0),
+                StackOffset::Normalize => {
+                    let total = total(&group);
+                    (0.0, if total > 0.0 { 1.0 / total } else { 0.0 })
+                }
+            };
+
+            let (mut positive, mut negative) = (offset, offset);
+            for index in group {
+                let number = number(index) * scale;
+                let stack = if number < 0.0 {
+                    &mut negative
+                } else {
+                    &mut positive
+                };
+
+                let start = *stack;
+                *stack += number;
+
+                result[index].insert(start_output, DataItem::Number(start));
+                result[index].insert(end_output, DataItem::Number(*stack));
+            }
+        }
+
+        result
+    }
+}
+
+impl Evaluation for StackOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        Pulse::data(self.apply(&single))
+    }
+
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.iter().fold(Vec::new(), |mut acc, pulse| {
+            acc.extend(self.apply(pulse));
+            acc
+        });
+
+        Pulse::data(values)
+    }
+}
+
+/// `BinOperator` represents an operator of the graph, which divides the numbers of a field for
+/// the incoming pulse values into bins, by a given bin pipe.
+#[derive(Debug, PartialEq)]
+pub struct BinOperator {
+    pipe: BinPipe,
+}
+
+impl BinOperator {
+    /// Create a new `BinOperator` instance with a certain bin pipe.
+    pub(crate) fn new(pipe: BinPipe) -> Self {
+        BinOperator { pipe }
+    }
+
+    /// Apply the operator's logic by adding the start and the end of the bin of each value. The
+    /// bins cover the extent of the values' numbers, if the pipe doesn't declare one. Values
+    /// without a number, or outside of the extent, have null bins.
+    fn apply(&self, values: Vec<DataValue>) -> Vec<DataValue> {
+        let extent = self.pipe.extent.or_else(|| {
+            values
+                .iter()
+                .filter_map(|value| value.get_number(&self.pipe.field).copied())
+                .fold(None, |extent, number| match extent {
+                    Some([min, max]) => Some([number.min(min), number.max(max)]),
+                    None => Some([number, number]),
+                })
+        });
+        let bins = extent.map(|extent| self.pipe.bins(extent));
+
+        let [start_output, end_output] = &self.pipe.output;
+        values
+            .into_iter()
+            .map(|mut value| {
+                let bin = bins.zip(value.get_number(&self.pipe.field).copied());
+                let (start, end) = match bin.and_then(|(bins, number)| bins.find(number)) {
+                    Some((start, end)) => (DataItem::Number(start), DataItem::Number(end)),
+                    None => (DataItem::Null, DataItem::Null),
+                };
+
+                value.insert(start_output, start);
+                value.insert(end_output, end);
+                value
+            })
+            .collect()
+    }
+}
+
+impl Evaluation for BinOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        let SinglePulse::Data(values) = single else {
+            return Pulse::data(Vec::new());
+        };
+
+        Pulse::data(self.apply(values))
+    }
+
+    /// Bin the values of all pulses together, so that they share the same bins.
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.into_iter().fold(Vec::new(), |mut acc, pulse| {
+            if let SinglePulse::Data(values) = pulse {
+                acc.extend(values);
+            }
+            acc
+        });
+
+        Pulse::data(self.apply(values))
+    }
+}
+
+/// `LookupOperator` represents an operator of the graph, which joins the incoming pulse values
+/// with the values of another data entry, by a given lookup pipe. The operator's sources are the
+/// incoming values and the values of the other entry, found at a certain `position` of the
+/// sources.
+#[derive(Debug, PartialEq)]
+pub struct LookupOperator {
+    pipe: LookupPipe,
+    position: usize,
+}
+
+impl LookupOperator {
+    /// Create a new `LookupOperator` instance with a certain lookup pipe, whose `from` values are
+    /// found at a certain `position` of the operator's sources.
+    pub(crate) fn new(pipe: LookupPipe, position: usize) -> Self {
+        LookupOperator { pipe, position }
+    }
+
+    /// Apply the operator's logic by copying the fields of the first `from` value, whose key
+    /// matches each value's field. Values with a null or missing field, or without a match, get
+    /// the defaults of the copied fields.
+    fn apply(&self, values: Vec<DataValue>, from: &[DataValue]) -> Vec<DataValue> {
+        let mut index: HashMap<&DataItem, &DataValue> = HashMap::new();
+        for value in from {
+            if let Some(key) = value.get(&self.pipe.key).filter(|key| key.is_valid()) {
+                index.entry(key).or_insert(value);
+            }
+        }
+
+        values
+            .into_iter()
+            .map(|mut value| {
+                let found = value
+                    .get(&self.pipe.field)
+                    .filter(|key| key.is_valid())
+                    .and_then(|key| index.get(key))
+                    .copied();
+
+                for field in &self.pipe.values {
+                    let item = found
+                        .and_then(|found| found.get(&field.field))
+                        .unwrap_or(&field.default)
+                        .clone();
+
+                    value.insert(&field.output, item);
+                }
+
+                value
+            })
+            .collect()
+    }
+}
+
+impl Evaluation for LookupOperator {
+    /// Look up the values in themselves, if the `from` entry is the operator's only source.
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        let SinglePulse::Data(values) = single else {
+            return Pulse::data(Vec::new());
+        };
+
+        let from = values.clone();
+        Pulse::data(self.apply(values, &from))
+    }
+
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let mut values = Vec::new();
+        let mut from = Vec::new();
+
+        for (position, pulse) in multi.pulses.into_iter().enumerate() {
+            let SinglePulse::Data(data) = pulse else {
+                continue;
+            };
+
+            if position == self.position {
+                from = data;
+            } else {
+                values.extend(data);
+            }
+        }
+
+        Pulse::data(self.apply(values, &from))
+    }
+}
+
+/// `FoldOperator` represents an operator of the graph, which splits each incoming pulse value
+/// into one value per folded field, by a given fold pipe.
+#[derive(Debug, PartialEq)]
+pub struct FoldOperator {
+    pipe: FoldPipe,
+}
+
+impl FoldOperator {
+    /// Create a new `FoldOperator` instance with a certain fold pipe.
+    pub(crate) fn new(pipe: FoldPipe) -> Self {
+        FoldOperator { pipe }
+    }
+
+    /// Apply the operator's logic by folding each value of the pulse.
+    fn apply(&self, pulse: &SinglePulse) -> Vec<DataValue> {
+        let SinglePulse::Data(values) = pulse else {
+            return Vec::new();
+        };
+
+        values.iter().flat_map(|value| self.fold(value)).collect()
+    }
+
+    /// Fold a value into one value per folded field, where the folded fields are replaced with
+    /// the name and the item of one of them. Missing fields are folded as null.
+    fn fold(&self, value: &DataValue) -> Vec<DataValue> {
+        let [key_output, value_output] = &self.pipe.output;
+
+        let mut rest = value.clone();
+        for field in &self.pipe.fields {
+            rest.instance.remove(field);
+        }
+
+        self.pipe
+            .fields
+            .iter()
+            .map(|field| {
+                let mut folded = rest.clone();
+                folded.insert(key_output, DataItem::Text(field.clone()));
+                folded.insert(
+                    value_output,
+                    value.get(field).cloned().unwrap_or(DataItem::Null),
+                );
+                folded
+            })
+            .collect()
+    }
+}
+
+impl Evaluation for FoldOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        Pulse::data(self.apply(&single))
+    }
+
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.iter().fold(Vec::new(), |mut acc, pulse| {
+            acc.extend(self.apply(pulse));
+            acc
+        });
+
+        Pulse::data(values)
+    }
+
+    /// Fold only the changed values. Folding only moves the items of the values' fields, so
+    /// removed values fold into the values present in the output.
+    fn evaluate_changes(&self, output: &mut Pulse, changes: &Changeset) -> Propagation {
+        let changes = Changeset {
+            add: changes
+                .add
+                .iter()
+                .flat_map(|value| self.fold(value))
+                .collect(),
+            rem: changes
+                .rem
+                .iter()
+                .flat_map(|value| self.fold(value))
+                .collect(),
+            modified: changes
+                .modified
+                .iter()
+                .flat_map(|(old, new)| self.fold(old).into_iter().zip(self.fold(new)))
+                .collect(),
+        };
+
+        if output.apply_changes(&changes) {
+            Propagation::Changes(changes)
+        } else {
+            Propagation::Unsupported
+        }
+    }
+}
+
+/// `PivotOperator` represents an operator of the graph, which adds a field for each distinct
+/// item of a field to the groups of the incoming pulse values, by a given pivot pipe. Groups are
+/// kept in the order of their first value.
+#[derive(Debug, PartialEq)]
+pub struct PivotOperator {
+    pipe: PivotPipe,
+}
+
+impl PivotOperator {
+    /// Create a new `PivotOperator` instance with a certain pivot pipe.
+    pub(crate) fn new(pipe: PivotPipe) -> Self {
+        PivotOperator { pipe }
+    }
+
+    /// Apply the operator's logic by summarizing the values of each group with the same item of
+    /// the pivoted field into a field named after the item. Every group has the fields of all
+    /// items, in the order of their first value, even if the group has no values for them.
+    /// Values with a null or missing pivoted field are skipped.
+    fn apply(&self, values: &[DataValue]) -> Vec<DataValue> {
+        let mut keys: Vec<&DataItem> = Vec::new();
+        for value in values {
+            if let Some(key) = value.get(&self.pipe.field).filter(|key| key.is_valid()) {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        partition(values, &self.pipe.groupby, &SortPipe::new(Vec::new()))
+            .into_iter()
+            .map(|group| {
+                let group: Vec<&DataValue> =
+                    group.into_iter().map(|index| &values[index]).collect();
+
+                let mut pivoted = DataValue::new();
+                for field in &self.pipe.groupby {
+                    let item = group[0].get(field).cloned().unwrap_or(DataItem::Null);
+                    pivoted.insert(field, item);
+                }
+
+                for key in &keys {
+                    let matching: Vec<&DataValue> = group
+                        .iter()
+                        .filter(|value| value.get(&self.pipe.field) == Some(*key))
+                        .copied()
+                        .collect();
+
+                    pivoted.insert(
+                        &key.to_string(),
+                        aggregate(self.pipe.op, Some(&self.pipe.value), &matching),
+                    );
+                }
+
+                pivoted
+            })
+            .collect()
+    }
+}
+
+impl Evaluation for PivotOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        let SinglePulse::Data(values) = single else {
+            return Pulse::data(Vec::new());
+        };
+
+        Pulse::data(self.apply(&values))
+    }
+
+    /// Pivot the values of all pulses together.
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.into_iter().fold(Vec::new(), |mut acc, pulse| {
+            if let SinglePulse::Data(values) = pulse {
+                acc.extend(values);
+            }
+            acc
+        });
+
+        Pulse::data(self.apply(&values))
+    }
+}
+
+/// `RegressionOperator` represents an operator of the graph, which fits a regression model to
+/// the groups of the incoming pulse values, by a given regression pipe. Groups are kept in the
+/// order of their first value.
+#[derive(Debug, PartialEq)]
+pub struct RegressionOperator {
+    pipe: RegressionPipe,
+}
+
+impl RegressionOperator {
+    /// Create a new `RegressionOperator` instance with a certain regression pipe.
+    pub(crate) fn new(pipe: RegressionPipe) -> Self {
+        RegressionOperator { pipe }
+    }
+
+    /// Apply the operator's logic by replacing each group with points sampled along the curve of
+    /// its fitted model. Groups without a fitted model are skipped.
+    fn apply(&self, values: &[DataValue]) -> Vec<DataValue> {
+        let [x_output, y_output] = self.pipe.outputs();
+        let mut result = Vec::new();
+
+        for group in fit_groups(values, &self.pipe.groupby, &self.pipe.x, &self.pipe.y) {
+            let Some(fit) = self.pipe.fit(&group.points) else {
+                continue;
+            };
+
+            for (x, y) in self.pipe.sample(&fit) {
+                let mut point = group.value.clone();
+                point.insert(x_output, DataItem::Number(x));
+                point.insert(y_output, DataItem::Number(y));
+
+                if self.pipe.params {
+                    let coefficients = fit.coefficients.iter().copied().map(DataItem::Number);
+                    point.insert(
+                        COEFFICIENTS_FIELD_NAME,
+                        DataItem::Array(coefficients.collect()),
+                    );
+                    point.insert(R_SQUARED_FIELD_NAME, DataItem::Number(fit.r_squared));
+                }
+
+                result.push(point);
+            }
+        }
+
+        result
+    }
+}
+
+impl Evaluation for RegressionOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        let SinglePulse::Data(values) = single else {
+            return Pulse::data(Vec::new());
+        };
+
+        Pulse::data(self.apply(&values))
+    }
+
+    /// Fit the values of all pulses together.
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.into_iter().fold(Vec::new(), |mut acc, pulse| {
+            if let SinglePulse::Data(values) = pulse {
+                acc.extend(values);
+            }
+            acc
+        });
+
+        Pulse::data(self.apply(&values))
+    }
+}
+
+/// `LoessOperator` represents an operator of the graph, which fits a smooth curve to the groups
+/// of the incoming pulse values, by a given loess pipe. Groups are kept in the order of their
+/// first value.
+#[derive(Debug, PartialEq)]
+pub struct LoessOperator {
+    pipe: LoessPipe,
+}
+
+impl LoessOperator {
+    /// Create a new `LoessOperator` instance with a certain loess pipe.
+    pub(crate) fn new(pipe: LoessPipe) -> Self {
+        LoessOperator { pipe }
+    }
+
+    /// Apply the operator's logic by replacing each group with the points of its fitted curve.
+    fn apply(&self, values: &[DataValue]) -> Vec<DataValue> {
+        let [x_output, y_output] = self.pipe.outputs();
+        let mut result = Vec::new();
+
+        for group in fit_groups(values, &self.pipe.groupby, &self.pipe.x, &self.pipe.y) {
+            for (x, y) in self.pipe.fit(&group.points) {
+                let mut point = group.value.clone();
+                point.insert(x_output, DataItem::Number(x));
+                point.insert(y_output, DataItem::Number(y));
+
+                result.push(point);
+            }
+        }
+
+        result
+    }
+}
+
+impl Evaluation for LoessOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        let SinglePulse::Data(values) = single else {
+            return Pulse::data(Vec::new());
+        };
+
+        Pulse::data(self.apply(&values))
+    }
+
+    /// Fit the values of all pulses together.
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.into_iter().fold(Vec::new(), |mut acc, pulse| {
+            if let SinglePulse::Data(values) = pulse {
+                acc.extend(values);
+            }
+            acc
+        });
+
+        Pulse::data(self.apply(&values))
+    }
+}
+
+/// `FitGroup` is a group of data values to fit a curve to, with the `value` of its `groupby`
+/// fields and the `points` of its `x` and `y` numbers.
+struct FitGroup {
+    value: DataValue,
+    points: Vec<(f64, f64)>,
+}
+
+/// Collect the points of the groups of certain `groupby` fields, in the order of their first
+/// value. Values without an `x` or `y` number are skipped.
+fn fit_groups(values: &[DataValue], groupby: &[String], x: &str, y: &str) -> Vec<FitGroup> {
+    partition(values, groupby, &SortPipe::new(Vec::new()))
+        .into_iter()
+        .map(|group| {
+            let mut value = DataValue::new();
+            for field in groupby {
+                let item = values[group[0]]
+                    .get(field)
+                    .cloned()
+                    .unwrap_or(DataItem::Null);
+                value.insert(field, item);
+            }
+
+            let points = group
+                .into_iter()
+                .filter_map(|index| {
+                    let x = values[index].get_number(x)?;
+                    let y = values[index].get_number(y)?;
+                    Some((*x, *y))
+                })
+                .collect();
+
+            FitGroup { value, points }
+        })
+        .collect()
+}
+
+/// Partition data values into the groups of certain `groupby` fields, in the order of their
+/// first value, where missing fields are grouped as null. Each group holds the indices of its
+/// values, ordered by a certain `sort`.
+fn partition(values: &[DataValue], groupby: &[String], sort: &SortPipe) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut indices: HashMap<Vec<&DataItem>, usize> = HashMap::new();
+
+    for (index, value) in values.iter().enumerate() {
+        let key: Vec<&DataItem> = groupby
+            .iter()
+            .map(|field| value.get(field).unwrap_or(&DataItem::Null))
+            .collect();
+
+        match indices.get(&key) {
+            Some(group) => groups[*group].push(index),
+            None => {
+                indices.insert(key, groups.len());
+                groups.push(vec![index]);
+            }
+        }
+    }
+
+    for group in &mut groups {
+        group.sort_by(|left, right| sort.compare(&values[*left], &values[*right]));
+    }
+
+    groups
+}
+
+/// `AggregateOperator` represents an operator of the graph, which groups the incoming pulse values
+/// by the values of certain fields, and summarizes each group with a set of aggregate operations.
+/// Groups are kept in the order of their first value.
+#[derive(Debug, PartialEq)]
+pub struct AggregateOperator {
+    pipe: AggregatePipe,
+}
+
+impl AggregateOperator {
+    /// Create a new `AggregateOperator` instance with a certain aggregate pipe.
+    pub(crate) fn new(pipe: AggregatePipe) -> Self {
+        AggregateOperator { pipe }
+    }
+
+    /// Apply the operator's logic by grouping the pulse data values by the `groupby` fields, and
+    /// aggregating the values of each group. Values missing any of the fields are left out.
+    fn apply(&self, pulse: &SinglePulse) -> Vec<DataValue> {
+        let SinglePulse::Data(values) = pulse else {
+            return Vec::new();
+        };
+
+        let mut groups: Vec<(Vec<&DataItem>, Vec<&DataValue>)> = Vec::new();
+        let mut indices: HashMap<Vec<&DataItem>, usize> = HashMap::new();
+
+        for value in values {
+            let Some(key) = self
+                .pipe
+                .groupby
+                .iter()
+                .map(|field| value.get(field))
+                .collect::<Option<Vec<&DataItem>>>()
+            else {
+                continue;
+            };
+
+            match indices.get(&key) {
+                Some(index) => groups[*index].1.push(value),
+                None => {
+                    indices.insert(key.clone(), groups.len());
+                    groups.push((key, vec![value]));
+                }
+            }
+        }
+
+        // Without fields to group by, values are summarized into a single group, even if empty
+        if self.pipe.groupby.is_empty() && groups.is_empty() {
+            groups.push((Vec::new(), Vec::new()));
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, group)| {
+                let mut result = DataValue::new();
+                for (field, item) in self.pipe.groupby.iter().zip(key) {
+                    result.insert(field, item.clone());
+                }
+                for op in &self.pipe.ops {
+                    result.insert(&op.output, aggregate(op.op, op.field.as_deref(), &group));
+                }
+                result
+            })
+            .collect()
+    }
+}
+
+impl Evaluation for AggregateOperator {
+    async fn evaluate_single(&self, single: SinglePulse) -> Pulse {
+        Pulse::data(self.apply(&single))
+    }
+
+    async fn evaluate_multi(&self, multi: MultiPulse) -> Pulse {
+        let values = multi.pulses.iter().fold(Vec::new(), |mut acc, pulse| {
+            acc.extend(self.apply(pulse));
+            acc
+        });
+
+        Pulse::data(values)
+    }
+}
+
+/// Aggregate the values of a `field` of a group with a certain aggregate operation. Numeric
+/// operations skip values whose field isn't a number, and result in null if no number is left.
+fn aggregate(op: AggregateOp, field: Option<&str>, group: &[&DataValue]) -> DataItem {
+    let items = || {
+        group.iter().map(|value| {
+            field
+                .and_then(|field| value.get(field))
+                .unwrap_or(&DataItem::Null)
+        })
+    };
+    let numbers = || -> Vec<f64> { items().filter_map(DataItem::get_number).copied().collect() };
+
+    let result = match op {
+        AggregateOp::Count => Some(group.len() as f64),
+        AggregateOp::Distinct => Some(items().collect::<HashSet<_>>().len() as f64),
+        AggregateOp::Sum => Some(numbers().iter().sum()),
+        AggregateOp::Mean => mean(&numbers()),
+        AggregateOp::Median => quantile(numbers(), 0.5),
+        AggregateOp::Q1 => quantile(numbers(), 0.25),
+        AggregateOp::Q3 => quantile(numbers(), 0.75),
+        AggregateOp::Variance => variance(&numbers()),
+        AggregateOp::Stdev => variance(&numbers()).map(f64::sqrt),
+        AggregateOp::Min | AggregateOp::Max | AggregateOp::Argmin | AggregateOp::Argmax => {
+            let ordering = match op {
+                AggregateOp::Min | AggregateOp::Argmin => Ordering::Less,
+                _ => Ordering::Greater,
+            };
+
+            let extremum = group.iter().zip(items()).fold(
+                None,
+                |extremum: Option<(&DataValue, &DataItem, f64)>, (value, item)| {
+                    let key = match item {
+                        DataItem::Number(number) => *number,
+                        DataItem::Date(date) => *date as f64,
+                        _ => return extremum,
+                    };
+
+                    match extremum {
+                        Some((_, _, current)) if key.total_cmp(&current) != ordering => extremum,
+                        _ => Some((value, item, key)),
+                    }
+                },
+            );
+
+            return match (op, extremum) {
+                (AggregateOp::Min | AggregateOp::Max, Some((_, item, _))) => item.clone(),
+                (_, Some((value, _, _))) => DataItem::Object(
+                    value
+                        .instance
+                        .iter()
+                        .map(|(key, item)| (key.clone(), item.clone()))
+                        .collect(),
+                ),
+                (_, None) => DataItem::Null,
+            };
+        }
+    };
+
+    result.map_or(DataItem::Null, DataItem::Number)
+}
+
+fn mean(numbers: &[f64]) -> Option<f64> {
+    if numbers.is_empty() {
+        return None;
+    }
+
+    Some(numbers.iter().sum::<f64>() / numbers.len() as f64)
+}
+
+/// Sample variance of certain numbers, which requires at least two of them.
+fn variance(numbers: &[f64]) -> Option<f64> {
+    if numbers.len() < 2 {
+        return None;
+    }
+
+    let mean = mean(numbers)?;
+    let squares: f64 = numbers.iter().map(|number| (number - mean).powi(2)).sum();
+
+    Some(squares / (numbers.len() - 1) as f64)
+}
+
+/// Quantile `p` of certain numbers, interpolating linearly between the closest ranks.
+fn quantile(mut numbers: Vec<f64>, p: f64) -> Option<f64> {
+    if numbers.is_empty() {
+        return None;
+    }
+    numbers.sort_by(f64::total_cmp);
+
+    let rank = (numbers.len() - 1) as f64 * p;
+    let (lower, upper) = (
+        numbers[rank.floor() as usize],
+        numbers[rank.ceil() as usize],
+    );
+
+    Some(lower + (upper - lower) * rank.fract())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bruc_expression::data::DataItem;
+
+    use crate::spec::transform::group::GroupOperator as GroupOperatorSpec;
+    use crate::{
+        data::DataValue,
+        graph::{
+            node::transform::{
+                aggregate, AggregateOperator, BinOperator, FilterOperator, FoldOperator,
+                GroupOperator, LoessOperator, LookupOperator, MapOperator, PivotOperator,
+                RegressionOperator, SortOperator, StackOperator, WindowOperator,
+            },
+            pulse::Changeset,
+            Evaluation, Propagation, Pulse, SinglePulse,
+        },
+        spec::transform::{
+            aggregate::{AggregateField, AggregateOp, AggregatePipe},
+            bin::BinPipe,
+            filter::FilterPipe,
+            fold::FoldPipe,
+            group::GroupPipe,
+            loess::LoessPipe,
+            lookup::{LookupField, LookupPipe},
+            map::MapPipe,
+            pivot::PivotPipe,
+            regression::{RegressionMethod, RegressionPipe},
+            sort::{SortField, SortPipe},
+            stack::{StackOffset, StackPipe},
+            window::{Frame, WindowField, WindowFunction, WindowOp, WindowPipe},
+        },
+    };
+
+    #[tokio::test]
+    async fn applies_map_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
+        ];
+
+        let operator = MapOperator::new(MapPipe::new("x + 2 * y", "z").unwrap());
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![
+                    ("x", (-2.0).into()),
+                    ("y", 1.0.into()),
+                    ("z", 0.0.into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("x", 5.0.into()),
+                    ("y", 1.0.into()),
+                    ("z", 7.0.into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("x", 10.0.into()),
+                    ("y", 1.0.into()),
+                    ("z", 12.0.into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("x", 15.0.into()),
+                    ("y", 1.0.into()),
+                    ("z", 17.0.into())
+                ]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_map_multi_pulse() {
+        let first = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
+        ]);
+        let second = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
+        ]);
+
+        let operator = MapOperator::new(MapPipe::new("x + 2 * y", "z").unwrap());
+
+        let result = operator.evaluate(Pulse::multi(vec![first, second])).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![
+                    ("x", (-2.0).into()),
+                    ("y", 1.0.into()),
+                    ("z", 0.0.into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("x", 5.0.into()),
+                    ("y", 1.0.into()),
+                    ("z", 7.0.into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("x", 10.0.into()),
+                    ("y", 1.0.into()),
+                    ("z", 12.0.into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("x", 15.0.into()),
+                    ("y", 1.0.into()),
+                    ("z", 17.0.into())
+                ]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_map_with_conditional_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("x", 5.0.into())]),
+            DataValue::from_pairs(vec![("x", 12.0.into())]),
+        ];
+
+        let operator = MapOperator::new(MapPipe::new("x > 10 ? x : 0", "z").unwrap());
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![("x", 5.0.into()), ("z", 0.0.into())]),
+                DataValue::from_pairs(vec![("x", 12.0.into()), ("z", 12.0.into())]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_map_with_dynamic_types_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("a", 3.0.into()), ("name", "apple".into())]),
+            DataValue::from_pairs(vec![("a", 8.0.into()), ("name", "pear".into())]),
+        ];
+
+        let label = MapOperator::new(MapPipe::new("a > 5", "label").unwrap());
+        let title = MapOperator::new(MapPipe::new("name + 's'", "title").unwrap());
+
+        let result = label.evaluate(Pulse::data(series)).await;
+        let result = title.evaluate(result).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![
+                    ("a", 3.0.into()),
+                    ("name", "apple".into()),
+                    ("label", false.into()),
+                    ("title", "apples".into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("a", 8.0.into()),
+                    ("name", "pear".into()),
+                    ("label", true.into()),
+                    ("title", "pears".into())
+                ]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_filter_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
+        ];
+
+        let operator = FilterOperator::new(FilterPipe::new("x > y").unwrap());
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
+                DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
+                DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_filter_on_text_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("x", 1.0.into()), ("category", "fruit".into())]),
+            DataValue::from_pairs(vec![("x", 2.0.into()), ("category", "vegetable".into())]),
+            DataValue::from_pairs(vec![("x", 3.0.into()), ("category", "fruit".into())]),
+        ];
+
+        let operator = FilterOperator::new(FilterPipe::new("category == 'fruit'").unwrap());
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![("x", 1.0.into()), ("category", "fruit".into())]),
+                DataValue::from_pairs(vec![("x", 3.0.into()), ("category", "fruit".into())]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_filter_with_conditional_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("x", 1.0.into()), ("y", 5.0.into())]),
+            DataValue::from_pairs(vec![("x", (-1.0).into()), ("y", 5.0.into())]),
+            DataValue::from_pairs(vec![("x", (-1.0).into()), ("y", (-5.0).into())]),
+        ];
+
+        let operator = FilterOperator::new(FilterPipe::new("x > 0 ? y > 0 : y < 0").unwrap());
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![("x", 1.0.into()), ("y", 5.0.into())]),
+                DataValue::from_pairs(vec![("x", (-1.0).into()), ("y", (-5.0).into())]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_filter_multi_pulse() {
+        let first = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![("x", (-2.0).into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
+        ]);
+        let second = SinglePulse::Data(vec![
+            DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
+            DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
+        ]);
+
+        let operator = FilterOperator::new(FilterPipe::new("x > y").unwrap());
+
+        let result = operator.evaluate(Pulse::multi(vec![first, second])).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![("x", 5.0.into()), ("y", 1.0.into())]),
+                DataValue::from_pairs(vec![("x", 10.0.into()), ("y", 1.0.into())]),
+                DataValue::from_pairs(vec![("x", 15.0.into()), ("y", 1.0.into())]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_map_and_filter_with_null_values() {
+        let series = vec![
+            DataValue::from_pairs(vec![("x", 5.0.into())]),
+            DataValue::from_pairs(vec![("x", DataItem::Null)]),
+            DataValue::new(),
+        ];
+
+        let map = MapOperator::new(MapPipe::new("x * 2", "z").unwrap());
+        let filter = FilterOperator::new(FilterPipe::new("x == null || x > 1").unwrap());
+
+        let result = map.evaluate(Pulse::data(series)).await;
+        let result = filter.evaluate(result).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![("x", 5.0.into()), ("z", 10.0.into())]),
+                DataValue::from_pairs(vec![("x", DataItem::Null), ("z", DataItem::Null)]),
+                DataValue::from_pairs(vec![("z", DataItem::Null)]),
+            ])
+        );
+
+        let filter = FilterOperator::new(FilterPipe::new("x > 1").unwrap());
+        let result = filter.evaluate(result).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![DataValue::from_pairs(vec![
+                ("x", 5.0.into()),
+                ("z", 10.0.into())
+            ])])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_group_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("a", 1.0.into()), ("b", 2.0.into())]),
+            DataValue::from_pairs(vec![("a", 1.0.into()), ("b", 2.0.into())]),
+        ];
+
+        let operator = GroupOperator::new(GroupPipe::new("a", GroupOperatorSpec::Count, "count"));
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![DataValue::from_pairs(vec![
+                ("a", 1.0.into()),
+                ("count", 2.0.into())
+            ])])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_filter_and_group_on_nested_fields() {
+        let item = |country: &str, population: f64| {
+            DataValue::from_pairs(vec![(
+                "geo",
+                DataItem::Object(BTreeMap::from([
+                    ("country".to_string(), country.into()),
+                    (
+                        "population".to_string(),
+                        DataItem::Array(vec![population.into()]),
+                    ),
+                ])),
+            )])
+        };
+        let series = vec![item("es", 2.0), item("es", 3.0), item("de", 1.0)];
+
+        let filter = FilterOperator::new(FilterPipe::new("geo.population[0] > 1").unwrap());
+        let group = GroupOperator::new(GroupPipe::new(
+            "geo.country",
+            GroupOperatorSpec::Count,
+            "count",
+        ));
+
+        let result = group
+            .evaluate(filter.evaluate(Pulse::data(series)).await)
+            .await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![DataValue::from_pairs(vec![
+                ("geo.country", "es".into()),
+                ("count", 2.0.into())
+            ])])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_sort_after_group() {
+        let series = [3.0, 1.0, 2.0, 3.0, 1.0, 3.0]
+            .into_iter()
+            .map(|a| DataValue::from_pairs(vec![("a", a.into())]))
+            .collect();
+        let group = |a: f64, count: f64| {
+            DataValue::from_pairs(vec![("a", a.into()), ("count", count.into())])
+        };
+
+        let operator = GroupOperator::new(GroupPipe::new("a", GroupOperatorSpec::Count, "count"));
+        let sort = SortOperator::new(SortPipe::new(vec![SortField::ascending("a")]));
+
+        let result = sort
+            .evaluate(operator.evaluate(Pulse::data(series)).await)
+            .await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![group(1.0, 2.0), group(2.0, 1.0), group(3.0, 3.0)])
+        );
+
+        let sort = SortOperator::new(SortPipe::new(vec![SortField::descending("count")]));
+        let result = sort.evaluate(result).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![group(3.0, 3.0), group(1.0, 2.0), group(2.0, 1.0)])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_sort_multi_pulse() {
+        let value = |a: f64| DataValue::from_pairs(vec![("a", a.into())]);
+        let first = SinglePulse::Data(vec![value(3.0), value(1.0)]);
+        let second = SinglePulse::Data(vec![value(2.0)]);
+
+        let operator = SortOperator::new(SortPipe::new(vec![SortField::ascending("a")]));
+
+        let result = operator.evaluate(Pulse::multi(vec![first, second])).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![value(1.0), value(2.0), value(3.0)])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_window_ranks_and_offsets() {
+        let value = |a: &str, b: f64| DataValue::from_pairs(vec![("a", a.into()), ("b", b.into())]);
+        let series = vec![
+            value("x", 3.0),
+            value("y", 1.0),
+            value("x", 1.0),
+            value("x", 3.0),
+            value("x", 2.0),
+        ];
+
+        let function = |function: WindowFunction, field: Option<&str>, output: &str| {
+            WindowField::new(WindowOp::Function(function), field, output)
+        };
+        let operator = WindowOperator::new(
+            WindowPipe::new(vec![
+                function(WindowFunction::RowNumber, None, "row"),
+                function(WindowFunction::Rank, None, "rank"),
+                function(WindowFunction::DenseRank, None, "dense"),
+                function(WindowFunction::Lag, Some("b"), "lag"),
+                function(WindowFunction::Lead, Some("b"), "lead").with_param(2),
+                WindowField::new(WindowOp::Aggregate(AggregateOp::Sum), Some("b"), "total"),
+            ])
+            .with_groupby(vec!["a"])
+            .with_sort(vec![SortField::ascending("b")]),
+        );
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        let windowed = |a: &str, b: f64, row: f64, rank: f64, dense: f64, lag, lead, total: f64| {
+            DataValue::from_pairs(vec![
+                ("a", a.into()),
+                ("b", b.into()),
+                ("row", row.into()),
+                ("rank", rank.into()),
+                ("dense", dense.into()),
+                ("lag", lag),
+                ("lead", lead),
+                ("total", total.into()),
+            ])
+        };
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                windowed("x", 3.0, 3.0, 3.0, 3.0, 2.0.into(), DataItem::Null, 6.0),
+                windowed("y", 1.0, 1.0, 1.0, 1.0, DataItem::Null, DataItem::Null, 1.0),
+                windowed("x", 1.0, 1.0, 1.0, 1.0, DataItem::Null, 3.0.into(), 1.0),
+                windowed("x", 3.0, 4.0, 3.0, 3.0, 3.0.into(), DataItem::Null, 9.0),
+                windowed("x", 2.0, 2.0, 2.0, 2.0, 1.0.into(), 3.0.into(), 3.0),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_window_moving_average() {
+        let value = |x: f64| DataValue::from_pairs(vec![("x", x.into())]);
+        let series = vec![value(1.0), value(5.0), value(3.0), value(7.0)];
+
+        let operator = WindowOperator::new(
+            WindowPipe::new(vec![
+                WindowField::new(WindowOp::Aggregate(AggregateOp::Mean), Some("x"), "mean"),
+                WindowField::new(
+                    WindowOp::Function(WindowFunction::FirstValue),
+                    Some("x"),
+                    "first",
+                ),
+            ])
+            .with_frame(Frame::new(Some(-1), Some(1))),
+        );
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        let averaged = |x: f64, mean: f64, first: f64| {
+            DataValue::from_pairs(vec![
+                ("x", x.into()),
+                ("mean", mean.into()),
+                ("first", first.into()),
+            ])
+        };
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                averaged(1.0, 3.0, 1.0),
+                averaged(5.0, 3.0, 1.0),
+                averaged(3.0, 5.0, 5.0),
+                averaged(7.0, 5.0, 3.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn accumulates_window_frames_as_aggregates() {
+        let values: Vec<DataValue> = [1.0, 4.0, f64::NAN, 2.0, 8.0, 3.0]
+            .into_iter()
+            .map(|x| match x.is_nan() {
+                true => DataValue::from_pairs(vec![("x", DataItem::Null)]),
+                false => DataValue::from_pairs(vec![("x", x.into())]),
+            })
+            .collect();
+        let group: Vec<&DataValue> = values.iter().collect();
+
+        for frame in [
+            Frame::cumulative(),
+            Frame::new(None, None),
+            Frame::new(Some(-1), Some(1)),
+            Frame::new(Some(1), Some(2)),
+            Frame::new(Some(-3), Some(-2)),
+            Frame::new(Some(0), None),
+        ] {
+            let operator = WindowOperator::new(WindowPipe::new(Vec::new()).with_frame(frame));
+
+            for op in [AggregateOp::Count, AggregateOp::Sum, AggregateOp::Mean] {
+                let expected: Vec<DataItem> = (0..group.len())
+                    .map(|position| {
+                        aggregate(op, Some("x"), &group[frame.range(position, group.len())])
+                    })
+                    .collect();
+
+                assert_eq!(
+                    operator.accumulate(op, Some("x"), &group),
+                    expected,
+                    "{op:?} over {frame:?}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn applies_stack_by_group() {
+        let value = |week: f64, category: &str, amount: f64| {
+            DataValue::from_pairs(vec![
+                ("week", week.into()),
+                ("category", category.into()),
+                ("amount", amount.into()),
+            ])
+        };
+        let series = vec![
+            value(1.0, "b", 2.0),
+            value(1.0, "a", 3.0),
+            value(2.0, "a", 1.0),
+            value(1.0, "c", -1.0),
+            value(2.0, "b", 4.0),
+        ];
+
+        let operator = StackOperator::new(
+            StackPipe::new("amount")
+                .with_groupby(vec!["week"])
+                .with_sort(vec![SortField::ascending("category")]),
+        );
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        let stacked = |week: f64, category: &str, amount: f64, y0: f64, y1: f64| {
+            DataValue::from_pairs(vec![
+                ("week", week.into()),
+                ("category", category.into()),
+                ("amount", amount.into()),
+                ("y0", y0.into()),
+                ("y1", y1.into()),
+            ])
+        };
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                stacked(1.0, "b", 2.0, 3.0, 5.0),
+                stacked(1.0, "a", 3.0, 0.0, 3.0),
+                stacked(2.0, "a", 1.0, 0.0, 1.0),
+                stacked(1.0, "c", -1.0, 0.0, -1.0),
+                stacked(2.0, "b", 4.0, 1.0, 5.0),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_stack_with_offsets() {
+        let value = |week: f64, amount: f64| {
+            DataValue::from_pairs(vec![("week", week.into()), ("amount", amount.into())])
+        };
+        let series = vec![value(1.0, 1.0), value(1.0, 3.0), value(2.0, 2.0)];
+
+        let stacked = |week: f64, amount: f64, y0: f64, y1: f64| {
+            DataValue::from_pairs(vec![
+                ("week", week.into()),
+                ("amount", amount.into()),
+                ("y0", y0.into()),
+                ("y1", y1.into()),
+            ])
+        };
+
+        let center = StackOperator::new(
+            StackPipe::new("amount")
+                .with_groupby(vec!["week"])
+                .with_offset(StackOffset::Center),
+        );
+        assert_eq!(
+            center.evaluate(Pulse::data(series.clone())).await,
+            Pulse::data(vec![
+                stacked(1.0, 1.0, 0.0, 1.0),
+                stacked(1.0, 3.0, 1.0, 4.0),
+                stacked(2.0, 2.0, 1.0, 3.0),
+            ])
+        );
+
+        let normalize = StackOperator::new(
+            StackPipe::new("amount")
+                .with_groupby(vec!["week"])
+                .with_offset(StackOffset::Normalize),
+        );
+        assert_eq!(
+            normalize.evaluate(Pulse::data(series)).await,
+            Pulse::data(vec![
+                stacked(1.0, 1.0, 0.0, 0.25),
+                stacked(1.0, 3.0, 0.25, 1.0),
+                stacked(2.0, 2.0, 0.0, 1.0),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_bin_single_pulse() {
+        let series = vec![
+            DataValue::from_pairs(vec![("a", 1.3.into())]),
+            DataValue::from_pairs(vec![("a", 12.0.into())]),
+            DataValue::from_pairs(vec![("a", 48.2.into())]),
+            DataValue::from_pairs(vec![("a", DataItem::Null)]),
+        ];
+        let binned = |a: DataItem, bin0: DataItem, bin1: DataItem| {
+            DataValue::from_pairs(vec![("a", a), ("bin0", bin0), ("bin1", bin1)])
+        };
+
+        let operator = BinOperator::new(BinPipe::new("a").with_maxbins(10));
+        let result = operator.evaluate(Pulse::data(series.clone())).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                binned(1.3.into(), 0.0.into(), 5.0.into()),
+                binned(12.0.into(), 10.0.into(), 15.0.into()),
+                binned(48.2.into(), 45.0.into(), 50.0.into()),
+                binned(DataItem::Null, DataItem::Null, DataItem::Null),
+            ])
+        );
+
+        let operator = BinOperator::new(BinPipe::new("a").with_step(10.0).with_extent(0.0, 20.0));
+        let result = operator.evaluate(Pulse::data(series)).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                binned(1.3.into(), 0.0.into(), 10.0.into()),
+                binned(12.0.into(), 10.0.into(), 20.0.into()),
+                binned(48.2.into(), DataItem::Null, DataItem::Null),
+                binned(DataItem::Null, DataItem::Null, DataItem::Null),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_group_after_bin() {
+        let series = [0.5, 7.0, 2.5, 4.0, 9.5, 1.0]
+            .into_iter()
+            .map(|a| DataValue::from_pairs(vec![("a", a.into())]))
+            .collect();
+        let group = |bin0: f64, count: f64| {
+            DataValue::from_pairs(vec![("bin0", bin0.into()), ("count", count.into())])
+        };
+
+        let bin = BinOperator::new(BinPipe::new("a").with_step(2.5));
+        let operator =
+            GroupOperator::new(GroupPipe::new("bin0", GroupOperatorSpec::Count, "count"));
+        let sort = SortOperator::new(SortPipe::new(vec![SortField::ascending("bin0")]));
+
+        let result = sort
+            .evaluate(
+                operator
+                    .evaluate(bin.evaluate(Pulse::data(series)).await)
+                    .await,
+            )
+            .await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                group(0.0, 2.0),
+                group(2.5, 2.0),
+                group(5.0, 1.0),
+                group(7.5, 1.0)
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_lookup_multi_pulse() {
+        let category = |id: DataItem, name: &str| {
+            DataValue::from_pairs(vec![("id", id), ("name", name.into())])
+        };
+        let categories = vec![
+            category(1.0.into(), "a"),
+            category(2.0.into(), "b"),
+            category(1.0.into(), "c"),
+        ];
+        let facts = vec![
+            DataValue::from_pairs(vec![("category", 2.0.into())]),
+            DataValue::from_pairs(vec![("category", 1.0.into())]),
+            DataValue::from_pairs(vec![("category", 3.0.into())]),
+            DataValue::from_pairs(vec![("category", DataItem::Null)]),
+        ];
+        let joined = |category: DataItem, name: DataItem| {
+            DataValue::from_pairs(vec![("category", category), ("label", name)])
+        };
+
+        let operator = LookupOperator::new(
+            LookupPipe::new(
+                "categories",
+                "id",
+                "category",
+                vec![LookupField::new("name")
+                    .with_output("label")
+                    .with_default("unknown".into())],
+            ),
+            1,
+        );
+
+        let result = operator
+            .evaluate(Pulse::multi(vec![
+                SinglePulse::Data(facts),
+                SinglePulse::Data(categories),
+            ]))
+            .await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                joined(2.0.into(), "b".into()),
+                joined(1.0.into(), "a".into()),
+                joined(3.0.into(), "unknown".into()),
+                joined(DataItem::Null, "unknown".into()),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_fold_and_pivot() {
+        let wide = |date: f64, cpu: DataItem, mem: f64| {
+            DataValue::from_pairs(vec![
+                ("date", date.into()),
+                ("cpu", cpu),
+                ("mem", mem.into()),
+            ])
+        };
+        let long = |date: f64, key: &str, value: DataItem| {
+            DataValue::from_pairs(vec![
+                ("date", date.into()),
+                ("key", key.into()),
+                ("value", value),
+            ])
+        };
+        let series = vec![wide(1.0, 0.5.into(), 10.0), wide(2.0, DataItem::Null, 12.0)];
+
+        let fold = FoldOperator::new(FoldPipe::new(vec!["cpu", "mem"]));
+        let result = fold.evaluate(Pulse::data(series.clone())).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                long(1.0, "cpu", 0.5.into()),
+                long(1.0, "mem", 10.0.into()),
+                long(2.0, "cpu", DataItem::Null),
+                long(2.0, "mem", 12.0.into()),
+            ])
+        );
+
+        let pivot = PivotOperator::new(PivotPipe::new("key", "value").with_groupby(vec!["date"]));
+        let result = pivot.evaluate(result).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                wide(1.0, 0.5.into(), 10.0),
+                wide(2.0, 0.0.into(), 12.0)
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_pivot_with_collisions() {
+        let value = |date: f64, key: &str, value: f64| {
+            DataValue::from_pairs(vec![
+                ("date", date.into()),
+                ("key", key.into()),
+                ("value", value.into()),
+            ])
+        };
+        let series = vec![
+            value(1.0, "cpu", 0.5),
+            value(1.0, "cpu", 0.7),
+            value(2.0, "mem", 12.0),
+            value(1.0, "mem", 10.0),
+        ];
+
+        let operator = PivotOperator::new(
+            PivotPipe::new("key", "value")
+                .with_groupby(vec!["date"])
+                .with_op(AggregateOp::Max),
+        );
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        let pivoted = |date: f64, cpu: DataItem, mem: f64| {
+            DataValue::from_pairs(vec![
+                ("date", date.into()),
+                ("cpu", cpu),
+                ("mem", mem.into()),
+            ])
+        };
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                pivoted(1.0, 0.7.into(), 10.0),
+                pivoted(2.0, DataItem::Null, 12.0)
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_regression_by_group() {
+        let value = |series: &str, x: f64, y: f64| {
+            DataValue::from_pairs(vec![
+                ("series", series.into()),
+                ("x", x.into()),
+                ("y", y.into()),
+            ])
+        };
+        let series = vec![
+            value("a", 0.0, 1.0),
+            value("b", 1.0, 4.0),
+            value("a", 2.0, 5.0),
+            value("b", 3.0, 0.0),
+            value("a", 1.0, 3.0),
+            value("c", 1.0, 1.0),
+        ];
+
+        let operator = RegressionOperator::new(
+            RegressionPipe::new("x", "y")
+                .with_groupby(vec!["series"])
+                .with_params(true),
+        );
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        let fitted = |series: &str, x: f64, y: f64, coef: [f64; 2]| {
+            DataValue::from_pairs(vec![
+                ("series", series.into()),
+                ("x", x.into()),
+                ("y", y.into()),
+                (
+                    "coef",
+                    DataItem::Array(coef.into_iter().map(DataItem::Number).collect()),
+                ),
+                ("rSquared", 1.0.into()),
+            ])
+        };
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                fitted("a", 0.0, 1.0, [1.0, 2.0]),
+                fitted("a", 2.0, 5.0, [1.0, 2.0]),
+                fitted("b", 1.0, 4.0, [6.0, -2.0]),
+                fitted("b", 3.0, 0.0, [6.0, -2.0]),
+            ])
+        );
+
+        let operator = RegressionOperator::new(
+            RegressionPipe::new("x", "y")
+                .with_method(RegressionMethod::Quad)
+                .with_output("u", "v"),
+        );
+        let Pulse::Single(SinglePulse::Data(result)) = operator
+            .evaluate(Pulse::data(vec![
+                value("a", 0.0, 0.0),
+                value("a", 1.0, 1.0),
+                value("a", 2.0, 4.0),
+            ]))
+            .await
+        else {
+            panic!("expected data pulse");
+        };
+        assert_eq!(result.len(), 100);
+        assert_eq!(
+            result[99],
+            DataValue::from_pairs(vec![("u", 2.0.into()), ("v", 4.0.into())])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_loess_by_group() {
+        let value = |series: &str, x: f64, y: f64| {
+            DataValue::from_pairs(vec![
+                ("series", series.into()),
+                ("x", x.into()),
+                ("y", y.into()),
+            ])
+        };
+        let series = vec![
+            value("a", 2.0, 2.0),
+            value("b", 0.0, 5.0),
+            value("a", 0.0, 0.0),
+            value("a", 1.0, 1.0),
+            value("b", 1.0, 5.0),
+        ];
+
+        let operator = LoessOperator::new(
+            LoessPipe::new("x", "y")
+                .with_groupby(vec!["series"])
+                .with_bandwidth(1.0),
+        );
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                value("a", 0.0, 0.0),
+                value("a", 1.0, 1.0),
+                value("a", 2.0, 2.0),
+                value("b", 0.0, 5.0),
+                value("b", 1.0, 5.0),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_aggregate_single_pulse() {
+        let value = |a: &str, b: f64, c: DataItem| {
+            DataValue::from_pairs(vec![("a", a.into()), ("b", b.into()), ("c", c)])
+        };
+        let series = vec![
+            value("x", 1.0, 2.0.into()),
+            value("y", 5.0, DataItem::Null),
+            value("x", 4.0, 2.0.into()),
+            value("x", 3.0, 7.0.into()),
+            value("x", 2.0, 1.0.into()),
+        ];
+
+        let operator = AggregateOperator::new(AggregatePipe::new(
+            vec!["a"],
+            vec![
+                AggregateField::count("count"),
+                AggregateField::new(AggregateOp::Sum, "b", "sum"),
+                AggregateField::new(AggregateOp::Mean, "b", "mean"),
+                AggregateField::new(AggregateOp::Median, "b", "median"),
+                AggregateField::new(AggregateOp::Q1, "b", "q1"),
+                AggregateField::new(AggregateOp::Q3, "b", "q3"),
+                AggregateField::new(AggregateOp::Variance, "b", "variance"),
+                AggregateField::new(AggregateOp::Min, "c", "min"),
+                AggregateField::new(AggregateOp::Max, "c", "max"),
+                AggregateField::new(AggregateOp::Distinct, "c", "distinct"),
+                AggregateField::new(AggregateOp::Argmax, "c", "argmax"),
+            ],
+        ));
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+
+        assert_eq!(
+            result,
+            Pulse::data(vec![
+                DataValue::from_pairs(vec![
+                    ("a", "x".into()),
+                    ("count", 4.0.into()),
+                    ("sum", 10.0.into()),
+                    ("mean", 2.5.into()),
+                    ("median", 2.5.into()),
+                    ("q1", 1.75.into()),
+                    ("q3", 3.25.into()),
+                    ("variance", (5.0 / 3.0).into()),
+                    ("min", 1.0.into()),
+                    ("max", 7.0.into()),
+                    ("distinct", 3.0.into()),
+                    (
+                        "argmax",
+                        DataItem::Object(BTreeMap::from([
+                            ("a".to_string(), "x".into()),
+                            ("b".to_string(), 3.0.into()),
+                            ("c".to_string(), 7.0.into()),
+                        ]))
+                    ),
+                ]),
+                DataValue::from_pairs(vec![
+                    ("a", "y".into()),
+                    ("count", 1.0.into()),
+                    ("sum", 5.0.into()),
+                    ("mean", 5.0.into()),
+                    ("median", 5.0.into()),
+                    ("q1", 5.0.into()),
+                    ("q3", 5.0.into()),
+                    ("variance", DataItem::Null),
+                    ("min", DataItem::Null),
+                    ("max", DataItem::Null),
+                    ("distinct", 1.0.into()),
+                    ("argmax", DataItem::Null),
+                ]),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_aggregate_without_groupby() {
+        let series = vec![
+            DataValue::from_pairs(vec![("a", 1.0.into()), ("b", 2.0.into())]),
+            DataValue::from_pairs(vec![("a", 3.0.into()), ("b", 2.0.into())]),
+        ];
+
+        let operator = AggregateOperator::new(AggregatePipe::new(
+            Vec::new(),
+            vec![
+                AggregateField::count("count"),
+                AggregateField::new(AggregateOp::Stdev, "a", "stdev"),
+            ],
+        ));
+
+        let result = operator.evaluate(Pulse::data(series)).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![DataValue::from_pairs(vec![
+                ("count", 2.0.into()),
+                ("stdev", 2.0_f64.sqrt().into())
+            ])])
+        );
+
+        let result = operator.evaluate(Pulse::data(Vec::new())).await;
+        assert_eq!(
+            result,
+            Pulse::data(vec![DataValue::from_pairs(vec![
+                ("count", 0.0.into()),
+                ("stdev", DataItem::Null)
+            ])])
+        );
+    }
+
+    #[test]
+    fn evaluates_map_changes() {
+        let value = |x: f64| DataValue::from_pairs(vec![("x", x.into())]);
+        let mapped = |x: f64| DataValue::from_pairs(vec![("x", x.into()), ("z", (x * 2.0).into())]);
+
+        let operator = MapOperator::new(MapPipe::new("x * 2", "z").unwrap());
+        let mut output = Pulse::data(vec![mapped(1.0), mapped(2.0)]);
+
+        let propagation = operator.evaluate_changes(
+            &mut output,
+            &Changeset::new()
+                .with_add(vec![value(3.0)])
+                .with_rem(vec![value(1.0)])
+                .with_modified(vec![(value(2.0), value(4.0))]),
+        );
+
+        assert_eq!(
+            propagation,
+            Propagation::Changes(
+                Changeset::new()
+                    .with_add(vec![mapped(3.0)])
+                    .with_rem(vec![mapped(1.0)])
+                    .with_modified(vec![(mapped(2.0), mapped(4.0))])
+            )
+        );
+        assert_eq!(output, Pulse::data(vec![mapped(4.0), mapped(3.0)]));
+
+        let operator = MapOperator::new(MapPipe::new("now()", "z").unwrap());
+        assert_eq!(
+            operator.evaluate_changes(&mut output, &Changeset::new().with_add(vec![value(5.0)])),
+            Propagation::Unsupported
+        );
+    }
+
+    #[test]
+    fn evaluates_filter_changes() {
+        let value = |x: f64| DataValue::from_pairs(vec![("x", x.into())]);
+
+        let operator = FilterOperator::new(FilterPipe::new("x > 2").unwrap());
+        let mut output = Pulse::data(vec![value(3.0), value(4.0)]);
+
+        let propagation = operator.evaluate_changes(
+            &mut output,
+            &Changeset::new()
+                .with_add(vec![value(1.0), value(5.0)])
+                .with_rem(vec![value(0.0)])
+                .with_modified(vec![
+                    (value(3.0), value(6.0)),
+                    (value(4.0), value(2.0)),
+                    (value(2.0), value(7.0)),
+                ]),
+        );
+
+        assert_eq!(
+            propagation,
+            Propagation::Changes(
+                Changeset::new()
+                    .with_add(vec![value(5.0), value(7.0)])
+                    .with_rem(vec![value(4.0)])
+                    .with_modified(vec![(value(3.0), value(6.0))])
+            )
+        );
+        assert_eq!(
+            output,
+            Pulse::data(vec![value(6.0), value(5.0), value(7.0)])
+        );
+    }
+
+    #[test]
+    fn evaluates_count_changes() {
+        let value = |x: f64| DataValue::from_pairs(vec![("x", x.into())]);
+        let group = |x: f64, count: f64| {
+            DataValue::from_pairs(vec![("x", x.into()), ("count", count.into())])
+        };
+
+        let operator = GroupOperator::new(GroupPipe::new("x", GroupOperatorSpec::Count, "count"));
+        let mut output = Pulse::data(vec![group(1.0, 2.0), group(2.0, 1.0)]);
+
+        let propagation = operator.evaluate_changes(
+            &mut output,
+            &Changeset::new()
+                .with_add(vec![value(1.0), value(3.0)])
+                .with_modified(vec![(value(2.0), value(3.0))]),
+        );
+
+        assert_eq!(
+            propagation,
+            Propagation::Changes(
+                Changeset::new()
+                    .with_add(vec![group(3.0, 2.0)])
+                    .with_rem(vec![group(2.0, 1.0)])
+                    .with_modified(vec![(group(1.0, 2.0), group(1.0, 3.0))])
+            )
+        );
+        assert_eq!(output, Pulse::data(vec![group(1.0, 3.0), group(3.0, 2.0)]));
+    }
+
+    #[test]
+    fn evaluates_fold_changes() {
+        let value = |a: f64, b: f64| DataValue::from_pairs(vec![("a", a.into()), ("b", b.into())]);
+        let folded = |key: &str, value: f64| {
+            DataValue::from_pairs(vec![("key", key.into()), ("value", value.into())])
+        };
+
+        let operator = FoldOperator::new(FoldPipe::new(vec!["a", "b"]));
+        let mut output = Pulse::data(vec![
+            folded("a", 1.0),
+            folded("b", 2.0),
+            folded("a", 3.0),
+            folded("b", 4.0),
+        ]);
+
+        let propagation = operator.evaluate_changes(
+            &mut output,
+            &Changeset::new()
+                .with_add(vec![value(5.0, 6.0)])
+                .with_rem(vec![value(1.0, 2.0)])
+                .with_modified(vec![(value(3.0, 4.0), value(3.0, 7.0))]),
+        );
+
+        assert_eq!(
+            propagation,
+            Propagation::Changes(
+                Changeset::new()
+                    .with_add(vec![folded("a", 5.0), folded("b", 6.0)])
+                    .with_rem(vec![folded("a", 1.0), folded("b", 2.0)])
+                    .with_modified(vec![
+                        (folded("a", 3.0), folded("a", 3.0)),
+                        (folded("b", 4.0), folded("b", 7.0))
+                    ])
+            )
+        );
+        assert_eq!(
+            output,
+            Pulse::data(vec![
+                folded("a", 3.0),
+                folded("b", 7.0),
+                folded("a", 5.0),
+                folded("b", 6.0)
+            ])
         );
     }
 }
@@ -39,6 +39,18 @@ impl Pulse {
         Pulse::data(Vec::new())
     }
 
+    /// Apply a changeset to the values of a single data `Pulse`. Nothing is changed for other
+    /// pulses, and `false` is returned.
+    pub(crate) fn apply_changes(&mut self, changes: &Changeset) -> bool {
+        match self {
+            Pulse::Single(SinglePulse::Data(values)) => {
+                changes.apply(values);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Merge a collection of pulses together so that if more than one `SinglePulse` is found,
     /// a `MultiPulse` is created collecting all single pulses. Otherwise, a `SinglePulse` is
     /// returned.
@@ -127,15 +139,98 @@ impl MultiPulse {
     }
 }
 
+/// `Changeset` collects the changes of a list of data values: values added, values removed, and
+/// values modified from an old into a new value. Values have no identity, so removed and modified
+/// values are matched by equality.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Changeset {
+    pub(crate) add: Vec<DataValue>,
+    pub(crate) rem: Vec<DataValue>,
+    pub(crate) modified: Vec<(DataValue, DataValue)>,
+}
+
+impl Changeset {
+    /// Create a new `Changeset` instance with no changes.
+    pub fn new() -> Self {
+        Changeset::default()
+    }
+
+    /// Add certain values.
+    pub fn with_add(mut self, values: Vec<DataValue>) -> Self {
+        self.add.extend(values);
+        self
+    }
+
+    /// Remove certain values.
+    pub fn with_rem(mut self, values: Vec<DataValue>) -> Self {
+        self.rem.extend(values);
+        self
+    }
+
+    /// Modify certain values, given as pairs of the old and the new value.
+    pub fn with_modified(mut self, values: Vec<(DataValue, DataValue)>) -> Self {
+        self.modified.extend(values);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.rem.is_empty() && self.modified.is_empty()
+    }
+
+    /// Apply the changes to a list of values. Each removed or modified value replaces the first
+    /// equal value in the list, while added values are appended.
+    pub fn apply(&self, values: &mut Vec<DataValue>) {
+        if !self.rem.is_empty() {
+            let mut pending: Vec<&DataValue> = self.rem.iter().collect();
+
+            values.retain(|value| match pending.iter().position(|rem| *rem == value) {
+                Some(position) => {
+                    pending.swap_remove(position);
+                    false
+                }
+                None => true,
+            });
+        }
+
+        for (old, new) in &self.modified {
+            if let Some(value) = values.iter_mut().find(|value| *value == old) {
+                *value = new.clone();
+            }
+        }
+
+        values.extend(self.add.iter().cloned());
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResolvedDomain {
-    Interval(f32, f32),
+    Interval(f64, f64),
 }
 
 impl ResolvedDomain {
-    pub(crate) fn interval(&self) -> Option<(f32, f32)> {
+    pub(crate) fn interval(&self) -> Option<(f64, f64)> {
         match self {
             ResolvedDomain::Interval(min, max) => Some((*min, *max)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::data::DataValue;
+    use crate::graph::pulse::Changeset;
+
+    #[test]
+    fn applies_changesets() {
+        let value = |a: f64| DataValue::from_pairs(vec![("a", a.into())]);
+        let mut values = vec![value(1.0), value(2.0), value(2.0), value(3.0)];
+
+        Changeset::new()
+            .with_add(vec![value(4.0)])
+            .with_rem(vec![value(2.0), value(5.0)])
+            .with_modified(vec![(value(3.0), value(6.0))])
+            .apply(&mut values);
+
+        assert_eq!(values, vec![value(1.0), value(2.0), value(6.0), value(4.0)]);
+    }
+}
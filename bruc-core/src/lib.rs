@@ -1,21 +1,29 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_std::channel::{bounded, Sender};
 use async_std::stream::{Stream, StreamExt};
 
 use data::DataValue;
+use graph::node::data::DataOperator;
 use graph::node::{Node, Operator};
+use graph::pulse::Changeset;
 use graph::Graph;
 use parser::{DataNode, ParseResult};
 
+use crate::loader::{DataLoader, DefaultLoader, LoadError};
+use crate::parser::error::Error;
 use crate::parser::Parser;
 use crate::render::SceneRenderer;
 use crate::scene::{SceneDimensions, SceneRoot, Scenegraph};
+use crate::spec::format::Format;
+use crate::spec::transform::error::FormatError;
 use crate::spec::Specification;
 
 pub mod data;
 pub mod graph;
-mod parser;
+pub mod loader;
+pub mod parser;
 pub mod render;
 mod scale;
 mod scene;
@@ -36,25 +44,52 @@ pub struct View {
 }
 
 impl View {
-    pub fn build(spec: Specification) -> View {
+    /// Build a view for a certain specification. Data entries with an url are loaded with the
+    /// `DefaultLoader`, which reads files relative to the current working directory of the
+    /// process. Use `View::build_with_loader` to read files relative to another directory, e.g.
+    /// with `FileLoader::new(base)`.
+    pub fn build(spec: Specification) -> Result<View, Error> {
+        View::build_with_loader(spec, DefaultLoader::default())
+    }
+
+    /// Build a view for a certain specification, whose data entries with an url are loaded with
+    /// a certain `loader` once the view is rendered.
+    pub fn build_with_loader(
+        spec: Specification,
+        loader: impl DataLoader + 'static,
+    ) -> Result<View, Error> {
         let dimensions = SceneDimensions {
             width: spec.dimensions.width,
             height: spec.dimensions.height,
         };
-        let ParseResult { graph, collection } = Parser.parse(spec);
+        let ParseResult { graph, collection } = Parser::new(Arc::new(loader)).parse(spec)?;
 
-        View {
+        Ok(View {
             state: ViewState {
                 graph,
                 data_nodes: collection.data,
                 dimensions,
             },
             listeners: Vec::new(),
-        }
+        })
     }
 
-    pub async fn set_data(&mut self, name: &str, values: Vec<DataValue>) {
+    /// Replace the values of a data entry, and notify the listeners with the updated scene. Text
+    /// values of fields declared as dates by the entry's schema are parsed as ISO-8601 dates.
+    /// Entries derived from the data entry are updated as well, while derived entries themselves
+    /// own no values to be replaced.
+    pub async fn set_data(&mut self, name: &str, mut values: Vec<DataValue>) {
         if let Some(node) = self.state.data_nodes.get(name) {
+            if node.derived {
+                return;
+            }
+
+            if let Some(schema) = &node.schema {
+                for value in &mut values {
+                    value.parse_dates(schema);
+                }
+            }
+
             self.state
                 .graph
                 .replace_node(node.source, Node::init(Operator::data(values)));
@@ -66,16 +101,148 @@ impl View {
         }
     }
 
-    pub async fn render<R: SceneRenderer>(&mut self, renderer: R) -> impl Stream<Item = String> {
+    /// Insert values into a data entry, and notify the listeners with the updated scene. Only the
+    /// inserted values are propagated through the graph where possible, instead of evaluating all
+    /// values again. Text values of fields declared as dates by the entry's schema are parsed as
+    /// ISO-8601 dates.
+    pub async fn insert(&mut self, name: &str, mut values: Vec<DataValue>) {
+        if let Some(schema) = self
+            .state
+            .data_nodes
+            .get(name)
+            .and_then(|node| node.schema.as_ref())
+        {
+            for value in &mut values {
+                value.parse_dates(schema);
+            }
+        }
+
+        self.apply_changes(name, |_| Changeset::new().with_add(values))
+            .await;
+    }
+
+    /// Remove the values of a data entry matching a certain `predicate`, and notify the
+    /// listeners with the updated scene, see `insert`.
+    pub async fn remove(&mut self, name: &str, predicate: impl Fn(&DataValue) -> bool) {
+        self.apply_changes(name, |data| {
+            Changeset::new().with_rem(data.select(predicate))
+        })
+        .await;
+    }
+
+    /// Modify the values of a data entry matching a certain `predicate` with an `update`, and
+    /// notify the listeners with the updated scene, see `insert`. Text values of fields declared
+    /// as dates by the entry's schema are parsed as ISO-8601 dates after the update.
+    pub async fn modify(
+        &mut self,
+        name: &str,
+        predicate: impl Fn(&DataValue) -> bool,
+        update: impl Fn(&mut DataValue),
+    ) {
+        let schema = self
+            .state
+            .data_nodes
+            .get(name)
+            .and_then(|node| node.schema.clone());
+
+        self.apply_changes(name, |data| {
+            let modified = data
+                .select(predicate)
+                .into_iter()
+                .map(|old| {
+                    let mut new = old.clone();
+                    update(&mut new);
+                    if let Some(schema) = &schema {
+                        new.parse_dates(schema);
+                    }
+                    (old, new)
+                })
+                .collect();
+
+            Changeset::new().with_modified(modified)
+        })
+        .await;
+    }
+
+    /// Propagate the changes of the values of a data entry through the graph, and notify the
+    /// listeners with the updated scene. Derived entries own no values to be changed.
+    async fn apply_changes(
+        &mut self,
+        name: &str,
+        changes: impl FnOnce(&DataOperator) -> Changeset,
+    ) {
+        let Some(node) = self.state.data_nodes.get(name) else {
+            return;
+        };
+        if node.derived {
+            return;
+        }
+        let Some(Operator::Data(data)) = self
+            .state
+            .graph
+            .nodes
+            .get(node.source)
+            .map(|node| &node.operator)
+        else {
+            return;
+        };
+
+        let changes = changes(data);
+        let items = self.state.graph.build_changes(node.source, changes).await;
+        let scene = Scenegraph::new(SceneRoot::new(items, self.state.dimensions));
+
+        self.notify_listeners(scene).await;
+    }
+
+    /// Replace the values of a data entry with the values read from delimiter-separated `text`
+    /// in a certain `format`, and notify the listeners with the updated scene.
+    pub async fn set_data_csv(
+        &mut self,
+        name: &str,
+        text: &str,
+        format: &Format,
+    ) -> Result<(), FormatError> {
+        let values = format.read(text)?;
+        self.set_data(name, values).await;
+
+        Ok(())
+    }
+
+    /// Render the view with a certain `renderer`, and return the stream of the rendered scenes,
+    /// starting with the current one. Fails if the values of a data entry couldn't be loaded
+    /// from its url.
+    pub async fn render<R: SceneRenderer>(
+        &mut self,
+        renderer: R,
+    ) -> Result<impl Stream<Item = String>, Error> {
         let (sender, recv) = bounded(5);
 
         let items = self.state.graph.build().await;
+        if let Some(error) = self.load_error() {
+            return Err(Error::Load(error));
+        }
         let scene = Scenegraph::new(SceneRoot::new(items, self.state.dimensions));
 
         sender.send(scene).await.unwrap();
         self.listeners.push(sender);
 
-        recv.map(move |scene| renderer.render(&scene))
+        Ok(recv.map(move |scene| renderer.render(&scene)))
+    }
+
+    /// Error of a data entry, whose values couldn't be loaded from its url.
+    fn load_error(&self) -> Option<LoadError> {
+        self.state.data_nodes.values().find_map(|node| {
+            match self
+                .state
+                .graph
+                .nodes
+                .get(node.source)
+                .map(|node| &node.operator)
+            {
+                Some(Operator::Data(data)) if !node.derived => data.error(),
+                _ => None,
+            }
+        })
     }
 
     async fn notify_listeners(&self, scene: Scenegraph) {
@@ -90,13 +257,16 @@ mod tests {
     use std::collections::HashMap;
 
     use async_std::stream::StreamExt;
+    use bruc_expression::types::{DataType, Schema};
 
     use crate::data::DataValue;
+    use crate::loader::MemoryLoader;
     use crate::parser::DataNode;
     use crate::render::DebugRenderer;
     use crate::scene::SceneDimensions;
     use crate::spec::axis::{Axis, AxisOrientation};
     use crate::spec::data::DataEntry;
+    use crate::spec::format::Format;
     use crate::spec::scale::domain::Domain;
     use crate::spec::scale::linear::LinearScale;
     use crate::spec::scale::range::Range;
@@ -104,8 +274,10 @@ mod tests {
     use crate::spec::shape::line::{LinePropertiesBuilder, LineShape};
     use crate::spec::shape::{DataSource, Shape};
     use crate::spec::transform::filter::FilterPipe;
+    use crate::spec::transform::lookup::{LookupField, LookupPipe};
     use crate::spec::transform::map::MapPipe;
     use crate::spec::transform::pipe::Pipe;
+    use crate::spec::transform::regression::RegressionPipe;
     use crate::spec::{Dimensions, Specification, Visual};
     use crate::View;
 
@@ -320,7 +492,7 @@ mod tests {
     #[test]
     fn builds_specification() {
         // when
-        let view = View::build(line_chart_spec());
+        let view = View::build(line_chart_spec()).unwrap();
 
         // then
         assert_eq!(
@@ -339,10 +511,10 @@ mod tests {
     #[tokio::test]
     async fn renders_line() {
         // given
-        let mut view = View::build(line_chart_spec());
+        let mut view = View::build(line_chart_spec()).unwrap();
 
         // when
-        let mut result = view.render(DebugRenderer).await;
+        let mut result = view.render(DebugRenderer).await.unwrap();
         let content = result.next().await;
 
         // then
@@ -355,58 +527,58 @@ mod tests {
     #[tokio::test]
     async fn renders_bar() {
         // given
-        let mut view = View::build(bar_chart_spec());
+        let mut view = View::build(bar_chart_spec()).unwrap();
 
         // when
-        let mut result = view.render(DebugRenderer).await;
+        let mut result = view.render(DebugRenderer).await.unwrap();
         let content = result.next().await;
 
         // then
         assert_eq!(
             content.unwrap(),
-            "Scenegraph { root: SceneRoot { items: [Group(SceneGroup { items: [Rect(SceneRect { width: 150.0, height: 300.0, x: 93.75, y: 0.0, fill: \"blue\" }), Rect(SceneRect { width: 150.0, height: 100.0, x: 468.75, y: 200.0, fill: \"blue\" }), Rect(SceneRect { width: 150.0, height: 166.66667, x: 843.75, y: 133.33333, fill: \"blue\" }), Rect(SceneRect { width: 150.0, height: 0.0, x: 1218.75, y: 300.0, fill: \"blue\" })] }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (1500.0, 0.0) }, ticks: [SceneAxisTick { position: (187.5, 0.0), label: Some(\"0.00\") }, SceneAxisTick { position: (562.5, 0.0), label: Some(\"1.00\") }, SceneAxisTick { position: (937.5, 0.0), label: Some(\"2.00\") }, SceneAxisTick { position: (1312.5, 0.0), label: Some(\"3.00\") }], orientation: Bottom }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (0.0, 300.0) }, ticks: [SceneAxisTick { position: (0.0, 0.0), label: Some(\"6.00\") }, SceneAxisTick { position: (0.0, 29.999998), label: Some(\"8.70\") }, SceneAxisTick { position: (0.0, 59.999996), label: Some(\"11.40\") }, SceneAxisTick { position: (0.0, 90.0), label: Some(\"14.10\") }, SceneAxisTick { position: (0.0, 119.99999), label: Some(\"16.80\") }, SceneAxisTick { position: (0.0, 150.0), label: Some(\"19.50\") }, SceneAxisTick { position: (0.0, 180.0), label: Some(\"22.20\") }, SceneAxisTick { position: (0.0, 210.0), label: Some(\"24.90\") }, SceneAxisTick { position: (0.0, 240.0), label: Some(\"27.60\") }, SceneAxisTick { position: (0.0, 270.0), label: Some(\"30.30\") }, SceneAxisTick { position: (0.0, 300.0), label: Some(\"33.00\") }], orientation: Left })], dimensions: SceneDimensions { width: 1500, height: 300 } } }"
+            "Scenegraph { root: SceneRoot { items: [Group(SceneGroup { items: [Rect(SceneRect { width: 150.0, height: 300.0, x: 93.75, y: 0.0, fill: \"blue\" }), Rect(SceneRect { width: 150.0, height: 100.0, x: 468.75, y: 200.0, fill: \"blue\" }), Rect(SceneRect { width: 150.0, height: 166.66666666666669, x: 843.75, y: 133.33333333333331, fill: \"blue\" }), Rect(SceneRect { width: 150.0, height: 0.0, x: 1218.75, y: 300.0, fill: \"blue\" })] }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (1500.0, 0.0) }, ticks: [SceneAxisTick { position: (187.5, 0.0), label: Some(\"0.00\") }, SceneAxisTick { position: (562.5, 0.0), label: Some(\"1.00\") }, SceneAxisTick { position: (937.5, 0.0), label: Some(\"2.00\") }, SceneAxisTick { position: (1312.5, 0.0), label: Some(\"3.00\") }], orientation: Bottom }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (0.0, 300.0) }, ticks: [SceneAxisTick { position: (0.0, 0.0), label: Some(\"6.00\") }, SceneAxisTick { position: (0.0, 29.999999999999993), label: Some(\"8.70\") }, SceneAxisTick { position: (0.0, 60.0), label: Some(\"11.40\") }, SceneAxisTick { position: (0.0, 90.00000000000001), label: Some(\"14.10\") }, SceneAxisTick { position: (0.0, 120.0), label: Some(\"16.80\") }, SceneAxisTick { position: (0.0, 150.0), label: Some(\"19.50\") }, SceneAxisTick { position: (0.0, 180.00000000000003), label: Some(\"22.20\") }, SceneAxisTick { position: (0.0, 210.00000000000003), label: Some(\"24.90\") }, SceneAxisTick { position: (0.0, 240.0), label: Some(\"27.60\") }, SceneAxisTick { position: (0.0, 270.0), label: Some(\"30.30\") }, SceneAxisTick { position: (0.0, 300.0), label: Some(\"33.00\") }], orientation: Left })], dimensions: SceneDimensions { width: 1500, height: 300 } } }"
         )
     }
 
     #[tokio::test]
     async fn renders_pie() {
         // given
-        let mut view = View::build(pie_chart_spec());
+        let mut view = View::build(pie_chart_spec()).unwrap();
 
         // when
-        let mut result = view.render(DebugRenderer).await;
+        let mut result = view.render(DebugRenderer).await.unwrap();
         let content = result.next().await;
 
         // then
         assert_eq!(
             content.unwrap(),
-            "Scenegraph { root: SceneRoot { items: [Group(SceneGroup { items: [Arc(SceneArc { start_angle: 5.729578, end_angle: 152.67041, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#1F77B4\" }), Arc(SceneArc { start_angle: 164.12958, end_angle: 224.67041, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#FF7F0E\" }), Arc(SceneArc { start_angle: 236.12958, end_angle: 325.47043, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#2CA02C\" }), Arc(SceneArc { start_angle: 336.9296, end_angle: 354.27042, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#D62728\" })] })], dimensions: SceneDimensions { width: 1500, height: 300 } } }"
+            "Scenegraph { root: SceneRoot { items: [Group(SceneGroup { items: [Arc(SceneArc { start_angle: 5.729577951308232, end_angle: 152.67042204869176, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#1F77B4\" }), Arc(SceneArc { start_angle: 164.12957795130825, end_angle: 224.67042204869176, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#FF7F0E\" }), Arc(SceneArc { start_angle: 236.12957795130825, end_angle: 325.47042204869183, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#2CA02C\" }), Arc(SceneArc { start_angle: 336.92957795130826, end_angle: 354.27042204869184, radius: 750.0, outer_radius: None, inner_radius: 50.0, fill: \"#D62728\" })] })], dimensions: SceneDimensions { width: 1500, height: 300 } } }"
         )
     }
 
     #[tokio::test]
     async fn renders_scatter_plot() {
         // given
-        let mut view = View::build(scatter_plot());
+        let mut view = View::build(scatter_plot()).unwrap();
 
         // when
-        let mut result = view.render(DebugRenderer).await;
+        let mut result = view.render(DebugRenderer).await.unwrap();
         let content = result.next().await;
 
         // then
         assert_eq!(
             content.unwrap(),
-            "Scenegraph { root: SceneRoot { items: [Group(SceneGroup { items: [Point(ScenePoint { x: 93.75, y: 0.0, size: 3.0, color: \"red\" }), Point(ScenePoint { x: 468.75, y: 200.0, size: 3.0, color: \"green\" }), Point(ScenePoint { x: 843.75, y: 133.33333, size: 3.0, color: \"blue\" }), Point(ScenePoint { x: 1218.75, y: 300.0, size: 3.0, color: \"orange\" })] }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (1500.0, 0.0) }, ticks: [SceneAxisTick { position: (187.5, 0.0), label: Some(\"0.00\") }, SceneAxisTick { position: (562.5, 0.0), label: Some(\"1.00\") }, SceneAxisTick { position: (937.5, 0.0), label: Some(\"2.00\") }, SceneAxisTick { position: (1312.5, 0.0), label: Some(\"3.00\") }], orientation: Bottom }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (0.0, 300.0) }, ticks: [SceneAxisTick { position: (0.0, 0.0), label: Some(\"6.00\") }, SceneAxisTick { position: (0.0, 29.999998), label: Some(\"8.70\") }, SceneAxisTick { position: (0.0, 59.999996), label: Some(\"11.40\") }, SceneAxisTick { position: (0.0, 90.0), label: Some(\"14.10\") }, SceneAxisTick { position: (0.0, 119.99999), label: Some(\"16.80\") }, SceneAxisTick { position: (0.0, 150.0), label: Some(\"19.50\") }, SceneAxisTick { position: (0.0, 180.0), label: Some(\"22.20\") }, SceneAxisTick { position: (0.0, 210.0), label: Some(\"24.90\") }, SceneAxisTick { position: (0.0, 240.0), label: Some(\"27.60\") }, SceneAxisTick { position: (0.0, 270.0), label: Some(\"30.30\") }, SceneAxisTick { position: (0.0, 300.0), label: Some(\"33.00\") }], orientation: Left })], dimensions: SceneDimensions { width: 1500, height: 300 } } }"
+            "Scenegraph { root: SceneRoot { items: [Group(SceneGroup { items: [Point(ScenePoint { x: 93.75, y: 0.0, size: 3.0, color: \"red\" }), Point(ScenePoint { x: 468.75, y: 200.0, size: 3.0, color: \"green\" }), Point(ScenePoint { x: 843.75, y: 133.33333333333331, size: 3.0, color: \"blue\" }), Point(ScenePoint { x: 1218.75, y: 300.0, size: 3.0, color: \"orange\" })] }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (1500.0, 0.0) }, ticks: [SceneAxisTick { position: (187.5, 0.0), label: Some(\"0.00\") }, SceneAxisTick { position: (562.5, 0.0), label: Some(\"1.00\") }, SceneAxisTick { position: (937.5, 0.0), label: Some(\"2.00\") }, SceneAxisTick { position: (1312.5, 0.0), label: Some(\"3.00\") }], orientation: Bottom }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (0.0, 300.0) }, ticks: [SceneAxisTick { position: (0.0, 0.0), label: Some(\"6.00\") }, SceneAxisTick { position: (0.0, 29.999999999999993), label: Some(\"8.70\") }, SceneAxisTick { position: (0.0, 60.0), label: Some(\"11.40\") }, SceneAxisTick { position: (0.0, 90.00000000000001), label: Some(\"14.10\") }, SceneAxisTick { position: (0.0, 120.0), label: Some(\"16.80\") }, SceneAxisTick { position: (0.0, 150.0), label: Some(\"19.50\") }, SceneAxisTick { position: (0.0, 180.00000000000003), label: Some(\"22.20\") }, SceneAxisTick { position: (0.0, 210.00000000000003), label: Some(\"24.90\") }, SceneAxisTick { position: (0.0, 240.0), label: Some(\"27.60\") }, SceneAxisTick { position: (0.0, 270.0), label: Some(\"30.30\") }, SceneAxisTick { position: (0.0, 300.0), label: Some(\"33.00\") }], orientation: Left })], dimensions: SceneDimensions { width: 1500, height: 300 } } }"
         )
     }
 
     #[tokio::test]
     async fn renders_after_set_data() {
         // given
-        let mut view = View::build(line_chart_spec());
+        let mut view = View::build(line_chart_spec()).unwrap();
 
         // when
-        let mut result = view.render(DebugRenderer).await;
+        let mut result = view.render(DebugRenderer).await.unwrap();
         let first = result.next().await;
 
         view.set_data(
@@ -430,4 +602,430 @@ mod tests {
             "Scenegraph { root: SceneRoot { items: [Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(20.0, 8.0), (16.0, 10.0)] }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (40.0, 0.0) }, ticks: [SceneAxisTick { position: (0.0, 0.0), label: Some(\"0.00\") }, SceneAxisTick { position: (4.0, 0.0), label: Some(\"2.00\") }, SceneAxisTick { position: (8.0, 0.0), label: Some(\"4.00\") }, SceneAxisTick { position: (12.0, 0.0), label: Some(\"6.00\") }, SceneAxisTick { position: (16.0, 0.0), label: Some(\"8.00\") }, SceneAxisTick { position: (20.0, 0.0), label: Some(\"10.00\") }, SceneAxisTick { position: (24.0, 0.0), label: Some(\"12.00\") }, SceneAxisTick { position: (28.0, 0.0), label: Some(\"14.00\") }, SceneAxisTick { position: (32.0, 0.0), label: Some(\"16.00\") }, SceneAxisTick { position: (36.0, 0.0), label: Some(\"18.00\") }, SceneAxisTick { position: (40.0, 0.0), label: Some(\"20.00\") }], orientation: Bottom }), Axis(SceneAxis { rule: SceneAxisRule { from: (0.0, 0.0), to: (0.0, 20.0) }, ticks: [SceneAxisTick { position: (0.0, 0.0), label: Some(\"0.00\") }, SceneAxisTick { position: (0.0, 2.0), label: Some(\"2.00\") }, SceneAxisTick { position: (0.0, 4.0), label: Some(\"4.00\") }, SceneAxisTick { position: (0.0, 6.0), label: Some(\"6.00\") }, SceneAxisTick { position: (0.0, 8.0), label: Some(\"8.00\") }, SceneAxisTick { position: (0.0, 10.0), label: Some(\"10.00\") }, SceneAxisTick { position: (0.0, 12.0), label: Some(\"12.00\") }, SceneAxisTick { position: (0.0, 14.0), label: Some(\"14.00\") }, SceneAxisTick { position: (0.0, 16.0), label: Some(\"16.00\") }, SceneAxisTick { position: (0.0, 18.0), label: Some(\"18.00\") }, SceneAxisTick { position: (0.0, 20.0), label: Some(\"20.00\") }], orientation: Left })], dimensions: SceneDimensions { width: 40, height: 20 } } }"
         );
     }
+
+    #[tokio::test]
+    async fn renders_after_set_data_csv() {
+        // given
+        let mut view = View::build(line_chart_spec()).unwrap();
+        let mut expected_view = View::build(line_chart_spec()).unwrap();
+
+        // when
+        let mut result = view.render(DebugRenderer).await.unwrap();
+        result.next().await;
+        view.set_data_csv("primary", "a\n10\n8\n", &Format::csv())
+            .await
+            .unwrap();
+
+        let mut expected = expected_view.render(DebugRenderer).await.unwrap();
+        expected.next().await;
+        expected_view
+            .set_data(
+                "primary",
+                vec![
+                    DataValue::from_pairs(vec![("a", 10.0.into())]),
+                    DataValue::from_pairs(vec![("a", 8.0.into())]),
+                ],
+            )
+            .await;
+
+        // then
+        assert_eq!(result.next().await, expected.next().await);
+        assert!(view
+            .set_data_csv("primary", "a\n\"10", &Format::csv())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn renders_data_loaded_from_url() {
+        // given
+        let mut spec = line_chart_spec();
+        spec.data = spec
+            .data
+            .into_iter()
+            .map(|entry| {
+                DataEntry::new(&entry.name, Vec::new(), entry.transform)
+                    .with_url("primary.csv")
+                    .with_format(Format::csv())
+            })
+            .collect();
+        let loader = MemoryLoader::new().with_source("primary.csv", "a\n5\n13\n");
+
+        let mut view = View::build_with_loader(spec, loader).unwrap();
+        let mut expected_view = View::build(line_chart_spec()).unwrap();
+
+        // when
+        let mut result = view.render(DebugRenderer).await.unwrap();
+        let mut expected = expected_view.render(DebugRenderer).await.unwrap();
+
+        // then
+        assert_eq!(result.next().await, expected.next().await);
+    }
+
+    #[tokio::test]
+    async fn doesnt_render_data_failing_to_load() {
+        // given
+        let spec = |url: &str| {
+            Specification::new(
+                Dimensions::new(40, 20),
+                vec![DataEntry::new("primary", Vec::new(), Vec::new()).with_url(url)],
+                Vec::new(),
+                Visual::new(
+                    vec![Shape::line(
+                        "primary",
+                        LineShape::new(
+                            LinePropertiesBuilder::new()
+                                .with_x(DataSource::field("a", None))
+                                .with_y(DataSource::field("b", None))
+                                .build(),
+                        ),
+                    )],
+                    Vec::new(),
+                ),
+            )
+        };
+        let loader = MemoryLoader::new().with_source("invalid.json", "{ a: 1 }");
+
+        // when
+        let mut missing = View::build_with_loader(spec("missing.json"), loader.clone()).unwrap();
+        let mut invalid = View::build_with_loader(spec("invalid.json"), loader).unwrap();
+
+        // then
+        assert_eq!(
+            missing
+                .render(DebugRenderer)
+                .await
+                .err()
+                .unwrap()
+                .to_string(),
+            "LoadError::NotFound: missing.json"
+        );
+        assert!(invalid
+            .render(DebugRenderer)
+            .await
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("LoadError::Invalid: invalid.json: FormatError::InvalidJson"));
+    }
+
+    #[tokio::test]
+    async fn renders_derived_data_after_set_data() {
+        // given
+        let line = |from: &str| {
+            Shape::line(
+                from,
+                LineShape::new(
+                    LinePropertiesBuilder::new()
+                        .with_x(DataSource::field("a", None))
+                        .with_y(DataSource::field("a", None))
+                        .build(),
+                ),
+            )
+        };
+        let spec = Specification::new(
+            Dimensions::new(40, 20),
+            vec![
+                DataEntry::new(
+                    "primary",
+                    vec![
+                        DataValue::from_pairs(vec![("a", 5.0.into())]),
+                        DataValue::from_pairs(vec![("a", 13.0.into())]),
+                    ],
+                    Vec::new(),
+                ),
+                DataEntry::new(
+                    "large",
+                    Vec::new(),
+                    vec![Pipe::Filter(FilterPipe::new("a > 6").unwrap())],
+                )
+                .with_source("primary"),
+            ],
+            Vec::new(),
+            Visual::new(vec![line("primary"), line("large")], Vec::new()),
+        );
+        let mut view = View::build(spec).unwrap();
+
+        // when
+        let mut result = view.render(DebugRenderer).await.unwrap();
+        let first = result.next().await;
+
+        view.set_data(
+            "primary",
+            vec![
+                DataValue::from_pairs(vec![("a", 10.0.into())]),
+                DataValue::from_pairs(vec![("a", 2.0.into())]),
+            ],
+        )
+        .await;
+        let second = result.next().await;
+
+        // then
+        assert_eq!(
+            first.unwrap(),
+            "Scenegraph { root: SceneRoot { items: [Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(5.0, 15.0), (13.0, 7.0)] }), Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(13.0, 7.0)] })], dimensions: SceneDimensions { width: 40, height: 20 } } }"
+        );
+        assert_eq!(
+            second.unwrap(),
+            "Scenegraph { root: SceneRoot { items: [Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(10.0, 10.0), (2.0, 18.0)] }), Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(10.0, 10.0)] })], dimensions: SceneDimensions { width: 40, height: 20 } } }"
+        );
+    }
+
+    /// Render the line chart after applying certain changes to its data, along with the line
+    /// chart after replacing its data with the `expected` values.
+    async fn render_changes<F>(changes: F, expected: Vec<DataValue>) -> (String, String)
+    where
+        F: AsyncFnOnce(&mut View),
+    {
+        let mut view = View::build(line_chart_spec()).unwrap();
+        let mut result = view.render(DebugRenderer).await.unwrap();
+        result.next().await;
+        changes(&mut view).await;
+
+        let mut expected_view = View::build(line_chart_spec()).unwrap();
+        let mut expected_result = expected_view.render(DebugRenderer).await.unwrap();
+        expected_result.next().await;
+        expected_view.set_data("primary", expected).await;
+
+        (
+            result.next().await.unwrap(),
+            expected_result.next().await.unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn renders_after_insert() {
+        let (result, expected) = render_changes(
+            async |view: &mut View| {
+                view.insert(
+                    "primary",
+                    vec![DataValue::from_pairs(vec![("a", 8.0.into())])],
+                )
+                .await
+            },
+            vec![
+                DataValue::from_pairs(vec![("a", 5.0.into())]),
+                DataValue::from_pairs(vec![("a", 13.0.into())]),
+                DataValue::from_pairs(vec![("a", 8.0.into())]),
+            ],
+        )
+        .await;
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn renders_after_remove() {
+        let (result, expected) = render_changes(
+            async |view: &mut View| {
+                view.remove("primary", |value| value.get_number("a") == Some(&5.0))
+                    .await
+            },
+            vec![DataValue::from_pairs(vec![("a", 13.0.into())])],
+        )
+        .await;
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn renders_after_modify() {
+        let (result, expected) = render_changes(
+            async |view: &mut View| {
+                view.modify(
+                    "primary",
+                    |value| value.get_number("a") == Some(&13.0),
+                    |value| value.insert("a", 15.0.into()),
+                )
+                .await
+            },
+            vec![
+                DataValue::from_pairs(vec![("a", 5.0.into())]),
+                DataValue::from_pairs(vec![("a", 15.0.into())]),
+            ],
+        )
+        .await;
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn renders_after_modify_with_dates() {
+        let spec = || {
+            let mut spec = line_chart_spec();
+            spec.data[0] = DataEntry::new(
+                "primary",
+                vec![
+                    DataValue::from_pairs(vec![("a", 5.0.into()), ("d", "2024-03-01".into())]),
+                    DataValue::from_pairs(vec![("a", 13.0.into()), ("d", "2024-06-01".into())]),
+                ],
+                vec![Pipe::Map(MapPipe::new("month(d) * 2", "b").unwrap())],
+            )
+            .with_schema(Schema::from_pairs(vec![
+                ("a", DataType::Number),
+                ("d", DataType::Date),
+            ]));
+            spec
+        };
+
+        let mut view = View::build(spec()).unwrap();
+        let mut result = view.render(DebugRenderer).await.unwrap();
+        result.next().await;
+        view.modify(
+            "primary",
+            |value| value.get_number("a") == Some(&13.0),
+            |value| value.insert("d", "2024-09-01".into()),
+        )
+        .await;
+
+        let mut expected_view = View::build(spec()).unwrap();
+        let mut expected_result = expected_view.render(DebugRenderer).await.unwrap();
+        expected_result.next().await;
+        expected_view
+            .set_data(
+                "primary",
+                vec![
+                    DataValue::from_pairs(vec![("a", 5.0.into()), ("d", "2024-03-01".into())]),
+                    DataValue::from_pairs(vec![("a", 13.0.into()), ("d", "2024-09-01".into())]),
+                ],
+            )
+            .await;
+
+        let result = result.next().await.unwrap();
+        assert_eq!(result, expected_result.next().await.unwrap());
+        assert!(result.contains("(26.0, 4.0)"));
+    }
+
+    #[tokio::test]
+    async fn renders_lookup_data_after_set_data() {
+        // given
+        let spec = Specification::new(
+            Dimensions::new(40, 20),
+            vec![
+                DataEntry::new(
+                    "categories",
+                    vec![
+                        DataValue::from_pairs(vec![("id", 1.0.into()), ("y", 4.0.into())]),
+                        DataValue::from_pairs(vec![("id", 2.0.into()), ("y", 8.0.into())]),
+                    ],
+                    Vec::new(),
+                ),
+                DataEntry::new(
+                    "facts",
+                    vec![
+                        DataValue::from_pairs(vec![("category", 1.0.into()), ("x", 5.0.into())]),
+                        DataValue::from_pairs(vec![("category", 2.0.into()), ("x", 13.0.into())]),
+                    ],
+                    vec![Pipe::Lookup(LookupPipe::new(
+                        "categories",
+                        "id",
+                        "category",
+                        vec![LookupField::new("y")],
+                    ))],
+                ),
+            ],
+            Vec::new(),
+            Visual::new(
+                vec![Shape::line(
+                    "facts",
+                    LineShape::new(
+                        LinePropertiesBuilder::new()
+                            .with_x(DataSource::field("x", None))
+                            .with_y(DataSource::field("y", None))
+                            .build(),
+                    ),
+                )],
+                Vec::new(),
+            ),
+        );
+        let mut view = View::build(spec).unwrap();
+
+        // when
+        let mut result = view.render(DebugRenderer).await.unwrap();
+        let first = result.next().await;
+
+        view.set_data(
+            "categories",
+            vec![
+                DataValue::from_pairs(vec![("id", 1.0.into()), ("y", 10.0.into())]),
+                DataValue::from_pairs(vec![("id", 2.0.into()), ("y", 2.0.into())]),
+            ],
+        )
+        .await;
+        let second = result.next().await;
+
+        view.set_data(
+            "facts",
+            vec![DataValue::from_pairs(vec![
+                ("category", 2.0.into()),
+                ("x", 1.0.into()),
+            ])],
+        )
+        .await;
+        let third = result.next().await;
+
+        // then
+        assert_eq!(
+            first.unwrap(),
+            "Scenegraph { root: SceneRoot { items: [Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(5.0, 16.0), (13.0, 12.0)] })], dimensions: SceneDimensions { width: 40, height: 20 } } }"
+        );
+        assert_eq!(
+            second.unwrap(),
+            "Scenegraph { root: SceneRoot { items: [Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(5.0, 10.0), (13.0, 18.0)] })], dimensions: SceneDimensions { width: 40, height: 20 } } }"
+        );
+        assert_eq!(
+            third.unwrap(),
+            "Scenegraph { root: SceneRoot { items: [Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(1.0, 18.0)] })], dimensions: SceneDimensions { width: 40, height: 20 } } }"
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_regression_line() {
+        // given
+        let spec = Specification::new(
+            Dimensions::new(40, 20),
+            vec![
+                DataEntry::new(
+                    "points",
+                    vec![
+                        DataValue::from_pairs(vec![("a", 2.0.into()), ("b", 3.0.into())]),
+                        DataValue::from_pairs(vec![("a", 6.0.into()), ("b", 5.0.into())]),
+                        DataValue::from_pairs(vec![("a", 4.0.into()), ("b", 5.0.into())]),
+                        DataValue::from_pairs(vec![("a", 4.0.into()), ("b", 3.0.into())]),
+                    ],
+                    Vec::new(),
+                ),
+                DataEntry::new(
+                    "trend",
+                    Vec::new(),
+                    vec![Pipe::Regression(RegressionPipe::new("a", "b"))],
+                )
+                .with_source("points"),
+            ],
+            Vec::new(),
+            Visual::new(
+                vec![Shape::line(
+                    "trend",
+                    LineShape::new(
+                        LinePropertiesBuilder::new()
+                            .with_x(DataSource::field("a", None))
+                            .with_y(DataSource::field("b", None))
+                            .build(),
+                    ),
+                )],
+                Vec::new(),
+            ),
+        );
+        let mut view = View::build(spec).unwrap();
+
+        // when
+        let mut result = view.render(DebugRenderer).await.unwrap();
+        let first = result.next().await;
+
+        // then
+        assert_eq!(
+            first.unwrap(),
+            "Scenegraph { root: SceneRoot { items: [Line(SceneLine { stroke: \"black\", stroke_width: 1.0, points: [(2.0, 17.0), (6.0, 15.0)] })], dimensions: SceneDimensions { width: 40, height: 20 } } }"
+        );
+    }
 }
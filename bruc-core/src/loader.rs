@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::{error, fmt};
+
+use futures::future::LocalBoxFuture;
+
+/// `LoadFuture` resolves the text of the data loaded from an url.
+pub type LoadFuture<'a> = LocalBoxFuture<'a, Result<String, LoadError>>;
+
+/// `DataLoader` loads the text of the data entries declared with an url, e.g. from the
+/// filesystem or the network. Data is loaded once the graph is evaluated.
+pub trait DataLoader: fmt::Debug + Send + Sync {
+    fn load<'a>(&'a self, url: &'a str) -> LoadFuture<'a>;
+}
+
+/// Loader used by default to build a view, reading from the filesystem where available. Paths
+/// are relative to the current working directory of the process, see `FileLoader::default`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type DefaultLoader = FileLoader;
+
+/// Loader used by default to build a view. No filesystem is available on the web, so no data is
+/// loaded.
+#[cfg(target_arch = "wasm32")]
+pub type DefaultLoader = MemoryLoader;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LoadError {
+    /// No data is found for a certain url.
+    NotFound(String),
+    /// Data of a certain url isn't allowed to be loaded by the loader.
+    Forbidden(String),
+    /// Data of a certain url couldn't be loaded, for a certain reason.
+    Failed(String, String),
+    /// Data of a certain url couldn't be read in the entry's format, for a certain reason.
+    Invalid(String, String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::NotFound(url) => write!(f, "LoadError::NotFound: {url}"),
+            LoadError::Forbidden(url) => write!(f, "LoadError::Forbidden: {url}"),
+            LoadError::Failed(url, reason) => write!(f, "LoadError::Failed: {url}: {reason}"),
+            LoadError::Invalid(url, reason) => write!(f, "LoadError::Invalid: {url}: {reason}"),
+        }
+    }
+}
+
+impl error::Error for LoadError {}
+
+/// `FileLoader` reads data from files, whose paths are relative to a `base` directory. Urls with
+/// the `file://` scheme are read as paths. Absolute paths, paths with `..` components and paths
+/// resolving outside of the base directory through symbolic links are forbidden, so that only
+/// files inside of the base directory are read.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileLoader {
+    base: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileLoader {
+    pub fn new(base: impl Into<std::path::PathBuf>) -> Self {
+        FileLoader { base: base.into() }
+    }
+}
+
+/// Loader reading files relative to the current working directory of the process, which is
+/// resolved each time a file is loaded.
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for FileLoader {
+    fn default() -> Self {
+        FileLoader::new(".")
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DataLoader for FileLoader {
+    fn load<'a>(&'a self, url: &'a str) -> LoadFuture<'a> {
+        Box::pin(async move {
+            let path = std::path::Path::new(url.strip_prefix("file://").unwrap_or(url));
+
+            let inside_base = path.components().all(|component| {
+                matches!(
+                    component,
+                    std::path::Component::Normal(_) | std::path::Component::CurDir
+                )
+            });
+            if !inside_base {
+                return Err(LoadError::Forbidden(url.to_string()));
+            }
+
+            let error = |error: std::io::Error| match error.kind() {
+                std::io::ErrorKind::NotFound => LoadError::NotFound(url.to_string()),
+                _ => LoadError::Failed(url.to_string(), error.to_string()),
+            };
+
+            // Symbolic links are resolved, so that they can't point outside of the base
+            let base = async_std::fs::canonicalize(&self.base)
+                .await
+                .map_err(error)?;
+            let path = async_std::fs::canonicalize(self.base.join(path))
+                .await
+                .map_err(error)?;
+            if !path.starts_with(&base) {
+                return Err(LoadError::Forbidden(url.to_string()));
+            }
+
+            async_std::fs::read_to_string(path).await.map_err(error)
+        })
+    }
+}
+
+/// `MemoryLoader` serves data kept in memory for certain urls.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryLoader {
+    sources: HashMap<String, String>,
+}
+
+impl MemoryLoader {
+    pub fn new() -> Self {
+        MemoryLoader::default()
+    }
+
+    /// Serve a certain `text` for an `url`.
+    pub fn with_source(mut self, url: &str, text: &str) -> Self {
+        self.sources.insert(url.to_string(), text.to_string());
+        self
+    }
+}
+
+impl DataLoader for MemoryLoader {
+    fn load<'a>(&'a self, url: &'a str) -> LoadFuture<'a> {
+        Box::pin(async move {
+            self.sources
+                .get(url)
+                .cloned()
+                .ok_or_else(|| LoadError::NotFound(url.to_string()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loader::{DataLoader, FileLoader, LoadError, MemoryLoader};
+
+    #[tokio::test]
+    async fn loads_from_memory() {
+        let loader = MemoryLoader::new().with_source("data.csv", "a\n1");
+
+        assert_eq!(loader.load("data.csv").await, Ok("a\n1".to_string()));
+        assert_eq!(
+            loader.load("other.csv").await,
+            Err(LoadError::NotFound("other.csv".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn loads_from_files() {
+        let base = std::env::temp_dir().join("bruc-loads-from-files");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("data.csv"), "a\n1").unwrap();
+
+        let loader = FileLoader::new(&base);
+        assert_eq!(loader.load("data.csv").await, Ok("a\n1".to_string()));
+        assert_eq!(loader.load("file://data.csv").await, Ok("a\n1".to_string()));
+        assert_eq!(
+            loader.load("missing.csv").await,
+            Err(LoadError::NotFound("missing.csv".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn doesnt_load_files_outside_of_base() {
+        let base = std::env::temp_dir().join("bruc-doesnt-load-files-outside-of-base");
+        std::fs::create_dir_all(base.join("nested")).unwrap();
+        std::fs::write(base.join("data.csv"), "a\n1").unwrap();
+
+        let loader = FileLoader::new(base.join("nested"));
+        for url in [
+            "../data.csv",
+            "nested/../../data.csv",
+            "file:///etc/passwd",
+            "/etc/passwd",
+        ] {
+            assert_eq!(
+                loader.load(url).await,
+                Err(LoadError::Forbidden(url.to_string()))
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn doesnt_load_files_linked_outside_of_base() {
+        let base = std::env::temp_dir().join("bruc-doesnt-load-files-linked-outside-of-base");
+        std::fs::create_dir_all(base.join("nested")).unwrap();
+        std::fs::write(base.join("data.csv"), "a\n1").unwrap();
+        std::fs::write(base.join("nested/data.csv"), "a\n2").unwrap();
+        for (link, target) in [("outside.csv", "../data.csv"), ("inside.csv", "data.csv")] {
+            let link = base.join("nested").join(link);
+            let _ = std::fs::remove_file(&link);
+            std::os::unix::fs::symlink(target, link).unwrap();
+        }
+
+        let loader = FileLoader::new(base.join("nested"));
+        assert_eq!(
+            loader.load("outside.csv").await,
+            Err(LoadError::Forbidden("outside.csv".to_string()))
+        );
+        assert_eq!(loader.load("inside.csv").await, Ok("a\n2".to_string()));
+    }
+}
@@ -0,0 +1,43 @@
+use std::{error, fmt};
+
+use crate::loader::LoadError;
+use crate::spec::transform::error::Error as TransformError;
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    /// Values of a data entry couldn't be loaded from its url.
+    Load(LoadError),
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    /// A pipe of a data entry reads a field, which is not present in the entry's values.
+    UnknownField { data: String, field: String },
+    /// A data entry is derived from, or looks up values in, a source, which is not declared
+    /// before the entry.
+    UnknownSource { data: String, source: String },
+    /// A pipe of a data entry can't be applied to the types of the entry's values.
+    InvalidPipe { data: String, error: TransformError },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(error) => match error {
+                ParseError::UnknownField { data, field } => {
+                    write!(f, "ParseError::UnknownField: {field} in data {data}")
+                }
+                ParseError::UnknownSource { data, source } => {
+                    write!(f, "ParseError::UnknownSource: {source} in data {data}")
+                }
+                ParseError::InvalidPipe { data, error } => {
+                    write!(f, "ParseError::InvalidPipe: {error} in data {data}")
+                }
+            },
+            Error::Load(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -1,14 +1,22 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
 
 use bruc_expression::data::DataItem;
+use bruc_expression::path;
+use bruc_expression::types::Schema;
 
 use crate::data::DataValue;
+use crate::graph::node::data::DataUrl;
 use crate::graph::node::shape::{
     SceneWindow, PIE_OUTER_RADIUS_FIELD_NAME, PIE_VALUE_FIELD_NAME, POINT_COLOR_FIELD_NAME,
     POINT_SIZE_FIELD_NAME,
 };
+use crate::loader::DataLoader;
+use crate::parser::error::{Error, ParseError};
 use crate::scale::Scale;
 use crate::spec::axis::Axis;
+use crate::spec::format::Format;
 use crate::spec::scale::band::BandScale;
 use crate::spec::scale::linear::LinearScale;
 use crate::spec::scale::log::LogScale;
@@ -16,11 +24,14 @@ use crate::spec::scale::range::Range;
 use crate::spec::scale::{Scale as ScaleSpec, ScaleKind as ScaleSpecKind};
 use crate::spec::shape::bar::BarShape;
 use crate::spec::shape::base::{
-    BaseShapeProperties, HEIGHT_FIELD_NAME, WIDTH_FIELD_NAME, X_AXIS_FIELD_NAME, Y_AXIS_FIELD_NAME,
+    BaseShapeProperties, HEIGHT_FIELD_NAME, WIDTH_FIELD_NAME, X2_AXIS_FIELD_NAME,
+    X_AXIS_FIELD_NAME, Y2_AXIS_FIELD_NAME, Y_AXIS_FIELD_NAME,
 };
 use crate::spec::shape::line::LineShape;
 use crate::spec::shape::point::PointShape;
 use crate::spec::shape::{DataSource, Shape, ShapeKind};
+use crate::spec::transform::lookup::LookupPipe;
+use crate::spec::transform::pipe::Pipe;
 use crate::spec::Dimensions;
 use crate::{
     graph::{node::Operator, Graph},
@@ -28,6 +39,8 @@ use crate::{
     Specification,
 };
 
+pub mod error;
+
 /// `ParseResult` collects all the data needed after parsing the `Specification`
 #[derive(Debug)]
 pub(crate) struct ParseResult {
@@ -51,39 +64,81 @@ pub(crate) struct ParsedNodeCollection {
     pub(crate) scales: HashMap<String, usize>,
     pub(crate) axis: HashMap<String, usize>,
     pub(crate) shapes: Vec<usize>,
+    /// Fields of the data values read by each transform node.
+    pub(crate) dependencies: HashMap<usize, BTreeSet<String>>,
 }
 
 #[derive(Default, Debug, PartialEq)]
 pub(crate) struct DataNode {
+    /// Node of the data values. Derived entries share the node of the entry they read from.
     pub(crate) source: usize,
     pub(crate) out: usize,
+    /// Declared schema of the data values, used to parse the dates of values set later on.
+    pub(crate) schema: Option<Schema>,
+    /// Whether the entry reads the values of another entry, instead of owning its values.
+    pub(crate) derived: bool,
 }
 
 impl DataNode {
     pub(crate) fn new(source: usize, out: usize) -> Self {
-        DataNode { source, out }
+        DataNode {
+            source,
+            out,
+            schema: None,
+            derived: false,
+        }
+    }
+
+    /// Create a `DataNode` of an entry derived from the values of another entry.
+    pub(crate) fn derived(source: usize, out: usize) -> Self {
+        DataNode {
+            derived: true,
+            ..DataNode::new(source, out)
+        }
+    }
+
+    pub(crate) fn with_schema(mut self, schema: Option<Schema>) -> Self {
+        self.schema = schema;
+        self
     }
 }
 
 /// `Parser` allows to parse a certain `Specification` into a `Graph` representation, where
 /// nodes are generated from the different specification parts, and inter-connected accordingly.
-pub(crate) struct Parser;
+/// Data entries with an url are loaded by the parser's `DataLoader`.
+pub(crate) struct Parser {
+    loader: Arc<dyn DataLoader>,
+}
 
 impl Parser {
+    pub(crate) fn new(loader: Arc<dyn DataLoader>) -> Self {
+        Parser { loader }
+    }
+
     /// Parse a specification instance into a new graph.
-    pub(crate) fn parse(&self, specification: Specification) -> ParseResult {
+    pub(crate) fn parse(&self, specification: Specification) -> Result<ParseResult, Error> {
         let mut result = ParseResult::new();
 
-        self.walk_spec(specification, &mut result);
+        self.walk_spec(specification, &mut result)?;
 
-        result
+        Ok(result)
     }
 
-    fn walk_spec(&self, specification: Specification, result: &mut ParseResult) {
-        let visitor = Visitor::new(specification.dimensions, &specification.scales);
+    fn walk_spec(
+        &self,
+        specification: Specification,
+        result: &mut ParseResult,
+    ) -> Result<(), Error> {
+        let visitor = Visitor::new(
+            specification.dimensions,
+            &specification.scales,
+            self.loader.clone(),
+        );
 
+        // Fields of the values each data entry outputs, if known in advance
+        let mut fields = HashMap::new();
         for entry in specification.data {
-            visitor.visit_data(entry, result);
+            visitor.visit_data(entry, &mut fields, result)?;
         }
 
         for shape in specification.visual.shapes {
@@ -93,35 +148,172 @@ impl Parser {
         for axis in specification.visual.axes {
             visitor.visit_axis(axis, result);
         }
+
+        Ok(())
     }
 }
 
 struct Visitor {
     dimensions: Dimensions,
     scales: HashMap<String, ScaleSpec>,
+    loader: Arc<dyn DataLoader>,
 }
 
 impl Visitor {
-    fn new(dimensions: Dimensions, scales: &[ScaleSpec]) -> Self {
+    fn new(dimensions: Dimensions, scales: &[ScaleSpec], loader: Arc<dyn DataLoader>) -> Self {
         let scales = scales
             .iter()
             .map(|scale| (scale.name.clone(), scale.clone()))
             .collect::<HashMap<String, ScaleSpec>>();
 
-        Visitor { dimensions, scales }
+        Visitor {
+            dimensions,
+            scales,
+            loader,
+        }
     }
 
-    fn visit_data(&self, data: DataEntry, result: &mut ParseResult) {
-        let data_node = result.graph.add_node(Operator::data(data.values));
+    /// Visit a data entry, whose transform pipes are connected either to its own values, or to
+    /// the output of the entry it's derived from. Entries can only be derived from, or look up
+    /// values in, the entries declared before them.
+    fn visit_data(
+        &self,
+        data: DataEntry,
+        entry_fields: &mut HashMap<String, Option<BTreeSet<String>>>,
+        result: &mut ParseResult,
+    ) -> Result<(), Error> {
+        // Types are checked once the fields read by the pipes are known to be present
+        let checked = data.check();
+
+        let (data_node, mut out_node, mut fields) = match &data.source {
+            Some(source) => {
+                let Some(node) = result.collection.data.get(source) else {
+                    return Err(Error::Parse(ParseError::UnknownSource {
+                        data: data.name,
+                        source: source.clone(),
+                    }));
+                };
+
+                // Fields of a derived entry are the ones output by its source
+                let fields = entry_fields.get(source).cloned().flatten();
+                (node.source, node.out, fields)
+            }
+            None => {
+                // Pipes are only validated if the fields of the data values are known in advance
+                let fields = data.fields();
+
+                let operator = match data.url {
+                    Some(url) => Operator::data_url(DataUrl::new(
+                        url,
+                        data.format.unwrap_or_else(Format::json),
+                        data.schema.clone(),
+                        self.loader.clone(),
+                    )),
+                    None => Operator::data(data.values),
+                };
+                let data_node = result.graph.add_node(operator);
+
+                (data_node, data_node, fields)
+            }
+        };
+
+        for pipe in data.transform {
+            let dependencies: BTreeSet<String> =
+                pipe.fields().into_iter().map(str::to_string).collect();
+
+            if let Some(fields) = fields.as_mut() {
+                // Paths to nested items are known, as long as their root field is
+                let unknown = dependencies.iter().find(|field| {
+                    !fields.contains(field.as_str()) && !fields.contains(path::root(field))
+                });
+
+                if let Some(field) = unknown {
+                    return Err(Error::Parse(ParseError::UnknownField {
+                        data: data.name,
+                        field: field.clone(),
+                    }));
+                }
+
+                pipe.update_fields(fields);
+            }
+
+            // The following pipes can't be validated without knowing the fields of the values
+            if pipe.has_dynamic_fields() {
+                fields = None;
+            }
 
-        let out_node = data.transform.into_iter().fold(data_node, |acc, pipe| {
-            result.graph.add(Operator::transform(pipe), vec![acc])
-        });
+            let (operator, sources) = match pipe {
+                Pipe::Lookup(lookup) => {
+                    self.visit_lookup(lookup, &data.name, out_node, entry_fields, result)?
+                }
+                pipe => (Operator::transform(pipe), vec![out_node]),
+            };
+
+            out_node = result.graph.add(operator, sources);
+            result
+                .collection
+                .dependencies
+                .insert(out_node, dependencies);
+        }
 
-        result
-            .collection
-            .data
-            .insert(data.name, DataNode::new(data_node, out_node));
+        if let Err(error) = checked {
+            return Err(Error::Parse(ParseError::InvalidPipe {
+                data: data.name,
+                error,
+            }));
+        }
+
+        let node = match data.source {
+            Some(_) => DataNode::derived(data_node, out_node),
+            None => DataNode::new(data_node, out_node).with_schema(data.schema),
+        };
+        result.collection.data.insert(data.name.clone(), node);
+        entry_fields.insert(data.name, fields);
+
+        Ok(())
+    }
+
+    /// Visit a lookup pipe of a data entry `name`, whose operator reads both the values of the
+    /// pipe's `source` node and the output of the `from` entry. Returns the operator together
+    /// with its sources.
+    fn visit_lookup(
+        &self,
+        lookup: LookupPipe,
+        name: &str,
+        source: usize,
+        entry_fields: &HashMap<String, Option<BTreeSet<String>>>,
+        result: &ParseResult,
+    ) -> Result<(Operator, Vec<usize>), Error> {
+        let Some(from) = result.collection.data.get(&lookup.from) else {
+            return Err(Error::Parse(ParseError::UnknownSource {
+                data: name.to_string(),
+                source: lookup.from,
+            }));
+        };
+
+        // Fields read from the `from` entry are validated, if its output fields are known
+        if let Some(fields) = entry_fields.get(&lookup.from).and_then(Option::as_ref) {
+            let unknown = lookup
+                .from_fields()
+                .into_iter()
+                .find(|field| !fields.contains(*field) && !fields.contains(path::root(field)));
+
+            if let Some(field) = unknown {
+                return Err(Error::Parse(ParseError::UnknownField {
+                    data: lookup.from.clone(),
+                    field: field.to_string(),
+                }));
+            }
+        }
+
+        // Sources are passed to the operator in the order of their nodes
+        let (position, sources) = match from.out.cmp(&source) {
+            Ordering::Less => (0, vec![from.out, source]),
+            Ordering::Equal => (0, vec![source]),
+            Ordering::Greater => (1, vec![source, from.out]),
+        };
+
+        Ok((Operator::lookup(lookup, position), sources))
     }
 
     fn visit_shape(&self, shape: Shape, result: &mut ParseResult) {
@@ -152,7 +344,17 @@ impl Visitor {
     }
 
     fn visit_bar_shape(&self, bar: BarShape, data_node: usize, result: &mut ParseResult) {
-        let scale_nodes = self.visit_shape_props(&bar.props.base, data_node, result);
+        let mut scale_nodes = self.visit_shape_props(&bar.props.base, data_node, result);
+
+        // Parse scale node for the "x2" field
+        if let Some(x2) = bar.props.x2.as_ref() {
+            scale_nodes.push(self.visit_data_source(x2, X2_AXIS_FIELD_NAME, data_node, result));
+        }
+
+        // Parse scale node for the "y2" field
+        if let Some(y2) = bar.props.y2.as_ref() {
+            scale_nodes.push(self.visit_data_source(y2, Y2_AXIS_FIELD_NAME, data_node, result));
+        }
 
         let node = result.graph.add_node(Operator::bar(
             bar,
@@ -462,10 +664,14 @@ impl Visitor {
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, BTreeSet, HashMap};
+    use std::sync::Arc;
+
+    use bruc_expression::data::DataItem;
 
     use crate::graph::node::shape::{SceneWindow, POINT_COLOR_FIELD_NAME};
     use crate::graph::node::{Node, Operator};
     use crate::graph::Edge;
+    use crate::loader::MemoryLoader;
     use crate::parser::{DataNode, ParseResult, ParsedNodeCollection};
     use crate::scale::Scale;
     use crate::spec::axis::{Axis, AxisOrientation};
@@ -475,7 +681,10 @@ mod tests {
     use crate::spec::shape::line::LinePropertiesBuilder;
     use crate::spec::shape::pie::{PiePropertiesBuilder, PieShape};
     use crate::spec::shape::point::{PointPropertiesBuilder, PointShape};
+    use crate::spec::transform::group::{GroupOperator, GroupPipe};
+    use crate::spec::transform::lookup::{LookupField, LookupPipe};
     use crate::spec::transform::map::MapPipe;
+    use crate::spec::transform::pivot::PivotPipe;
     use crate::spec::{Dimensions, Visual};
     use crate::{
         data::DataValue,
@@ -536,10 +745,10 @@ mod tests {
                 ],
             ),
         );
-        let parser = Parser;
+        let parser = Parser::new(Arc::new(MemoryLoader::new()));
 
         // when
-        let ParseResult { graph, collection } = parser.parse(spec);
+        let ParseResult { graph, collection } = parser.parse(spec).unwrap();
 
         // then
         assert_eq!(
@@ -654,7 +863,11 @@ mod tests {
                 domain: HashMap::from([("vertical".to_string(), 5), ("horizontal".to_string(), 3)]),
                 scales: HashMap::from([("vertical".to_string(), 6), ("horizontal".to_string(), 4)]),
                 axis: HashMap::from([("vertical".to_string(), 9), ("horizontal".to_string(), 8)]),
-                shapes: vec![7]
+                shapes: vec![7],
+                dependencies: HashMap::from([
+                    (1, BTreeSet::from(["a".to_string()])),
+                    (2, BTreeSet::from(["b".to_string()]))
+                ])
             }
         )
     }
@@ -708,10 +921,10 @@ mod tests {
                 ],
             ),
         );
-        let parser = Parser;
+        let parser = Parser::new(Arc::new(MemoryLoader::new()));
 
         // when
-        let ParseResult { graph, collection } = parser.parse(spec);
+        let ParseResult { graph, collection } = parser.parse(spec).unwrap();
 
         // then
         assert_eq!(
@@ -829,7 +1042,11 @@ mod tests {
                 domain: HashMap::from([("vertical".to_string(), 5), ("horizontal".to_string(), 3)]),
                 scales: HashMap::from([("vertical".to_string(), 6), ("horizontal".to_string(), 4)]),
                 axis: HashMap::from([("vertical".to_string(), 9), ("horizontal".to_string(), 8)]),
-                shapes: vec![7]
+                shapes: vec![7],
+                dependencies: HashMap::from([
+                    (1, BTreeSet::from(["a".to_string()])),
+                    (2, BTreeSet::from(["b".to_string()]))
+                ])
             }
         )
     }
@@ -860,10 +1077,10 @@ mod tests {
                 vec![],
             ),
         );
-        let parser = Parser;
+        let parser = Parser::new(Arc::new(MemoryLoader::new()));
 
         // when
-        let ParseResult { graph, collection } = parser.parse(spec);
+        let ParseResult { graph, collection } = parser.parse(spec).unwrap();
 
         // then
         assert_eq!(
@@ -907,7 +1124,7 @@ mod tests {
             graph.nodes_in_degree,
             BTreeMap::from([(0, BTreeSet::from([0])), (1, BTreeSet::from([1, 2]))])
         );
-        assert_eq!(graph.order, vec![0, 1]);
+        assert_eq!(graph.order, vec![0, 1, 2]);
         assert_eq!(
             collection,
             ParsedNodeCollection {
@@ -915,7 +1132,8 @@ mod tests {
                 domain: HashMap::new(),
                 scales: HashMap::new(),
                 axis: HashMap::new(),
-                shapes: vec![2]
+                shapes: vec![2],
+                dependencies: HashMap::from([(1, BTreeSet::from(["y".to_string()]))])
             }
         )
     }
@@ -969,10 +1187,10 @@ mod tests {
                 ],
             ),
         );
-        let parser = Parser;
+        let parser = Parser::new(Arc::new(MemoryLoader::new()));
 
         // when
-        let ParseResult { graph, collection } = parser.parse(spec);
+        let ParseResult { graph, collection } = parser.parse(spec).unwrap();
 
         // then
         assert_eq!(
@@ -1086,8 +1304,285 @@ mod tests {
                 domain: HashMap::from([("vertical".to_string(), 5), ("horizontal".to_string(), 3)]),
                 scales: HashMap::from([("vertical".to_string(), 6), ("horizontal".to_string(), 4)]),
                 axis: HashMap::from([("vertical".to_string(), 10), ("horizontal".to_string(), 9)]),
-                shapes: vec![8]
+                shapes: vec![8],
+                dependencies: HashMap::from([
+                    (1, BTreeSet::from(["a".to_string()])),
+                    (2, BTreeSet::from(["b".to_string()]))
+                ])
             }
         )
     }
+
+    #[test]
+    fn doesnt_parse_pipes_with_unknown_fields() {
+        let spec = |transform: Vec<Pipe>| {
+            Specification::new(
+                Dimensions::default(),
+                vec![DataEntry::new(
+                    "primary",
+                    vec![DataValue::from_pairs(vec![("a", 10.0.into())])],
+                    transform,
+                )],
+                Vec::new(),
+                Visual::default(),
+            )
+        };
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec(vec![
+            Pipe::Map(MapPipe::new("a - 2", "b").unwrap()),
+            Pipe::Filter(FilterPipe::new("b > 2 && c").unwrap()),
+        ]));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::UnknownField: c in data primary"
+        );
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec(vec![
+            Pipe::Group(GroupPipe::new("a", GroupOperator::Count, "count")),
+            Pipe::Map(MapPipe::new("a + count", "b").unwrap()),
+            Pipe::Filter(FilterPipe::new("a > 2").unwrap()),
+        ]));
+        assert!(result.is_ok());
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec(vec![
+            Pipe::Map(MapPipe::new("a - 2", "b").unwrap()),
+            Pipe::Group(GroupPipe::new("a", GroupOperator::Count, "count")),
+            Pipe::Filter(FilterPipe::new("b > 2").unwrap()),
+        ]));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::UnknownField: b in data primary"
+        );
+    }
+
+    #[test]
+    fn parses_pipes_with_nested_fields() {
+        let spec = |transform: Vec<Pipe>| {
+            Specification::new(
+                Dimensions::default(),
+                vec![DataEntry::new(
+                    "primary",
+                    vec![DataValue::from_pairs(vec![(
+                        "geo",
+                        DataItem::Object(BTreeMap::from([("lat".to_string(), 10.0.into())])),
+                    )])],
+                    transform,
+                )],
+                Vec::new(),
+                Visual::default(),
+            )
+        };
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec(vec![Pipe::Filter(
+            FilterPipe::new("geo.lat > 2").unwrap(),
+        )]));
+        assert!(result.is_ok());
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec(vec![Pipe::Filter(
+            FilterPipe::new("place.lat > 2").unwrap(),
+        )]));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::UnknownField: place.lat in data primary"
+        );
+    }
+
+    #[test]
+    fn parses_pipes_with_unknown_data_values() {
+        let spec = Specification::new(
+            Dimensions::default(),
+            vec![DataEntry::new(
+                "primary",
+                Vec::new(),
+                vec![Pipe::Filter(FilterPipe::new("a > 2 && b").unwrap())],
+            )],
+            Vec::new(),
+            Visual::default(),
+        );
+
+        let ParseResult { collection, .. } = Parser::new(Arc::new(MemoryLoader::new()))
+            .parse(spec)
+            .unwrap();
+        assert_eq!(
+            collection.dependencies,
+            HashMap::from([(1, BTreeSet::from(["a".to_string(), "b".to_string()]))])
+        );
+    }
+
+    #[test]
+    fn parses_pipes_after_pivot() {
+        let spec = Specification::new(
+            Dimensions::default(),
+            vec![DataEntry::new(
+                "primary",
+                vec![DataValue::from_pairs(vec![
+                    ("date", 1.0.into()),
+                    ("key", "cpu".into()),
+                    ("value", 0.5.into()),
+                ])],
+                vec![
+                    Pipe::Pivot(PivotPipe::new("key", "value").with_groupby(vec!["date"])),
+                    Pipe::Filter(FilterPipe::new("cpu > 0.2").unwrap()),
+                ],
+            )],
+            Vec::new(),
+            Visual::default(),
+        );
+
+        let ParseResult { collection, .. } = Parser::new(Arc::new(MemoryLoader::new()))
+            .parse(spec)
+            .unwrap();
+        assert_eq!(
+            collection.dependencies[&2],
+            BTreeSet::from(["cpu".to_string()])
+        );
+    }
+
+    #[test]
+    fn doesnt_parse_invalid_pipe_types() {
+        let spec = Specification::new(
+            Dimensions::default(),
+            vec![DataEntry::new(
+                "primary",
+                vec![DataValue::from_pairs(vec![("a", "fruit".into())])],
+                vec![Pipe::Map(MapPipe::new("a * 2", "b").unwrap())],
+            )],
+            Vec::new(),
+            Visual::default(),
+        );
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::InvalidPipe: PipeError::Expression: TypeError::InvalidOperands: text * number in data primary"
+        );
+    }
+
+    #[test]
+    fn parses_derived_data() {
+        let spec = |transform: Vec<Pipe>| {
+            Specification::new(
+                Dimensions::default(),
+                vec![
+                    DataEntry::new(
+                        "primary",
+                        vec![DataValue::from_pairs(vec![("a", 10.0.into())])],
+                        vec![Pipe::Map(MapPipe::new("a - 2", "b").unwrap())],
+                    ),
+                    DataEntry::new("summary", Vec::new(), transform).with_source("primary"),
+                ],
+                Vec::new(),
+                Visual::default(),
+            )
+        };
+
+        let ParseResult { graph, collection } = Parser::new(Arc::new(MemoryLoader::new()))
+            .parse(spec(vec![Pipe::Group(GroupPipe::new(
+                "b",
+                GroupOperator::Count,
+                "count",
+            ))]))
+            .unwrap();
+        assert_eq!(
+            collection.data,
+            HashMap::from([
+                ("primary".to_string(), DataNode::new(0, 1)),
+                ("summary".to_string(), DataNode::derived(0, 2)),
+            ])
+        );
+        assert_eq!(graph.edges, vec![Edge::new(0, 1), Edge::new(1, 2)]);
+
+        let result = Parser::new(Arc::new(MemoryLoader::new()))
+            .parse(spec(vec![Pipe::Filter(FilterPipe::new("c > 2").unwrap())]));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::UnknownField: c in data summary"
+        );
+    }
+
+    #[test]
+    fn doesnt_parse_data_with_unknown_source() {
+        let spec = Specification::new(
+            Dimensions::default(),
+            vec![
+                DataEntry::new("summary", Vec::new(), Vec::new()).with_source("primary"),
+                DataEntry::new("primary", Vec::new(), Vec::new()),
+            ],
+            Vec::new(),
+            Visual::default(),
+        );
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::UnknownSource: primary in data summary"
+        );
+    }
+
+    #[test]
+    fn parses_lookup_data() {
+        let spec = |lookup: LookupPipe| {
+            Specification::new(
+                Dimensions::default(),
+                vec![
+                    DataEntry::new(
+                        "categories",
+                        vec![DataValue::from_pairs(vec![
+                            ("id", 1.0.into()),
+                            ("name", "a".into()),
+                        ])],
+                        Vec::new(),
+                    ),
+                    DataEntry::new(
+                        "facts",
+                        vec![DataValue::from_pairs(vec![("category", 1.0.into())])],
+                        vec![Pipe::Lookup(lookup)],
+                    ),
+                ],
+                Vec::new(),
+                Visual::default(),
+            )
+        };
+
+        let lookup = LookupPipe::new(
+            "categories",
+            "id",
+            "category",
+            vec![LookupField::new("name")],
+        );
+        let ParseResult { graph, collection } = Parser::new(Arc::new(MemoryLoader::new()))
+            .parse(spec(lookup.clone()))
+            .unwrap();
+        assert_eq!(
+            collection.data,
+            HashMap::from([
+                ("categories".to_string(), DataNode::new(0, 0)),
+                ("facts".to_string(), DataNode::new(1, 2)),
+            ])
+        );
+        assert_eq!(graph.edges, vec![Edge::new(0, 2), Edge::new(1, 2)]);
+        assert_eq!(graph.nodes[2], Node::init(Operator::lookup(lookup, 0)));
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec(LookupPipe::new(
+            "categories",
+            "id",
+            "category",
+            vec![LookupField::new("color")],
+        )));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::UnknownField: color in data categories"
+        );
+
+        let result = Parser::new(Arc::new(MemoryLoader::new())).parse(spec(LookupPipe::new(
+            "colors",
+            "id",
+            "category",
+            vec![LookupField::new("color")],
+        )));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ParseError::UnknownSource: colors in data facts"
+        );
+    }
 }
@@ -9,7 +9,36 @@ pub trait SceneRenderer {
 trait ItemRenderer {
     type RenderResult;
 
-    fn render(&self, dimensions: &SceneDimensions) -> Self::RenderResult;
+    fn render(&self, context: &RenderContext) -> Self::RenderResult;
+}
+
+/// `RenderContext` holds the settings shared by the items of a scene while rendering them.
+struct RenderContext {
+    dimensions: SceneDimensions,
+    /// Amount of decimal digits numbers are rounded to.
+    precision: usize,
+}
+
+impl RenderContext {
+    fn new(dimensions: SceneDimensions, precision: usize) -> Self {
+        RenderContext {
+            dimensions,
+            precision,
+        }
+    }
+
+    /// Round a number to the context's precision, so that it's written without trailing digits.
+    fn round(&self, value: f64) -> f64 {
+        let factor = 10f64.powi(self.precision as i32);
+        let rounded = (value * factor).round() / factor;
+
+        // Avoid writing negative zeros
+        if rounded == 0.0 {
+            0.0
+        } else {
+            rounded
+        }
+    }
 }
 
 pub struct DebugRenderer;
@@ -1,52 +1,77 @@
-use core::f32;
-
 use crate::{
     scene::{
-        SceneArc, SceneAxis, SceneAxisTick, SceneDimensions, SceneGroup, SceneItem, SceneLine,
-        ScenePoint, SceneRect, SceneRoot, Scenegraph,
+        SceneArc, SceneAxis, SceneAxisTick, SceneGroup, SceneItem, SceneLine, ScenePoint,
+        SceneRect, SceneRoot, Scenegraph,
     },
     spec::axis::AxisOrientation,
 };
 
-use super::{ItemRenderer, SceneRenderer};
+use super::{ItemRenderer, RenderContext, SceneRenderer};
 
-const SVG_TICK_SIZE: f32 = 5.0;
+const SVG_TICK_SIZE: f64 = 5.0;
 const SVG_AXIS_COLOR: &str = "#212121";
-const SVG_AXIS_MARGIN: (f32, f32) = (35.0, 20.0);
-const SVG_CANVAS_MARGIN: (f32, f32) = (10.0, 10.0);
+const SVG_AXIS_MARGIN: (f64, f64) = (35.0, 20.0);
+const SVG_CANVAS_MARGIN: (f64, f64) = (10.0, 10.0);
+
+/// Default amount of decimal digits of the numbers written to the SVG output.
+const SVG_DEFAULT_PRECISION: usize = 2;
 
-pub struct SvgRenderer;
+/// `SvgRenderer` renders a scene as an SVG document. Numbers are rounded to two decimal digits
+/// by default, so that the output stays compact.
+#[derive(Debug, Clone, Copy)]
+pub struct SvgRenderer {
+    precision: usize,
+}
 
 impl SvgRenderer {
-    fn render_root(root: &SceneRoot) -> String {
-        let result = root.render(&root.dimensions);
+    pub fn new() -> Self {
+        SvgRenderer::default()
+    }
+
+    /// Round the numbers written to the SVG output to a certain amount of decimal digits instead.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    fn render_root(&self, root: &SceneRoot) -> String {
+        let context = RenderContext::new(root.dimensions, self.precision);
+        let result = root.render(&context);
 
         let canvas_margin_x = result.d_width + SVG_CANVAS_MARGIN.0;
         let canvas_margin_y = result.d_height + SVG_CANVAS_MARGIN.1;
 
         format!(
             "<svg width=\"{width}\" height=\"{height}\"><g transform=\"translate({margin_x}, {margin_y})\">{content}</g></svg>",
-            width = root.dimensions.width as f32 + canvas_margin_x.max(SVG_CANVAS_MARGIN.0 * 2.0),
-            height = root.dimensions.height as f32 + canvas_margin_y.max(SVG_CANVAS_MARGIN.1 * 2.0),
-            margin_x = result.margin.0.max(SVG_CANVAS_MARGIN.0),
-            margin_y = result.margin.1.max(SVG_CANVAS_MARGIN.1),
+            width = context.round(root.dimensions.width as f64 + canvas_margin_x.max(SVG_CANVAS_MARGIN.0 * 2.0)),
+            height = context.round(root.dimensions.height as f64 + canvas_margin_y.max(SVG_CANVAS_MARGIN.1 * 2.0)),
+            margin_x = context.round(result.margin.0.max(SVG_CANVAS_MARGIN.0)),
+            margin_y = context.round(result.margin.1.max(SVG_CANVAS_MARGIN.1)),
             content = result.content
         )
     }
 }
 
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer {
+            precision: SVG_DEFAULT_PRECISION,
+        }
+    }
+}
+
 impl SceneRenderer for SvgRenderer {
     fn render(&self, scene: &Scenegraph) -> String {
-        SvgRenderer::render_root(&scene.root)
+        self.render_root(&scene.root)
     }
 }
 
 #[derive(Default)]
 pub(crate) struct SvgRenderResult {
     content: String,
-    d_width: f32,
-    d_height: f32,
-    margin: (f32, f32),
+    d_width: f64,
+    d_height: f64,
+    margin: (f64, f64),
 }
 
 impl SvgRenderResult {
@@ -64,11 +89,11 @@ impl SvgRenderResult {
 impl ItemRenderer for SceneRoot {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, dimensions: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         self.items
             .iter()
             .fold(SvgRenderResult::default(), |mut acc, item| {
-                acc.merge(&item.render(dimensions));
+                acc.merge(&item.render(context));
                 acc
             })
     }
@@ -77,14 +102,14 @@ impl ItemRenderer for SceneRoot {
 impl ItemRenderer for SceneItem {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, dimensions: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         match self {
-            SceneItem::Group(group) => group.render(dimensions),
-            SceneItem::Line(line) => line.render(dimensions),
-            SceneItem::Rect(rect) => rect.render(dimensions),
-            SceneItem::Axis(axis) => axis.render(dimensions),
-            SceneItem::Arc(arc) => arc.render(dimensions),
-            SceneItem::Point(point) => point.render(dimensions),
+            SceneItem::Group(group) => group.render(context),
+            SceneItem::Line(line) => line.render(context),
+            SceneItem::Rect(rect) => rect.render(context),
+            SceneItem::Axis(axis) => axis.render(context),
+            SceneItem::Arc(arc) => arc.render(context),
+            SceneItem::Point(point) => point.render(context),
         }
     }
 }
@@ -92,12 +117,12 @@ impl ItemRenderer for SceneItem {
 impl ItemRenderer for SceneGroup {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, dimensions: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         let result = self
             .items
             .iter()
             .fold(SvgRenderResult::default(), |mut acc, item| {
-                acc.merge(&item.render(dimensions));
+                acc.merge(&item.render(context));
                 acc
             });
 
@@ -113,23 +138,23 @@ impl ItemRenderer for SceneGroup {
 impl ItemRenderer for SceneLine {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, _: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         let path =
             self.points
                 .iter()
                 .enumerate()
                 .fold(String::new(), |mut acc, (index, (x, y))| {
                     let coordinates = if index == 0 {
-                        format!("M{} {}", x, y)
+                        format!("M{} {}", context.round(*x), context.round(*y))
                     } else {
-                        format!(" L{} {}", x, y)
+                        format!(" L{} {}", context.round(*x), context.round(*y))
                     };
                     acc.push_str(&coordinates);
                     acc
                 });
 
         let stroke = &self.stroke;
-        let stroke_width = self.stroke_width;
+        let stroke_width = context.round(self.stroke_width);
 
         SvgRenderResult {
             content: format!("<path d=\"{path}\" fill=\"transparent\" stroke=\"{stroke}\" stroke-width=\"{stroke_width}\" stroke-linecap=\"round\" />"),
@@ -143,15 +168,15 @@ impl ItemRenderer for SceneLine {
 impl ItemRenderer for SceneArc {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, _dimensions: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         let a0 = angle_to_radians(self.start_angle);
         let a1 = angle_to_radians(self.end_angle);
 
-        let x0 = f32::cos(a0);
-        let y0 = f32::sin(a0);
+        let x0 = f64::cos(a0);
+        let y0 = f64::sin(a0);
 
-        let x1 = f32::cos(a1);
-        let y1 = f32::sin(a1);
+        let x1 = f64::cos(a1);
+        let y1 = f64::sin(a1);
 
         let large_arc = if (self.end_angle - self.start_angle) > 180.0 {
             1
@@ -186,22 +211,22 @@ impl ItemRenderer for SceneArc {
 
         let path = format!(
             "M {} {} L {}, {} A {} {} 0 {} 1 {} {} L {} {} A {} {} 0 {} 0 {} {}",
-            bottom_left.0,
-            bottom_left.1,
-            top_left.0,
-            top_left.1,
-            outer_radius,
-            outer_radius,
+            context.round(bottom_left.0),
+            context.round(bottom_left.1),
+            context.round(top_left.0),
+            context.round(top_left.1),
+            context.round(outer_radius),
+            context.round(outer_radius),
             large_arc,
-            top_right.0,
-            top_right.1,
-            bottom_right.0,
-            bottom_right.1,
-            self.inner_radius,
-            self.inner_radius,
+            context.round(top_right.0),
+            context.round(top_right.1),
+            context.round(bottom_right.0),
+            context.round(bottom_right.1),
+            context.round(self.inner_radius),
+            context.round(self.inner_radius),
             large_arc,
-            bottom_left.0,
-            bottom_left.1
+            context.round(bottom_left.0),
+            context.round(bottom_left.1)
         );
 
         let fill = &self.fill;
@@ -214,29 +239,29 @@ impl ItemRenderer for SceneArc {
     }
 }
 
-fn angle_to_radians(angle: f32) -> f32 {
-    (angle - 90.0) * f32::consts::PI / 180.0
+fn angle_to_radians(angle: f64) -> f64 {
+    (angle - 90.0) * std::f64::consts::PI / 180.0
 }
 
 impl ItemRenderer for SceneAxis {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, dimensions: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         match self.orientation {
-            AxisOrientation::Top => render_top_axis(self, dimensions),
-            AxisOrientation::Bottom => render_bottom_axis(self, dimensions),
-            AxisOrientation::Left => render_left_axis(self, dimensions),
-            AxisOrientation::Right => render_right_axis(self, dimensions),
+            AxisOrientation::Top => render_top_axis(self, context),
+            AxisOrientation::Bottom => render_bottom_axis(self, context),
+            AxisOrientation::Left => render_left_axis(self, context),
+            AxisOrientation::Right => render_right_axis(self, context),
         }
     }
 }
 
-fn render_top_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRenderResult {
+fn render_top_axis(axis: &SceneAxis, context: &RenderContext) -> SvgRenderResult {
     let tick_size = (0.0, -SVG_TICK_SIZE);
     let tick_text_margin = (0.0, -0.5);
 
-    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, dimensions);
-    let ruler = render_axis_ruler(axis, dimensions);
+    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, context);
+    let ruler = render_axis_ruler(axis, context);
 
     SvgRenderResult {
         content: format!("<g>{ticks}{ruler}</g>"),
@@ -246,12 +271,12 @@ fn render_top_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRenderR
     }
 }
 
-fn render_bottom_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRenderResult {
+fn render_bottom_axis(axis: &SceneAxis, context: &RenderContext) -> SvgRenderResult {
     let tick_size = (0.0, SVG_TICK_SIZE);
     let tick_text_margin = (0.0, 1.0);
 
-    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, dimensions);
-    let ruler = render_axis_ruler(axis, dimensions);
+    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, context);
+    let ruler = render_axis_ruler(axis, context);
 
     SvgRenderResult {
         content: format!("<g>{ticks}{ruler}</g>"),
@@ -261,12 +286,12 @@ fn render_bottom_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRend
     }
 }
 
-fn render_left_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRenderResult {
+fn render_left_axis(axis: &SceneAxis, context: &RenderContext) -> SvgRenderResult {
     let tick_size = (-SVG_TICK_SIZE, 0.0);
     let tick_text_margin = (-0.3, 0.3);
 
-    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, dimensions);
-    let ruler = render_axis_ruler(axis, dimensions);
+    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, context);
+    let ruler = render_axis_ruler(axis, context);
 
     SvgRenderResult {
         content: format!("<g>{ticks}{ruler}</g>"),
@@ -276,12 +301,12 @@ fn render_left_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRender
     }
 }
 
-fn render_right_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRenderResult {
+fn render_right_axis(axis: &SceneAxis, context: &RenderContext) -> SvgRenderResult {
     let tick_size = (SVG_TICK_SIZE, 0.0);
     let tick_text_margin = (0.3, 0.3);
 
-    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, dimensions);
-    let ruler = render_axis_ruler(axis, dimensions);
+    let ticks = render_axis_ticks(&axis.ticks, tick_size, tick_text_margin, context);
+    let ruler = render_axis_ruler(axis, context);
 
     SvgRenderResult {
         content: format!("<g>{ticks}{ruler}</g>"),
@@ -293,15 +318,15 @@ fn render_right_axis(axis: &SceneAxis, dimensions: &SceneDimensions) -> SvgRende
 
 fn render_axis_ticks(
     ticks: &[SceneAxisTick],
-    tick_size: (f32, f32),
-    tick_text_margin: (f32, f32),
-    dimensions: &SceneDimensions,
+    tick_size: (f64, f64),
+    tick_text_margin: (f64, f64),
+    context: &RenderContext,
 ) -> String {
     ticks.iter().fold(String::new(), |mut acc, tick| {
-    let x1 = tick.position.0;
-    let x2 = tick.position.0 + tick_size.0;
-    let y1 = dimensions.height as f32 - tick.position.1;
-    let y2 = dimensions.height as f32 - tick.position.1 + tick_size.1;
+    let x1 = context.round(tick.position.0);
+    let x2 = context.round(tick.position.0 + tick_size.0);
+    let y1 = context.round(context.dimensions.height as f64 - tick.position.1);
+    let y2 = context.round(context.dimensions.height as f64 - tick.position.1 + tick_size.1);
 
     let tick_line = format!(
       "<line x1=\"{x1}\" x2=\"{x2}\" y1=\"{y1}\" y2=\"{y2}\" stroke-width=\"1\" opacity=\"1\" stroke=\"{SVG_AXIS_COLOR}\" stroke-linecap=\"square\" />",
@@ -311,8 +336,8 @@ fn render_axis_ticks(
       format!(
           "<text transform=\"translate({x2}, {y2})\" dominant-baseline=\"middle\" text-anchor=\"middle\" font-size=\"10\" font-family=\"sans-serif\"><tspan dx=\"{label_x}em\" dy=\"{label_y}em\">{label}</tspan></text>",
           label = label,
-          label_x = tick_text_margin.0 * label.len() as f32,
-          label_y = tick_text_margin.1
+          label_x = context.round(tick_text_margin.0 * label.len() as f64),
+          label_y = context.round(tick_text_margin.1)
         )
     } else {
         String::new()
@@ -323,26 +348,26 @@ fn render_axis_ticks(
   })
 }
 
-fn render_axis_ruler(axis: &SceneAxis, dimensions: &SceneDimensions) -> String {
+fn render_axis_ruler(axis: &SceneAxis, context: &RenderContext) -> String {
     format!(
         "<line x1=\"{x1}\" x2=\"{x2}\" y1=\"{y1}\" y2=\"{y2}\" stroke-width=\"1\" opacity=\"1\" stroke=\"{SVG_AXIS_COLOR}\" stroke-linecap=\"square\" />",
-      x1 = axis.rule.from.0,
-      x2 = axis.rule.to.0,
-      y1 = dimensions.height as f32 - axis.rule.from.1,
-      y2 = dimensions.height as f32 - axis.rule.to.1
+      x1 = context.round(axis.rule.from.0),
+      x2 = context.round(axis.rule.to.0),
+      y1 = context.round(context.dimensions.height as f64 - axis.rule.from.1),
+      y2 = context.round(context.dimensions.height as f64 - axis.rule.to.1)
     )
 }
 
 impl ItemRenderer for SceneRect {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, _dimensions: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         let content = format!(
             "<rect x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" fill=\"{fill}\" />",
-            x = self.x,
-            y = self.y,
-            width = self.width,
-            height = self.height,
+            x = context.round(self.x),
+            y = context.round(self.y),
+            width = context.round(self.width),
+            height = context.round(self.height),
             fill = self.fill
         );
 
@@ -358,12 +383,12 @@ impl ItemRenderer for SceneRect {
 impl ItemRenderer for ScenePoint {
     type RenderResult = SvgRenderResult;
 
-    fn render(&self, _dimensions: &SceneDimensions) -> Self::RenderResult {
+    fn render(&self, context: &RenderContext) -> Self::RenderResult {
         let content = format!(
             "<circle cx=\"{x}\" cy=\"{y}\" r=\"{size}\" fill=\"{color}\" />",
-            x = self.x,
-            y = self.y,
-            size = self.size,
+            x = context.round(self.x),
+            y = context.round(self.y),
+            size = context.round(self.size),
             color = self.color
         );
 
@@ -399,7 +424,7 @@ mod tests {
             },
         ));
 
-        let renderer = SvgRenderer;
+        let renderer = SvgRenderer::new();
         let result = renderer.render(&scenegraph);
 
         assert_eq!(
@@ -437,7 +462,7 @@ mod tests {
             },
         ));
 
-        let result = SvgRenderer.render(&scenegraph);
+        let result = SvgRenderer::new().render(&scenegraph);
 
         assert_eq!(
             result,
@@ -474,7 +499,7 @@ mod tests {
             },
         ));
 
-        let result = SvgRenderer.render(&scenegraph);
+        let result = SvgRenderer::new().render(&scenegraph);
 
         assert_eq!(
             result,
@@ -511,7 +536,7 @@ mod tests {
             },
         ));
 
-        let result = SvgRenderer.render(&scenegraph);
+        let result = SvgRenderer::new().render(&scenegraph);
 
         assert_eq!(
             result,
@@ -548,7 +573,7 @@ mod tests {
             },
         ));
 
-        let result = SvgRenderer.render(&scenegraph);
+        let result = SvgRenderer::new().render(&scenegraph);
 
         assert_eq!(
             result,
@@ -573,11 +598,11 @@ mod tests {
             },
         ));
 
-        let result = SvgRenderer.render(&scenegraph);
+        let result = SvgRenderer::new().render(&scenegraph);
 
         assert_eq!(
             result,
-            "<svg width=\"520\" height=\"520\"><g transform=\"translate(10, 10)\"><path d=\"M 250 250 L 249.99998, 0 A 250 250 0 0 1 466.50635 125 L 250 250 A 0 0 0 0 0 250 250\" fill=\"blue\"/><path d=\"M 250 250 L 466.50635, 125 A 250 250 0 0 1 466.50635 375 L 250 250 A 0 0 0 0 0 250 250\" fill=\"red\"/><path d=\"M 250 250 L 466.50635, 375 A 250 250 0 0 1 249.99998 500 L 250 250 A 0 0 0 0 0 250 250\" fill=\"yellow\"/><path d=\"M 250 250 L 249.99998, 500 A 250 250 0 0 1 33.49362 374.99994 L 250 250 A 0 0 0 0 0 250 250\" fill=\"green\"/><path d=\"M 250 250 L 33.49362, 374.99994 A 250 250 0 0 1 33.493683 124.999954 L 250 250 A 0 0 0 0 0 250 250\" fill=\"pink\"/><path d=\"M 250 250 L 33.493683, 124.999954 A 250 250 0 0 1 250 0 L 250 250 A 0 0 0 0 0 250 250\" fill=\"black\"/></g></svg>"
+            "<svg width=\"520\" height=\"520\"><g transform=\"translate(10, 10)\"><path d=\"M 250 250 L 250, 0 A 250 250 0 0 1 466.51 125 L 250 250 A 0 0 0 0 0 250 250\" fill=\"blue\"/><path d=\"M 250 250 L 466.51, 125 A 250 250 0 0 1 466.51 375 L 250 250 A 0 0 0 0 0 250 250\" fill=\"red\"/><path d=\"M 250 250 L 466.51, 375 A 250 250 0 0 1 250 500 L 250 250 A 0 0 0 0 0 250 250\" fill=\"yellow\"/><path d=\"M 250 250 L 250, 500 A 250 250 0 0 1 33.49 375 L 250 250 A 0 0 0 0 0 250 250\" fill=\"green\"/><path d=\"M 250 250 L 33.49, 375 A 250 250 0 0 1 33.49 125 L 250 250 A 0 0 0 0 0 250 250\" fill=\"pink\"/><path d=\"M 250 250 L 33.49, 125 A 250 250 0 0 1 250 0 L 250 250 A 0 0 0 0 0 250 250\" fill=\"black\"/></g></svg>"
         )
     }
 
@@ -597,11 +622,37 @@ mod tests {
             },
         ));
 
-        let result = SvgRenderer.render(&scenegraph);
+        let result = SvgRenderer::new().render(&scenegraph);
 
         assert_eq!(
             result,
             "<svg width=\"520\" height=\"520\"><g transform=\"translate(10, 10)\"><circle cx=\"0\" cy=\"5\" r=\"1\" fill=\"red\" /><circle cx=\"1\" cy=\"10\" r=\"2\" fill=\"blue\" /><circle cx=\"2\" cy=\"2\" r=\"5\" fill=\"green\" /><circle cx=\"3\" cy=\"8\" r=\"3\" fill=\"orange\" /><circle cx=\"4\" cy=\"4\" r=\"1\" fill=\"yellow\" /></g></svg>"
         )
     }
+
+    #[test]
+    fn render_svg_with_precision() {
+        let scenegraph = Scenegraph::new(SceneRoot::new(
+            vec![
+                SceneItem::point(1.0 / 3.0, 2.0 / 3.0, 1.255, "red".to_string()),
+                SceneItem::rect(0.125, 20.0, 4.5, -0.0001, "blue".to_string()),
+            ],
+            SceneDimensions {
+                width: 500,
+                height: 500,
+            },
+        ));
+
+        let result = SvgRenderer::new().render(&scenegraph);
+        assert_eq!(
+            result,
+            "<svg width=\"520\" height=\"520\"><g transform=\"translate(10, 10)\"><circle cx=\"0.33\" cy=\"0.67\" r=\"1.25\" fill=\"red\" /><rect x=\"4.5\" y=\"0\" width=\"0.13\" height=\"20\" fill=\"blue\" /></g></svg>"
+        );
+
+        let result = SvgRenderer::new().with_precision(0).render(&scenegraph);
+        assert_eq!(
+            result,
+            "<svg width=\"520\" height=\"520\"><g transform=\"translate(10, 10)\"><circle cx=\"0\" cy=\"1\" r=\"1\" fill=\"red\" /><rect x=\"5\" y=\"0\" width=\"0\" height=\"20\" fill=\"blue\" /></g></svg>"
+        );
+    }
 }
@@ -3,13 +3,13 @@ use crate::spec::scale::Scale as ScaleSpec;
 use crate::spec::scale::ScaleKind as ScaleKindSpec;
 
 const DEFAULT_TICKS_COUNT: usize = 10;
-const EPSILON: f32 = 1e-10;
-const EPISLON_LOG: f32 = -10.0;
+const EPSILON: f64 = 1e-10;
+const EPISLON_LOG: f64 = -10.0;
 
 #[derive(PartialEq, Debug, Clone)]
 pub(crate) struct ScaleTick {
-    pub(crate) value: f32,
-    pub(crate) position: f32,
+    pub(crate) value: f64,
+    pub(crate) position: f64,
     pub(crate) label: Option<String>,
 }
 
@@ -36,25 +36,25 @@ impl Scale {
         }
     }
 
-    pub(crate) fn linear(range: (f32, f32)) -> Self {
+    pub(crate) fn linear(range: (f64, f64)) -> Self {
         Scale {
             kind: ScaleKind::Linear(ScaleLinear { range }),
         }
     }
 
-    pub(crate) fn band(range: (f32, f32)) -> Self {
+    pub(crate) fn band(range: (f64, f64)) -> Self {
         Scale {
             kind: ScaleKind::Band(ScaleBand { range }),
         }
     }
 
-    pub(crate) fn log(range: (f32, f32)) -> Self {
+    pub(crate) fn log(range: (f64, f64)) -> Self {
         Scale {
             kind: ScaleKind::Log(ScaleLog { range }),
         }
     }
 
-    pub(crate) fn range(&self) -> (f32, f32) {
+    pub(crate) fn range(&self) -> (f64, f64) {
         match &self.kind {
             ScaleKind::Linear(linear) => linear.range,
             ScaleKind::Log(log) => log.range,
@@ -62,7 +62,7 @@ impl Scale {
         }
     }
 
-    pub(crate) fn ticks(&self, domain: (f32, f32)) -> Vec<ScaleTick> {
+    pub(crate) fn ticks(&self, domain: (f64, f64)) -> Vec<ScaleTick> {
         match &self.kind {
             ScaleKind::Linear(linear) => linear.ticks(domain),
             ScaleKind::Log(log) => log.ticks(domain),
@@ -70,7 +70,7 @@ impl Scale {
         }
     }
 
-    pub(crate) fn apply(&self, value: f32, domain: (f32, f32)) -> f32 {
+    pub(crate) fn apply(&self, value: f64, domain: (f64, f64)) -> f64 {
         match &self.kind {
             ScaleKind::Linear(linear) => linear.apply(value, domain),
             ScaleKind::Log(log) => log.apply(value, domain),
@@ -88,15 +88,15 @@ enum ScaleKind {
 
 #[derive(PartialEq, Debug, Clone)]
 pub(crate) struct ScaleLinear {
-    pub(crate) range: (f32, f32),
+    pub(crate) range: (f64, f64),
 }
 
 impl ScaleLinear {
-    fn apply(&self, value: f32, domain: (f32, f32)) -> f32 {
+    fn apply(&self, value: f64, domain: (f64, f64)) -> f64 {
         interpolate(normalize(value, domain), self.range)
     }
 
-    fn ticks(&self, domain: (f32, f32)) -> Vec<ScaleTick> {
+    fn ticks(&self, domain: (f64, f64)) -> Vec<ScaleTick> {
         create_tick_relative_positions(DEFAULT_TICKS_COUNT, domain)
             .into_iter()
             .map(|value| ScaleTick {
@@ -110,25 +110,25 @@ impl ScaleLinear {
 
 #[derive(PartialEq, Debug, Clone)]
 pub(crate) struct ScaleLog {
-    pub(crate) range: (f32, f32),
+    pub(crate) range: (f64, f64),
 }
 
 impl ScaleLog {
-    fn apply(&self, value: f32, domain: (f32, f32)) -> f32 {
+    fn apply(&self, value: f64, domain: (f64, f64)) -> f64 {
         interpolate(normalize_log10(value, domain), self.range)
     }
 
-    fn ticks(&self, domain: (f32, f32)) -> Vec<ScaleTick> {
+    fn ticks(&self, domain: (f64, f64)) -> Vec<ScaleTick> {
         let from_axis = domain.0.max(EPSILON).log10() as i32;
         let to_axis = domain.1.max(EPSILON).log10() as i32;
 
         let mut ticks = Vec::new();
 
         for exp in from_axis..=to_axis {
-            let log_tick = 10f32.powf(exp as f32);
+            let log_tick = 10f64.powf(exp as f64);
 
             for i in 1..10 {
-                let value = log_tick * (i as f32);
+                let value = log_tick * (i as f64);
                 if value >= domain.0 && value <= domain.1 {
                     ticks.push(ScaleTick {
                         position: self.apply(value, domain),
@@ -150,7 +150,7 @@ impl ScaleLog {
         ticks
     }
 
-    fn format(value: f32, exp: i32) -> String {
+    fn format(value: f64, exp: i32) -> String {
         match exp {
             -2..=2 => format!("{}", value),
             _ => format!("10e{:}", exp),
@@ -160,11 +160,11 @@ impl ScaleLog {
 
 #[derive(PartialEq, Debug, Clone)]
 pub(crate) struct ScaleBand {
-    pub(crate) range: (f32, f32),
+    pub(crate) range: (f64, f64),
 }
 
 impl ScaleBand {
-    fn apply(&self, value: f32, domain: (f32, f32)) -> f32 {
+    fn apply(&self, value: f64, domain: (f64, f64)) -> f64 {
         let count = domain.1 - domain.0 + 1.0;
 
         let step = (self.range.1 - self.range.0) / count;
@@ -174,7 +174,7 @@ impl ScaleBand {
         Self::calculate(value, domain, range)
     }
 
-    fn ticks(&self, domain: (f32, f32)) -> Vec<ScaleTick> {
+    fn ticks(&self, domain: (f64, f64)) -> Vec<ScaleTick> {
         let count = domain.1 - domain.0;
 
         let step = (self.range.1 - self.range.0) / (count + 1.0);
@@ -191,44 +191,44 @@ impl ScaleBand {
             .collect()
     }
 
-    fn calculate(value: f32, domain: (f32, f32), range: (f32, f32)) -> f32 {
+    fn calculate(value: f64, domain: (f64, f64), range: (f64, f64)) -> f64 {
         interpolate(normalize(value, domain), range)
     }
 }
 
-fn normalize(value: f32, (min, max): (f32, f32)) -> f32 {
+fn normalize(value: f64, (min, max): (f64, f64)) -> f64 {
     let value = value.clamp(min, max);
     (value - min) / (max - min)
 }
 
-fn normalize_by<F>(value: f32, (min, max): (f32, f32), by: F) -> f32
+fn normalize_by<F>(value: f64, (min, max): (f64, f64), by: F) -> f64
 where
-    F: Fn(f32) -> f32,
+    F: Fn(f64) -> f64,
 {
     normalize(by(value), (by(min), by(max)))
 }
 
-fn logn(value: f32) -> f32 {
+fn logn(value: f64) -> f64 {
     if value > 0.0 {
         value.log10()
     } else if value < 0.0 {
-        -f32::log10(-value)
+        -f64::log10(-value)
     } else {
         EPISLON_LOG
     }
 }
 
-fn normalize_log10(value: f32, domain: (f32, f32)) -> f32 {
+fn normalize_log10(value: f64, domain: (f64, f64)) -> f64 {
     normalize_by(value, domain, logn)
 }
 
-fn interpolate(value: f32, (min, max): (f32, f32)) -> f32 {
+fn interpolate(value: f64, (min, max): (f64, f64)) -> f64 {
     (max - min) * value + min
 }
 
-fn create_tick_relative_positions(count: usize, (from, to): (f32, f32)) -> Vec<f32> {
-    let step = (to - from) / (count as f32);
-    (0..count + 1).map(|i| from + step * (i as f32)).collect()
+fn create_tick_relative_positions(count: usize, (from, to): (f64, f64)) -> Vec<f64> {
+    let step = (to - from) / (count as f64);
+    (0..count + 1).map(|i| from + step * (i as f64)).collect()
 }
 
 #[cfg(test)]
@@ -263,7 +263,7 @@ mod tests {
                 },
                 ScaleTick {
                     value: 300.0,
-                    position: 60.000004,
+                    position: 60.0,
                     label: Some("300.00".to_string())
                 },
                 ScaleTick {
@@ -278,7 +278,7 @@ mod tests {
                 },
                 ScaleTick {
                     value: 600.0,
-                    position: 120.00001,
+                    position: 120.0,
                     label: Some("600.00".to_string())
                 },
                 ScaleTick {
@@ -321,132 +321,132 @@ mod tests {
                 },
                 ScaleTick {
                     value: 2.0,
-                    position: 20.068668,
+                    position: 20.068666377598745,
                     label: None
                 },
                 ScaleTick {
                     value: 3.0,
-                    position: 31.808084,
+                    position: 31.808083647977497,
                     label: None
                 },
                 ScaleTick {
                     value: 4.0,
-                    position: 40.137337,
+                    position: 40.13733275519749,
                     label: None
                 },
                 ScaleTick {
                     value: 5.0,
-                    position: 46.598003,
+                    position: 46.598000289067926,
                     label: None
                 },
                 ScaleTick {
                     value: 6.0,
-                    position: 51.876755,
+                    position: 51.87675002557624,
                     label: None
                 },
                 ScaleTick {
                     value: 7.0,
-                    position: 56.339867,
+                    position: 56.33986933428379,
                     label: None
                 },
                 ScaleTick {
                     value: 8.0,
-                    position: 60.206,
+                    position: 60.20599913279624,
                     label: None
                 },
                 ScaleTick {
                     value: 9.0,
-                    position: 63.61617,
+                    position: 63.616167295954995,
                     label: None
                 },
                 ScaleTick {
                     value: 10.0,
-                    position: 66.66667,
+                    position: 66.66666666666666,
                     label: Some("10".to_string())
                 },
                 ScaleTick {
                     value: 20.0,
-                    position: 86.73534,
+                    position: 86.73533304426542,
                     label: None
                 },
                 ScaleTick {
                     value: 30.0,
-                    position: 98.47475,
+                    position: 98.47475031464415,
                     label: None
                 },
                 ScaleTick {
                     value: 40.0,
-                    position: 106.804,
+                    position: 106.80399942186416,
                     label: None
                 },
                 ScaleTick {
                     value: 50.0,
-                    position: 113.26467,
+                    position: 113.26466695573458,
                     label: None
                 },
                 ScaleTick {
                     value: 60.0,
-                    position: 118.54342,
+                    position: 118.54341669224291,
                     label: None
                 },
                 ScaleTick {
                     value: 70.0,
-                    position: 123.00653,
+                    position: 123.00653600095046,
                     label: None
                 },
                 ScaleTick {
                     value: 80.0,
-                    position: 126.87267,
+                    position: 126.87266579946291,
                     label: None
                 },
                 ScaleTick {
                     value: 90.0,
-                    position: 130.28284,
+                    position: 130.28283396262165,
                     label: None
                 },
                 ScaleTick {
                     value: 100.0,
-                    position: 133.33334,
+                    position: 133.33333333333331,
                     label: Some("100".to_string())
                 },
                 ScaleTick {
                     value: 200.0,
-                    position: 153.402,
+                    position: 153.4019997109321,
                     label: None
                 },
                 ScaleTick {
                     value: 300.0,
-                    position: 165.14143,
+                    position: 165.14141698131084,
                     label: None
                 },
                 ScaleTick {
                     value: 400.0,
-                    position: 173.47067,
+                    position: 173.47066608853083,
                     label: None
                 },
                 ScaleTick {
                     value: 500.0,
-                    position: 179.93134,
+                    position: 179.93133362240127,
                     label: None
                 },
                 ScaleTick {
                     value: 600.0,
-                    position: 185.21008,
+                    position: 185.21008335890957,
                     label: None
                 },
                 ScaleTick {
                     value: 700.0,
-                    position: 189.6732,
+                    position: 189.67320266761715,
                     label: None
                 },
                 ScaleTick {
                     value: 800.0,
-                    position: 193.53934,
+                    position: 193.53933246612957,
                     label: None
                 },
                 ScaleTick {
                     value: 900.0,
-                    position: 196.9495,
+                    position: 196.9495006292883,
                     label: None
                 },
                 ScaleTick {
@@ -461,82 +461,82 @@ mod tests {
             vec![
                 ScaleTick {
                     value: 3.0,
-                    position: 9.911936,
+                    position: 9.911933984047996,
                     label: Some("3".to_string())
                 },
                 ScaleTick {
                     value: 4.0,
-                    position: 25.551811,
+                    position: 25.55180543248395,
                     label: None
                 },
                 ScaleTick {
                     value: 5.0,
-                    position: 37.683037,
+                    position: 37.68303219735881,
                     label: None
                 },
                 ScaleTick {
                     value: 6.0,
-                    position: 47.59497,
+                    position: 47.594966181406804,
                     label: None
                 },
                 ScaleTick {
                     value: 7.0,
-                    position: 55.975388,
+                    position: 55.97538695507676,
                     label: None
                 },
                 ScaleTick {
                     value: 8.0,
-                    position: 63.234837,
+                    position: 63.23483762984276,
                     label: None
                 },
                 ScaleTick {
                     value: 9.0,
-                    position: 69.63813,
+                    position: 69.63812693032966,
                     label: None
                 },
                 ScaleTick {
                     value: 10.0,
-                    position: 75.36606,
+                    position: 75.36606439471761,
                     label: Some("10".to_string())
                 },
                 ScaleTick {
                     value: 20.0,
-                    position: 113.0491,
+                    position: 113.04909659207642,
                     label: None
                 },
                 ScaleTick {
                     value: 30.0,
-                    position: 135.09225,
+                    position: 135.09225734099925,
                     label: None
                 },
                 ScaleTick {
                     value: 40.0,
-                    position: 150.73212,
+                    position: 150.73212878943525,
                     label: None
                 },
                 ScaleTick {
                     value: 50.0,
-                    position: 162.86334,
+                    position: 162.8633555543101,
                     label: None
                 },
                 ScaleTick {
                     value: 60.0,
-                    position: 172.7753,
+                    position: 172.77528953835807,
                     label: None
                 },
                 ScaleTick {
                     value: 70.0,
-                    position: 181.15572,
+                    position: 181.15571031202805,
                     label: None
                 },
                 ScaleTick {
                     value: 80.0,
-                    position: 188.41518,
+                    position: 188.415160986794,
                     label: None
                 },
                 ScaleTick {
                     value: 90.0,
-                    position: 194.81845,
+                    position: 194.81845028728094,
                     label: Some("90".to_string())
                 }
             ]
@@ -551,132 +551,132 @@ mod tests {
                 },
                 ScaleTick {
                     value: 0.002,
-                    position: 20.068663,
+                    position: 20.068666377598753,
                     label: None
                 },
                 ScaleTick {
                     value: 0.003,
-                    position: 31.80809,
+                    position: 31.808083647977508,
                     label: None
                 },
                 ScaleTick {
                     value: 0.004,
-                    position: 40.137337,
+                    position: 40.137332755197505,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.0050000004,
-                    position: 46.598007,
+                    value: 0.005,
+                    position: 46.59800028906791,
                     label: None
                 },
                 ScaleTick {
                     value: 0.006,
-                    position: 51.876755,
+                    position: 51.87675002557626,
                     label: None
                 },
                 ScaleTick {
                     value: 0.007,
-                    position: 56.339867,
+                    position: 56.3398693342838,
                     label: None
                 },
                 ScaleTick {
                     value: 0.008,
-                    position: 60.206,
+                    position: 60.20599913279625,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.009000001,
-                    position: 63.61616,
+                    value: 0.009000000000000001,
+                    position: 63.61616729595498,
                     label: None
                 },
                 ScaleTick {
                     value: 0.01,
-                    position: 66.66667,
+                    position: 66.66666666666666,
                     label: Some("0.01".to_string())
                 },
                 ScaleTick {
                     value: 0.02,
-                    position: 86.73534,
+                    position: 86.73533304426542,
                     label: None
                 },
                 ScaleTick {
                     value: 0.03,
-                    position: 98.47475,
+                    position: 98.47475031464415,
                     label: None
                 },
                 ScaleTick {
                     value: 0.04,
-                    position: 106.804,
+                    position: 106.80399942186416,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.049999997,
-                    position: 113.26467,
+                    value: 0.05,
+                    position: 113.26466695573458,
                     label: None
                 },
                 ScaleTick {
                     value: 0.06,
-                    position: 118.54342,
+                    position: 118.54341669224291,
                     label: None
                 },
                 ScaleTick {
                     value: 0.07,
-                    position: 123.00653,
+                    position: 123.00653600095046,
                     label: None
                 },
                 ScaleTick {
                     value: 0.08,
-                    position: 126.87267,
+                    position: 126.87266579946291,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.089999996,
-                    position: 130.28284,
+                    value: 0.09,
+                    position: 130.28283396262165,
                     label: None
                 },
                 ScaleTick {
                     value: 0.1,
-                    position: 133.33334,
+                    position: 133.33333333333331,
                     label: Some("0.1".to_string())
                 },
                 ScaleTick {
                     value: 0.2,
-                    position: 153.402,
+                    position: 153.4019997109321,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.3,
-                    position: 165.14143,
+                    value: 0.30000000000000004,
+                    position: 165.14141698131084,
                     label: None
                 },
                 ScaleTick {
                     value: 0.4,
-                    position: 173.47067,
+                    position: 173.47066608853083,
                     label: None
                 },
                 ScaleTick {
                     value: 0.5,
-                    position: 179.93134,
+                    position: 179.93133362240127,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.6,
-                    position: 185.21008,
+                    value: 0.6000000000000001,
+                    position: 185.21008335890957,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.7,
-                    position: 189.6732,
+                    value: 0.7000000000000001,
+                    position: 189.67320266761715,
                     label: None
                 },
                 ScaleTick {
                     value: 0.8,
-                    position: 193.53934,
+                    position: 193.53933246612957,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.90000004,
-                    position: 196.9495,
+                    value: 0.9,
+                    position: 196.9495006292883,
                     label: None
                 },
                 ScaleTick {
@@ -696,42 +696,42 @@ mod tests {
                 },
                 ScaleTick {
                     value: 2e-10,
-                    position: 6.020603,
+                    position: 6.020599913279625,
                     label: None
                 },
                 ScaleTick {
                     value: 3e-10,
-                    position: 9.542427,
+                    position: 9.542425094393252,
                     label: None
                 },
                 ScaleTick {
                     value: 4e-10,
-                    position: 12.041206,
+                    position: 12.04119982655925,
                     label: None
                 },
                 ScaleTick {
                     value: 5e-10,
-                    position: 13.979396,
+                    position: 13.979400086720375,
                     label: None
                 },
                 ScaleTick {
                     value: 6e-10,
-                    position: 15.56303,
+                    position: 15.563025007672875,
                     label: None
                 },
                 ScaleTick {
-                    value: 7e-10,
-                    position: 16.90197,
+                    value: 7.000000000000001e-10,
+                    position: 16.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 8e-10,
-                    position: 18.06179,
+                    position: 18.061799739838875,
                     label: None
                 },
                 ScaleTick {
                     value: 9e-10,
-                    position: 19.084854,
+                    position: 19.084850188786504,
                     label: None
                 },
                 ScaleTick {
@@ -741,42 +741,42 @@ mod tests {
                 },
                 ScaleTick {
                     value: 2e-9,
-                    position: 26.020605,
+                    position: 26.020599913279625,
                     label: None
                 },
                 ScaleTick {
-                    value: 2.9999998e-9,
-                    position: 29.542429,
+                    value: 3.0000000000000004e-9,
+                    position: 29.542425094393256,
                     label: None
                 },
                 ScaleTick {
                     value: 4e-9,
-                    position: 32.041206,
+                    position: 32.04119982655925,
                     label: None
                 },
                 ScaleTick {
                     value: 5e-9,
-                    position: 33.979397,
+                    position: 33.979400086720375,
                     label: None
                 },
                 ScaleTick {
-                    value: 5.9999996e-9,
-                    position: 35.56303,
+                    value: 6.000000000000001e-9,
+                    position: 35.56302500767288,
                     label: None
                 },
                 ScaleTick {
-                    value: 6.9999997e-9,
-                    position: 36.90197,
+                    value: 7.000000000000001e-9,
+                    position: 36.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 8e-9,
-                    position: 38.06179,
+                    position: 38.061799739838875,
                     label: None
                 },
                 ScaleTick {
-                    value: 9e-9,
-                    position: 39.084854,
+                    value: 9.000000000000001e-9,
+                    position: 39.084850188786504,
                     label: None
                 },
                 ScaleTick {
@@ -786,87 +786,87 @@ mod tests {
                 },
                 ScaleTick {
                     value: 2e-8,
-                    position: 46.020603,
+                    position: 46.020599913279625,
                     label: None
                 },
                 ScaleTick {
-                    value: 3e-8,
-                    position: 49.542427,
+                    value: 3.0000000000000004e-8,
+                    position: 49.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 4e-8,
-                    position: 52.0412,
+                    position: 52.04119982655925,
                     label: None
                 },
                 ScaleTick {
                     value: 5e-8,
-                    position: 53.979397,
+                    position: 53.979400086720375,
                     label: None
                 },
                 ScaleTick {
-                    value: 6e-8,
-                    position: 55.56302,
+                    value: 6.000000000000001e-8,
+                    position: 55.56302500767287,
                     label: None
                 },
                 ScaleTick {
                     value: 7e-8,
-                    position: 56.901962,
+                    position: 56.90196080028513,
                     label: None
                 },
                 ScaleTick {
                     value: 8e-8,
-                    position: 58.061802,
+                    position: 58.06179973983887,
                     label: None
                 },
                 ScaleTick {
                     value: 9e-8,
-                    position: 59.084858,
+                    position: 59.08485018878651,
                     label: None
                 },
                 ScaleTick {
                     value: 1e-7,
-                    position: 60.000004,
+                    position: 60.0,
                     label: Some("10e-7".to_string())
                 },
                 ScaleTick {
                     value: 2e-7,
-                    position: 66.0206,
+                    position: 66.02059991327963,
                     label: None
                 },
                 ScaleTick {
                     value: 3e-7,
-                    position: 69.54243,
+                    position: 69.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 4e-7,
-                    position: 72.0412,
+                    position: 72.04119982655925,
                     label: None
                 },
                 ScaleTick {
                     value: 5e-7,
-                    position: 73.97939,
+                    position: 73.97940008672037,
                     label: None
                 },
                 ScaleTick {
                     value: 6e-7,
-                    position: 75.56302,
+                    position: 75.56302500767288,
                     label: None
                 },
                 ScaleTick {
                     value: 7e-7,
-                    position: 76.90196,
+                    position: 76.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 8e-7,
-                    position: 78.0618,
+                    position: 78.06179973983888,
                     label: None
                 },
                 ScaleTick {
-                    value: 9.0000003e-7,
-                    position: 79.084854,
+                    value: 9e-7,
+                    position: 79.0848501887865,
                     label: None
                 },
                 ScaleTick {
@@ -876,42 +876,42 @@ mod tests {
                 },
                 ScaleTick {
                     value: 2e-6,
-                    position: 86.0206,
+                    position: 86.02059991327963,
                     label: None
                 },
                 ScaleTick {
                     value: 3e-6,
-                    position: 89.54243,
+                    position: 89.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 4e-6,
-                    position: 92.04119,
+                    position: 92.04119982655925,
                     label: None
                 },
                 ScaleTick {
-                    value: 5e-6,
-                    position: 93.97939,
+                    value: 4.9999999999999996e-6,
+                    position: 93.97940008672037,
                     label: None
                 },
                 ScaleTick {
                     value: 6e-6,
-                    position: 95.56302,
+                    position: 95.56302500767288,
                     label: None
                 },
                 ScaleTick {
                     value: 7e-6,
-                    position: 96.90196,
+                    position: 96.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 8e-6,
-                    position: 98.0618,
+                    position: 98.06179973983888,
                     label: None
                 },
                 ScaleTick {
                     value: 9e-6,
-                    position: 99.084854,
+                    position: 99.0848501887865,
                     label: None
                 },
                 ScaleTick {
@@ -921,87 +921,87 @@ mod tests {
                 },
                 ScaleTick {
                     value: 2e-5,
-                    position: 106.02061,
+                    position: 106.02059991327964,
                     label: None
                 },
                 ScaleTick {
-                    value: 3e-5,
-                    position: 109.54243,
+                    value: 3.0000000000000004e-5,
+                    position: 109.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 4e-5,
-                    position: 112.0412,
+                    position: 112.04119982655925,
                     label: None
                 },
                 ScaleTick {
                     value: 5e-5,
-                    position: 113.9794,
+                    position: 113.97940008672039,
                     label: None
                 },
                 ScaleTick {
-                    value: 6e-5,
-                    position: 115.56303,
+                    value: 6.000000000000001e-5,
+                    position: 115.56302500767288,
                     label: None
                 },
                 ScaleTick {
-                    value: 6.9999995e-5,
-                    position: 116.901955,
+                    value: 7.000000000000001e-5,
+                    position: 116.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 8e-5,
-                    position: 118.061806,
+                    position: 118.06179973983888,
                     label: None
                 },
                 ScaleTick {
                     value: 9e-5,
-                    position: 119.08486,
+                    position: 119.08485018878649,
                     label: None
                 },
                 ScaleTick {
                     value: 0.0001,
-                    position: 120.00001,
+                    position: 120.0,
                     label: Some("10e-4".to_string())
                 },
                 ScaleTick {
                     value: 0.0002,
-                    position: 126.0206,
+                    position: 126.02059991327963,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.00029999999,
-                    position: 129.54242,
+                    value: 0.00030000000000000003,
+                    position: 129.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 0.0004,
-                    position: 132.0412,
+                    position: 132.04119982655925,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.00049999997,
-                    position: 133.97939,
+                    value: 0.0005,
+                    position: 133.97940008672037,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.00059999997,
-                    position: 135.56303,
+                    value: 0.0006000000000000001,
+                    position: 135.56302500767288,
                     label: None
                 },
                 ScaleTick {
                     value: 0.0007,
-                    position: 136.90196,
+                    position: 136.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 0.0008,
-                    position: 138.0618,
+                    position: 138.06179973983888,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.0009,
-                    position: 139.08485,
+                    value: 0.0009000000000000001,
+                    position: 139.0848501887865,
                     label: None
                 },
                 ScaleTick {
@@ -1011,42 +1011,42 @@ mod tests {
                 },
                 ScaleTick {
                     value: 0.002,
-                    position: 146.0206,
+                    position: 146.02059991327963,
                     label: None
                 },
                 ScaleTick {
                     value: 0.003,
-                    position: 149.54242,
+                    position: 149.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 0.004,
-                    position: 152.04121,
+                    position: 152.04119982655925,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.0050000004,
-                    position: 153.9794,
+                    value: 0.005,
+                    position: 153.97940008672037,
                     label: None
                 },
                 ScaleTick {
                     value: 0.006,
-                    position: 155.56303,
+                    position: 155.56302500767288,
                     label: None
                 },
                 ScaleTick {
                     value: 0.007,
-                    position: 156.90196,
+                    position: 156.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 0.008,
-                    position: 158.0618,
+                    position: 158.06179973983888,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.009000001,
-                    position: 159.08485,
+                    value: 0.009000000000000001,
+                    position: 159.0848501887865,
                     label: None
                 },
                 ScaleTick {
@@ -1056,42 +1056,42 @@ mod tests {
                 },
                 ScaleTick {
                     value: 0.02,
-                    position: 166.02061,
+                    position: 166.02059991327963,
                     label: None
                 },
                 ScaleTick {
                     value: 0.03,
-                    position: 169.54243,
+                    position: 169.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 0.04,
-                    position: 172.0412,
+                    position: 172.04119982655925,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.049999997,
-                    position: 173.9794,
+                    value: 0.05,
+                    position: 173.97940008672037,
                     label: None
                 },
                 ScaleTick {
                     value: 0.06,
-                    position: 175.56302,
+                    position: 175.56302500767288,
                     label: None
                 },
                 ScaleTick {
                     value: 0.07,
-                    position: 176.90198,
+                    position: 176.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 0.08,
-                    position: 178.0618,
+                    position: 178.06179973983888,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.089999996,
-                    position: 179.08485,
+                    value: 0.09,
+                    position: 179.0848501887865,
                     label: None
                 },
                 ScaleTick {
@@ -1101,42 +1101,42 @@ mod tests {
                 },
                 ScaleTick {
                     value: 0.2,
-                    position: 186.0206,
+                    position: 186.02059991327963,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.3,
-                    position: 189.54242,
+                    value: 0.30000000000000004,
+                    position: 189.54242509439325,
                     label: None
                 },
                 ScaleTick {
                     value: 0.4,
-                    position: 192.0412,
+                    position: 192.04119982655925,
                     label: None
                 },
                 ScaleTick {
                     value: 0.5,
-                    position: 193.9794,
+                    position: 193.97940008672037,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.6,
-                    position: 195.56303,
+                    value: 0.6000000000000001,
+                    position: 195.56302500767288,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.7,
-                    position: 196.90196,
+                    value: 0.7000000000000001,
+                    position: 196.90196080028514,
                     label: None
                 },
                 ScaleTick {
                     value: 0.8,
-                    position: 198.06178,
+                    position: 198.06179973983888,
                     label: None
                 },
                 ScaleTick {
-                    value: 0.90000004,
-                    position: 199.08485,
+                    value: 0.9,
+                    position: 199.0848501887865,
                     label: None
                 },
                 ScaleTick {
@@ -1159,27 +1159,27 @@ mod tests {
             vec![
                 ScaleTick {
                     value: 0.0,
-                    position: 9.090909,
+                    position: 9.090909090909092,
                     label: Some("0.00".to_string())
                 },
                 ScaleTick {
                     value: 1.0,
-                    position: 27.272728,
+                    position: 27.272727272727273,
                     label: Some("1.00".to_string())
                 },
                 ScaleTick {
                     value: 2.0,
-                    position: 45.454544,
+                    position: 45.45454545454546,
                     label: Some("2.00".to_string())
                 },
                 ScaleTick {
                     value: 3.0,
-                    position: 63.636364,
+                    position: 63.63636363636363,
                     label: Some("3.00".to_string())
                 },
                 ScaleTick {
                     value: 4.0,
-                    position: 81.818184,
+                    position: 81.81818181818183,
                     label: Some("4.00".to_string())
                 },
                 ScaleTick {
@@ -1189,27 +1189,27 @@ mod tests {
                 },
                 ScaleTick {
                     value: 6.0,
-                    position: 118.18182,
+                    position: 118.18181818181817,
                     label: Some("6.00".to_string())
                 },
                 ScaleTick {
                     value: 7.0,
-                    position: 136.36363,
+                    position: 136.36363636363635,
                     label: Some("7.00".to_string())
                 },
                 ScaleTick {
                     value: 8.0,
-                    position: 154.54546,
+                    position: 154.54545454545456,
                     label: Some("8.00".to_string())
                 },
                 ScaleTick {
                     value: 9.0,
-                    position: 172.72726,
+                    position: 172.72727272727272,
                     label: Some("9.00".to_string())
                 },
                 ScaleTick {
                     value: 10.0,
-                    position: 190.90909,
+                    position: 190.9090909090909,
                     label: Some("10.00".to_string())
                 }
             ]
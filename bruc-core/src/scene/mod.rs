@@ -46,15 +46,15 @@ impl SceneItem {
         SceneItem::Group(Box::new(SceneGroup::with_items(items)))
     }
 
-    pub fn line(points: Vec<(f32, f32)>, stroke: String, stroke_width: f32) -> Self {
+    pub fn line(points: Vec<(f64, f64)>, stroke: String, stroke_width: f64) -> Self {
         SceneItem::Line(Box::new(SceneLine::new(points, stroke, stroke_width)))
     }
 
-    pub fn point(x: f32, y: f32, size: f32, color: String) -> Self {
+    pub fn point(x: f64, y: f64, size: f64, color: String) -> Self {
         SceneItem::Point(Box::new(ScenePoint::new(x, y, size, color)))
     }
 
-    pub fn rect(width: f32, height: f32, x: f32, y: f32, fill: String) -> Self {
+    pub fn rect(width: f64, height: f64, x: f64, y: f64, fill: String) -> Self {
         SceneItem::Rect(Box::new(SceneRect {
             width,
             height,
@@ -65,11 +65,11 @@ impl SceneItem {
     }
 
     pub fn arc(
-        start_angle: f32,
-        end_angle: f32,
-        radius: f32,
-        inner_radius: f32,
-        outer_radius: Option<f32>,
+        start_angle: f64,
+        end_angle: f64,
+        radius: f64,
+        inner_radius: f64,
+        outer_radius: Option<f64>,
         fill: String,
     ) -> Self {
         SceneItem::Arc(Box::new(SceneArc {
@@ -125,12 +125,12 @@ impl SceneGroup {
 #[derive(Debug, Clone, PartialEq)]
 pub struct SceneLine {
     pub(crate) stroke: String,
-    pub(crate) stroke_width: f32,
-    pub(crate) points: Vec<(f32, f32)>,
+    pub(crate) stroke_width: f64,
+    pub(crate) points: Vec<(f64, f64)>,
 }
 
 impl SceneLine {
-    pub fn new(points: Vec<(f32, f32)>, stroke: String, stroke_width: f32) -> Self {
+    pub fn new(points: Vec<(f64, f64)>, stroke: String, stroke_width: f64) -> Self {
         SceneLine {
             points,
             stroke,
@@ -141,34 +141,34 @@ impl SceneLine {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ScenePoint {
-    pub(crate) x: f32,
-    pub(crate) y: f32,
-    pub(crate) size: f32,
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+    pub(crate) size: f64,
     pub(crate) color: String,
 }
 
 impl ScenePoint {
-    pub fn new(x: f32, y: f32, size: f32, color: String) -> Self {
+    pub fn new(x: f64, y: f64, size: f64, color: String) -> Self {
         ScenePoint { x, y, color, size }
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SceneRect {
-    pub(crate) width: f32,
-    pub(crate) height: f32,
-    pub(crate) x: f32,
-    pub(crate) y: f32,
+    pub(crate) width: f64,
+    pub(crate) height: f64,
+    pub(crate) x: f64,
+    pub(crate) y: f64,
     pub(crate) fill: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SceneArc {
-    pub(crate) start_angle: f32,
-    pub(crate) end_angle: f32,
-    pub(crate) radius: f32,
-    pub(crate) outer_radius: Option<f32>,
-    pub(crate) inner_radius: f32,
+    pub(crate) start_angle: f64,
+    pub(crate) end_angle: f64,
+    pub(crate) radius: f64,
+    pub(crate) outer_radius: Option<f64>,
+    pub(crate) inner_radius: f64,
     pub(crate) fill: String,
 }
 
@@ -195,12 +195,12 @@ impl SceneAxis {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SceneAxisRule {
-    pub(crate) from: (f32, f32),
-    pub(crate) to: (f32, f32),
+    pub(crate) from: (f64, f64),
+    pub(crate) to: (f64, f64),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SceneAxisTick {
-    pub(crate) position: (f32, f32),
+    pub(crate) position: (f64, f64),
     pub(crate) label: Option<String>,
 }
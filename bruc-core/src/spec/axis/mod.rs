@@ -5,6 +5,7 @@ pub struct Axis {
     pub(crate) orientation: AxisOrientation,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl Axis {
     pub(crate) fn new(scale: &str, orientation: AxisOrientation) -> Self {
         Axis {
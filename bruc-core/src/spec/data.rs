@@ -1,13 +1,25 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::types::Schema;
+
 use crate::data::DataValue;
+use crate::spec::format::Format;
+use crate::spec::transform::error::Error;
+#[cfg(feature = "serde")]
+use crate::spec::transform::error::FormatError;
 use crate::spec::transform::pipe::Pipe;
 
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "DataEntryDefinition"))]
 pub struct DataEntry {
     pub(crate) name: String,
     pub(crate) values: Vec<DataValue>,
-    #[cfg_attr(feature = "serde", serde(default))]
     pub(crate) transform: Vec<Pipe>,
+    pub(crate) schema: Option<Schema>,
+    pub(crate) url: Option<String>,
+    pub(crate) format: Option<Format>,
+    pub(crate) source: Option<String>,
 }
 
 impl DataEntry {
@@ -16,19 +28,493 @@ impl DataEntry {
             name: name.to_string(),
             values,
             transform,
+            schema: None,
+            url: None,
+            format: None,
+            source: None,
+        }
+    }
+
+    /// Derive the entry from the values output by another entry, declared before this one. The
+    /// entry's transform pipes are applied after the ones of the `source` entry.
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    /// Load the entry's values from an `url` when the view is rendered, instead of using the
+    /// inline values. The loaded text is read as JSON, unless another format is declared.
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    /// Declare the `format` of the text loaded from the entry's url.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Declare the `schema` of the entry's values, instead of inferring it from the values. Text
+    /// values of fields declared as dates are parsed as ISO-8601 dates.
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        for value in &mut self.values {
+            value.parse_dates(&schema);
+        }
+
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Check that the transform pipes can be applied to the entry's values. Checking is skipped
+    /// if no schema is declared and there are no values to infer it from.
+    pub fn check(&self) -> Result<(), Error> {
+        let Some(mut schema) = self.schema.clone().or_else(|| self.infer_schema()) else {
+            return Ok(());
+        };
+
+        for pipe in &self.transform {
+            pipe.check(&mut schema)?;
+
+            // The following pipes can't be checked without knowing the fields of the values
+            if pipe.has_dynamic_fields() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fields present in the entry's values, either declared by the schema or found in the
+    /// values. No fields are known, if there is no schema and the values are loaded from an url
+    /// or missing.
+    pub(crate) fn fields(&self) -> Option<BTreeSet<String>> {
+        if let Some(schema) = &self.schema {
+            return Some(schema.fields().map(str::to_string).collect());
+        }
+
+        if self.values.is_empty() || self.url.is_some() {
+            return None;
         }
+
+        let fields = self
+            .values
+            .iter()
+            .flat_map(|value| value.instance.keys().cloned())
+            .collect();
+
+        Some(fields)
+    }
+
+    /// Infer the schema from the entry's values, including the nested items of arrays and
+    /// objects, see `Schema::infer`.
+    fn infer_schema(&self) -> Option<Schema> {
+        if self.values.is_empty() || self.url.is_some() {
+            return None;
+        }
+
+        let mut schema = Schema::new();
+        for value in &self.values {
+            for (key, item) in &value.instance {
+                schema.infer(key, item);
+            }
+        }
+
+        Some(schema)
+    }
+}
+
+/// `DataEntryDefinition` is the deserialized form of a `DataEntry`, before its values are read
+/// in their declared format and its transform pipes are checked against the schema.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+pub(crate) struct DataEntryDefinition {
+    name: String,
+    #[serde(default)]
+    values: DataValuesDefinition,
+    url: Option<String>,
+    format: Option<Format>,
+    source: Option<String>,
+    #[serde(default)]
+    transform: Vec<Pipe>,
+    schema: Option<Schema>,
+}
+
+/// `DataValuesDefinition` are the values of a data entry, either given inline, or as text to be
+/// read in the entry's format.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+pub(crate) enum DataValuesDefinition {
+    Inline(Vec<DataValue>),
+    Text(String),
+}
+
+#[cfg(feature = "serde")]
+impl Default for DataValuesDefinition {
+    fn default() -> Self {
+        DataValuesDefinition::Inline(Vec::new())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<DataEntryDefinition> for DataEntry {
+    type Error = Error;
+
+    fn try_from(definition: DataEntryDefinition) -> Result<Self, Self::Error> {
+        let values = match (definition.values, &definition.format) {
+            (DataValuesDefinition::Inline(values), _) => values,
+            (DataValuesDefinition::Text(text), Some(format)) => format.read(&text)?,
+            (DataValuesDefinition::Text(_), None) => return Err(FormatError::MissingFormat.into()),
+        };
+
+        let mut entry = DataEntry::new(&definition.name, values, definition.transform);
+        entry.url = definition.url;
+        entry.format = definition.format;
+        entry.source = definition.source;
+
+        let entry = match definition.schema {
+            Some(schema) => entry.with_schema(schema),
+            None => entry,
+        };
+        entry.check()?;
+
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::data::DataItem;
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::data::{DataEntry, DataValue};
+    use crate::spec::transform::filter::FilterPipe;
+    use crate::spec::transform::group::{GroupOperator, GroupPipe};
+    use crate::spec::transform::map::MapPipe;
+    use crate::spec::transform::pipe::Pipe;
+
+    #[test]
+    fn checks_transform_with_inferred_schema() {
+        let values = vec![DataValue::from_pairs(vec![
+            ("a", 3.0.into()),
+            ("b", true.into()),
+        ])];
+
+        let entry = DataEntry::new(
+            "data",
+            values.clone(),
+            vec![
+                Pipe::Map(MapPipe::new("a > 2 && b", "c").unwrap()),
+                Pipe::Filter(FilterPipe::new("c").unwrap()),
+                Pipe::Group(GroupPipe::new("a", GroupOperator::Count, "count")),
+                Pipe::Filter(FilterPipe::new("count > 1").unwrap()),
+            ],
+        );
+        assert!(entry.check().is_ok());
+
+        let entry = DataEntry::new(
+            "data",
+            values.clone(),
+            vec![Pipe::Map(MapPipe::new("a + b", "c").unwrap())],
+        );
+        assert_eq!(
+            entry.check().unwrap_err().to_string(),
+            "PipeError::Expression: TypeError::InvalidOperands: number + bool"
+        );
+
+        let entry = DataEntry::new(
+            "data",
+            values.clone(),
+            vec![Pipe::Filter(FilterPipe::new("a + 1").unwrap())],
+        );
+        assert_eq!(
+            entry.check().unwrap_err().to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected bool, found number"
+        );
+
+        let entry = DataEntry::new(
+            "data",
+            values,
+            vec![
+                Pipe::Group(GroupPipe::new("a", GroupOperator::Count, "count")),
+                Pipe::Filter(FilterPipe::new("b").unwrap()),
+            ],
+        );
+        assert_eq!(
+            entry.check().unwrap_err().to_string(),
+            "PipeError::Expression: TypeError::UnknownVariable: b"
+        );
+    }
+
+    #[test]
+    fn checks_transform_with_null_values() {
+        let values = vec![
+            DataValue::from_pairs(vec![("a", DataItem::Null)]),
+            DataValue::from_pairs(vec![("a", 3.0.into())]),
+        ];
+
+        let entry = DataEntry::new(
+            "data",
+            values,
+            vec![Pipe::Filter(FilterPipe::new("a != null && a > 2").unwrap())],
+        );
+        assert!(entry.check().is_ok());
+    }
+
+    #[test]
+    fn checks_transform_with_declared_schema() {
+        let entry = DataEntry::new(
+            "data",
+            Vec::new(),
+            vec![Pipe::Filter(FilterPipe::new("a > 2").unwrap())],
+        );
+        assert!(entry.check().is_ok());
+
+        let entry = entry.with_schema(Schema::from_pairs(vec![("a", DataType::Text)]));
+        assert_eq!(
+            entry.check().unwrap_err().to_string(),
+            "PipeError::Expression: TypeError::InvalidOperands: text > number"
+        );
+    }
+
+    #[test]
+    fn parses_dates_with_declared_schema() {
+        let values = vec![
+            DataValue::from_pairs(vec![("d", "2024-03-01".into()), ("e", "2024-03-01".into())]),
+            DataValue::from_pairs(vec![("d", "yesterday".into())]),
+        ];
+        let entry = DataEntry::new(
+            "data",
+            values,
+            vec![Pipe::Filter(FilterPipe::new("year(d) == 2024").unwrap())],
+        )
+        .with_schema(Schema::from_pairs(vec![
+            ("d", DataType::Date),
+            ("e", DataType::Text),
+        ]));
+
+        assert_eq!(
+            entry.values,
+            vec![
+                DataValue::from_pairs(vec![
+                    ("d", DataItem::Date(1_709_251_200_000)),
+                    ("e", "2024-03-01".into())
+                ]),
+                DataValue::from_pairs(vec![("d", DataItem::Null)]),
+            ]
+        );
+        assert!(entry.check().is_ok());
     }
 }
 
 #[cfg(feature = "serde")]
 #[cfg(test)]
 mod serde_tests {
+    use std::collections::BTreeMap;
+
+    use bruc_expression::data::DataItem;
+    use bruc_expression::types::{DataType, Schema};
+
     use crate::spec::data::{DataEntry, DataValue};
+    use crate::spec::format::Format;
+    use crate::spec::transform::filter::FilterPipe;
+    use crate::spec::transform::pipe::Pipe;
 
     #[test]
     fn deserialize_data_value() {
         let data_value: DataValue = serde_json::from_str(r#"{ "a": 2.0 }"#).unwrap();
         assert_eq!(data_value, DataValue::from_pairs(vec![("a", 2.0.into())]));
+
+        let data_value: DataValue = serde_json::from_str(r#"{ "a": null }"#).unwrap();
+        assert_eq!(
+            data_value,
+            DataValue::from_pairs(vec![("a", DataItem::Null)])
+        );
+
+        let data_value: DataValue =
+            serde_json::from_str(r#"{ "geo": { "lat": 1.0 }, "tags": ["a", null] }"#).unwrap();
+        assert_eq!(
+            data_value,
+            DataValue::from_pairs(vec![
+                (
+                    "geo",
+                    DataItem::Object(BTreeMap::from([("lat".to_string(), 1.0.into())]))
+                ),
+                ("tags", DataItem::Array(vec!["a".into(), DataItem::Null]))
+            ])
+        );
+    }
+
+    #[test]
+    fn deserializes_data_with_nested_values() {
+        let data: Vec<DataEntry> = serde_json::from_str(
+            r#"[{
+        "name": "my_data",
+        "values": [{ "geo": { "lat": 1.0 }, "tags": ["a"] }],
+        "transform": [{ "type": "filter", "fn": "geo.lat > 2 && tags[0] == 'a'" }]
+      }]"#,
+        )
+        .unwrap();
+        assert_eq!(data[0].values.len(), 1);
+
+        let error = serde_json::from_str::<Vec<DataEntry>>(
+            r#"[{
+        "name": "my_data",
+        "values": [{ "geo": { "lat": 1.0 } }],
+        "transform": [{ "type": "map", "fn": "geo.lat + 'a'", "output": "b" }]
+      }]"#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("PipeError::Expression: TypeError::InvalidOperands: number + text"));
+    }
+
+    #[test]
+    fn deserializes_data_with_schema() {
+        let data: Vec<DataEntry> = serde_json::from_str(
+            r#"[{
+        "name": "my_data",
+        "values": [],
+        "schema": { "a": "number", "b": "bool" },
+        "transform": [{ "type": "filter", "fn": "b && a > 2" }]
+      }]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            data[0].schema,
+            Some(Schema::from_pairs(vec![
+                ("a", DataType::Number),
+                ("b", DataType::Bool)
+            ]))
+        );
+    }
+
+    #[test]
+    fn deserializes_data_with_dates() {
+        let data: Vec<DataEntry> = serde_json::from_str(
+            r#"[{
+        "name": "my_data",
+        "values": [{ "d": "2024-03-01T12:30:00Z" }, { "d": "2024-03-01T14:30:00+02:00" }],
+        "schema": { "d": "date" },
+        "transform": [{ "type": "filter", "fn": "hours(d) > 10" }]
+      }]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            data[0].values,
+            vec![
+                DataValue::from_pairs(vec![("d", DataItem::Date(1_709_296_200_000))]),
+                DataValue::from_pairs(vec![("d", DataItem::Date(1_709_296_200_000))]),
+            ]
+        );
+    }
+
+    #[test]
+    fn deserializes_data_in_csv_format() {
+        let data: Vec<DataEntry> = serde_json::from_str(
+            r#"[{
+        "name": "my_data",
+        "format": { "type": "csv", "delimiter": ";", "parse": { "id": "text" } },
+        "values": "id;a;d\n01;3;2024-03-01\n02;\"4\";",
+        "transform": [{ "type": "filter", "fn": "a > 2 && id != '01'" }]
+      }]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            data[0].values,
+            vec![
+                DataValue::from_pairs(vec![
+                    ("id", "01".into()),
+                    ("a", 3.0.into()),
+                    ("d", DataItem::Date(1_709_251_200_000))
+                ]),
+                DataValue::from_pairs(vec![
+                    ("id", "02".into()),
+                    ("a", 4.0.into()),
+                    ("d", DataItem::Null)
+                ]),
+            ]
+        );
+
+        let error = serde_json::from_str::<Vec<DataEntry>>(
+            r#"[{ "name": "my_data", "format": { "type": "tsv" }, "values": "a\tb\n1" }]"#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("FormatError::FieldCount: expected 2 fields, found 1 in line 2"));
+
+        let error =
+            serde_json::from_str::<Vec<DataEntry>>(r#"[{ "name": "my_data", "values": "a\n1" }]"#)
+                .unwrap_err();
+        assert!(error.to_string().starts_with("FormatError::MissingFormat"));
+    }
+
+    #[test]
+    fn deserializes_data_with_url() {
+        let data: Vec<DataEntry> = serde_json::from_str(
+            r#"[{
+        "name": "my_data",
+        "url": "data/values.csv",
+        "format": { "type": "csv" },
+        "transform": [{ "type": "filter", "fn": "a > 2" }]
+      }]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            data,
+            vec![DataEntry::new(
+                "my_data",
+                Vec::new(),
+                vec![Pipe::Filter(FilterPipe::new("a > 2").unwrap())]
+            )
+            .with_url("data/values.csv")
+            .with_format(Format::csv())]
+        );
+    }
+
+    #[test]
+    fn deserializes_derived_data() {
+        let data: Vec<DataEntry> = serde_json::from_str(
+            r#"[{
+        "name": "summary",
+        "source": "primary",
+        "transform": [{ "type": "filter", "fn": "a > 2" }]
+      }]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            data,
+            vec![DataEntry::new(
+                "summary",
+                Vec::new(),
+                vec![Pipe::Filter(FilterPipe::new("a > 2").unwrap())]
+            )
+            .with_source("primary")]
+        );
+    }
+
+    #[test]
+    fn doesnt_deserialize_data_with_type_mismatches() {
+        let error = serde_json::from_str::<Vec<DataEntry>>(
+            r#"[{
+        "name": "my_data",
+        "values": [{"a": 3.0, "b": true }],
+        "transform": [{ "type": "map", "fn": "a + b", "output": "c" }]
+      }]"#,
+        )
+        .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("PipeError::Expression: TypeError::InvalidOperands: number + bool"));
     }
 
     #[test]
@@ -0,0 +1,483 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use bruc_expression::data::DataItem;
+use bruc_expression::date;
+use bruc_expression::types::{DataType, Schema};
+
+use crate::data::DataValue;
+use crate::spec::transform::error::FormatError;
+
+/// `FormatKind` lists the text formats data values can be read from.
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum FormatKind {
+    Json,
+    Csv,
+    Tsv,
+}
+
+/// `Format` describes how to read data values from text, either as a JSON array of objects, or
+/// as delimiter-separated text following RFC 4180. For the latter, the first row is read as the
+/// header with the field names, which are unique. The type of each field is either declared with `parse`, or
+/// inferred from its values as number, bool, date or text.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Format {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub(crate) kind: FormatKind,
+    pub(crate) delimiter: Option<char>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) parse: Schema,
+}
+
+impl Format {
+    pub fn new(kind: FormatKind) -> Self {
+        Format {
+            kind,
+            delimiter: None,
+            parse: Schema::new(),
+        }
+    }
+
+    pub fn json() -> Self {
+        Format::new(FormatKind::Json)
+    }
+
+    pub fn csv() -> Self {
+        Format::new(FormatKind::Csv)
+    }
+
+    pub fn tsv() -> Self {
+        Format::new(FormatKind::Tsv)
+    }
+
+    /// Separate fields by a certain `delimiter`, instead of the default one of the format's kind.
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Declare the types of certain fields, instead of inferring them from their values.
+    pub fn with_parse(mut self, parse: Schema) -> Self {
+        self.parse = parse;
+        self
+    }
+
+    fn delimiter(&self) -> char {
+        self.delimiter.unwrap_or(match self.kind {
+            FormatKind::Json | FormatKind::Csv => ',',
+            FormatKind::Tsv => '\t',
+        })
+    }
+
+    /// Read the data values of a certain `text`. Empty fields, and fields that can't be read as
+    /// their declared type, are read as null.
+    pub fn read(&self, text: &str) -> Result<Vec<DataValue>, FormatError> {
+        match self.kind {
+            FormatKind::Json => self.read_json(text),
+            FormatKind::Csv | FormatKind::Tsv => self.read_delimited(text),
+        }
+    }
+
+    /// Read the data values of a JSON array of objects. Text of fields declared as dates is
+    /// parsed as ISO-8601 dates.
+    #[cfg(feature = "serde")]
+    fn read_json(&self, text: &str) -> Result<Vec<DataValue>, FormatError> {
+        let mut values: Vec<DataValue> = serde_json::from_str(text)
+            .map_err(|error| FormatError::InvalidJson(error.to_string()))?;
+
+        for value in &mut values {
+            value.parse_dates(&self.parse);
+        }
+
+        Ok(values)
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn read_json(&self, _text: &str) -> Result<Vec<DataValue>, FormatError> {
+        Err(FormatError::InvalidJson(
+            "reading JSON requires the serde feature".to_string(),
+        ))
+    }
+
+    fn read_delimited(&self, text: &str) -> Result<Vec<DataValue>, FormatError> {
+        let mut rows = Reader::new(text, self.delimiter()).rows()?.into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Ok(Vec::new());
+        };
+
+        let mut fields = HashSet::new();
+        if let Some(field) = header.iter().find(|field| !fields.insert(field.as_str())) {
+            return Err(FormatError::DuplicateField(field.clone()));
+        }
+
+        let rows = rows
+            .map(|(line, row)| {
+                if row.len() != header.len() {
+                    return Err(FormatError::FieldCount {
+                        line,
+                        expected: header.len(),
+                        found: row.len(),
+                    });
+                }
+
+                Ok(row)
+            })
+            .collect::<Result<Vec<Vec<String>>, FormatError>>()?;
+
+        let types: Vec<DataType> = header
+            .iter()
+            .enumerate()
+            .map(|(column, field)| {
+                self.parse
+                    .find(field)
+                    .unwrap_or_else(|| infer(rows.iter().map(|row| row[column].as_str())))
+            })
+            .collect();
+
+        let values = rows
+            .into_iter()
+            .map(|row| {
+                let instance: HashMap<String, DataItem> = header
+                    .iter()
+                    .zip(row)
+                    .zip(&types)
+                    .map(|((field, text), data_type)| (field.clone(), convert(&text, *data_type)))
+                    .collect();
+
+                DataValue::with_instance(instance)
+            })
+            .collect();
+
+        Ok(values)
+    }
+}
+
+/// Infer the type of a column from its non-empty values. Number, bool and date are tried in
+/// order, and text is used if the values can't be all read as any of them, or if the column has
+/// no values at all.
+fn infer<'a>(column: impl Iterator<Item = &'a str> + Clone) -> DataType {
+    let mut column = column.filter(|text| !text.is_empty()).peekable();
+    if column.peek().is_none() {
+        return DataType::Text;
+    }
+
+    [DataType::Number, DataType::Bool, DataType::Date]
+        .into_iter()
+        .find(|data_type| {
+            column
+                .clone()
+                .all(|text| convert(text, *data_type) != DataItem::Null)
+        })
+        .unwrap_or(DataType::Text)
+}
+
+/// Convert the text of a field into a data item of a certain type.
+fn convert(text: &str, data_type: DataType) -> DataItem {
+    if text.is_empty() {
+        return DataItem::Null;
+    }
+
+    match data_type {
+        DataType::Number => text
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map_or(DataItem::Null, DataItem::Number),
+        DataType::Bool if text.eq_ignore_ascii_case("true") => DataItem::Bool(true),
+        DataType::Bool if text.eq_ignore_ascii_case("false") => DataItem::Bool(false),
+        DataType::Bool => DataItem::Null,
+        DataType::Date => date::parse(text).map_or(DataItem::Null, DataItem::Date),
+        _ => DataItem::Text(text.to_string()),
+    }
+}
+
+/// `Reader` splits delimiter-separated text into rows of fields. Quoted fields may contain
+/// delimiters, line breaks and quotes escaped as `""`, and can't be surrounded by whitespace. Empty lines are skipped. Lines are
+/// counted as they're read, so that rows are reported by the line they start in.
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    delimiter: char,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(text: &'a str, delimiter: char) -> Self {
+        Reader {
+            chars: text.chars().peekable(),
+            delimiter,
+            line: 1,
+        }
+    }
+
+    /// Read the rows of the text, together with the line each row starts in.
+    fn rows(mut self) -> Result<Vec<(usize, Vec<String>)>, FormatError> {
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut start = self.line;
+
+        loop {
+            match self.chars.next() {
+                // Quoted fields start right after the delimiter
+                Some('"') if !quoted && !field.is_empty() && field.trim().is_empty() => {
+                    return Err(FormatError::InvalidQuote(self.line));
+                }
+                Some('"') if field.is_empty() && !quoted => {
+                    let opened = self.line;
+                    self.quoted(&mut field)
+                        .ok_or(FormatError::UnterminatedQuote(opened))?;
+                    quoted = true;
+
+                    match self.chars.peek() {
+                        None | Some('\r' | '\n') => {}
+                        Some(next) if *next == self.delimiter => {}
+                        Some(_) => return Err(FormatError::InvalidQuote(self.line)),
+                    }
+                }
+                Some(next) if next == self.delimiter => {
+                    row.push(std::mem::take(&mut field));
+                    quoted = false;
+                }
+                next @ (Some('\r' | '\n') | None) => {
+                    if next == Some('\r') && self.chars.peek() == Some(&'\n') {
+                        self.chars.next();
+                    }
+
+                    if !row.is_empty() || !field.is_empty() || quoted {
+                        row.push(std::mem::take(&mut field));
+                        rows.push((start, std::mem::take(&mut row)));
+                    }
+                    quoted = false;
+
+                    if next.is_none() {
+                        return Ok(rows);
+                    }
+
+                    self.line += 1;
+                    start = self.line;
+                }
+                Some(next) => field.push(next),
+            }
+        }
+    }
+
+    /// Read the rest of a quoted field, after its opening quote. Nothing is returned if the
+    /// closing quote is missing.
+    fn quoted(&mut self, field: &mut String) -> Option<()> {
+        loop {
+            match self.chars.next()? {
+                '"' if self.chars.peek() == Some(&'"') => {
+                    self.chars.next();
+                    field.push('"');
+                }
+                '"' => return Some(()),
+                next => {
+                    // Line breaks of quoted fields are lines of the text as well
+                    if next == '\n' || (next == '\r' && self.chars.peek() != Some(&'\n')) {
+                        self.line += 1;
+                    }
+                    field.push(next);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::data::DataItem;
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::data::DataValue;
+    use crate::spec::format::{infer, Format};
+    use crate::spec::transform::error::FormatError;
+
+    #[test]
+    fn reads_csv() {
+        let values = Format::csv()
+            .read("a,b,c,d\n1,true,2024-03-01,x\n-2.5,false,2024-03-02T12:00:00Z,y\n")
+            .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                DataValue::from_pairs(vec![
+                    ("a", 1.0.into()),
+                    ("b", true.into()),
+                    ("c", DataItem::Date(1_709_251_200_000)),
+                    ("d", "x".into())
+                ]),
+                DataValue::from_pairs(vec![
+                    ("a", (-2.5).into()),
+                    ("b", false.into()),
+                    ("c", DataItem::Date(1_709_380_800_000)),
+                    ("d", "y".into())
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_csv_with_quotes() {
+        let values = Format::csv()
+            .read("name,note\r\n\"Doe, Jane\",\"said \"\"hi\"\"\ntwice\"\r\nx,\"\"\r\n")
+            .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                DataValue::from_pairs(vec![
+                    ("name", "Doe, Jane".into()),
+                    ("note", "said \"hi\"\ntwice".into())
+                ]),
+                DataValue::from_pairs(vec![("name", "x".into()), ("note", DataItem::Null)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_tsv_and_custom_delimiters() {
+        let expected = vec![
+            DataValue::from_pairs(vec![("a", 1.0.into()), ("b", "x,y".into())]),
+            DataValue::from_pairs(vec![("a", DataItem::Null), ("b", "z".into())]),
+        ];
+
+        let values = Format::tsv().read("a\tb\n1\tx,y\n\tz").unwrap();
+        assert_eq!(values, expected);
+
+        let values = Format::csv()
+            .with_delimiter(';')
+            .read("a;b\n\n1;x,y\n;z\n")
+            .unwrap();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn infers_text_for_mixed_columns() {
+        let values = Format::csv().read("a,b\n1,true\nx,2").unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                DataValue::from_pairs(vec![("a", "1".into()), ("b", "true".into())]),
+                DataValue::from_pairs(vec![("a", "x".into()), ("b", "2".into())]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_csv_with_declared_types() {
+        let values = Format::csv()
+            .with_parse(Schema::from_pairs(vec![
+                ("id", DataType::Text),
+                ("n", DataType::Number),
+            ]))
+            .read("id,n\n001,3\n002,n/a")
+            .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                DataValue::from_pairs(vec![("id", "001".into()), ("n", 3.0.into())]),
+                DataValue::from_pairs(vec![("id", "002".into()), ("n", DataItem::Null)]),
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn reads_json() {
+        let values = Format::json()
+            .with_parse(Schema::from_pairs(vec![("d", DataType::Date)]))
+            .read(r#"[{ "a": 1.0, "d": "2024-03-01" }]"#)
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![DataValue::from_pairs(vec![
+                ("a", 1.0.into()),
+                ("d", DataItem::Date(1_709_251_200_000))
+            ])]
+        );
+
+        assert!(matches!(
+            Format::json().read("{ \"a\": 1.0 }"),
+            Err(FormatError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn doesnt_read_malformed_csv() {
+        assert_eq!(Format::csv().read(""), Ok(Vec::new()));
+        assert_eq!(
+            Format::csv().read("a,b\n1,\"2"),
+            Err(FormatError::UnterminatedQuote(2))
+        );
+        assert_eq!(
+            Format::csv().read("a,b\n1,\"2\"3"),
+            Err(FormatError::InvalidQuote(2))
+        );
+        assert_eq!(
+            Format::csv().read("a,b\n1, \"2\""),
+            Err(FormatError::InvalidQuote(2))
+        );
+        assert_eq!(
+            Format::csv().read("a,b\n\t\"1\",2"),
+            Err(FormatError::InvalidQuote(2))
+        );
+        assert_eq!(
+            Format::csv().read("a,b,a\n1,2,3"),
+            Err(FormatError::DuplicateField("a".to_string()))
+        );
+        assert_eq!(
+            Format::tsv().read("a\tb\tb\n"),
+            Err(FormatError::DuplicateField("b".to_string()))
+        );
+        assert_eq!(
+            Format::csv().read("a,b\n1,2\n3"),
+            Err(FormatError::FieldCount {
+                line: 3,
+                expected: 2,
+                found: 1
+            })
+        );
+
+        // Lines are counted after quoted fields spanning several lines, and empty lines
+        let text = "a,b\n\"1\n2\n3\",4\n\n5,\"6";
+        assert_eq!(
+            Format::csv().read(text),
+            Err(FormatError::UnterminatedQuote(6))
+        );
+        assert_eq!(
+            Format::csv().read("a,b\r\n\"1\r\n2\",3\r\n4\"5\",6\r\n\"7\"8,9"),
+            Err(FormatError::InvalidQuote(5))
+        );
+        assert_eq!(
+            Format::csv().read("a,b\n\"1\n2\",3\n\n4"),
+            Err(FormatError::FieldCount {
+                line: 5,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn infers_text_for_empty_columns() {
+        let values = Format::csv().read("a,b\n1,\n2,").unwrap();
+        assert_eq!(
+            values,
+            vec![
+                DataValue::from_pairs(vec![("a", 1.0.into()), ("b", DataItem::Null)]),
+                DataValue::from_pairs(vec![("a", 2.0.into()), ("b", DataItem::Null)]),
+            ]
+        );
+
+        assert_eq!(infer(["", ""].into_iter()), DataType::Text);
+        assert_eq!(infer(["", "1"].into_iter()), DataType::Number);
+    }
+}
@@ -5,6 +5,7 @@ use crate::spec::shape::Shape;
 
 pub mod axis;
 pub mod data;
+pub mod format;
 pub mod scale;
 pub mod shape;
 pub mod transform;
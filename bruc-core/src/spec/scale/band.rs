@@ -10,7 +10,7 @@ pub struct BandScale {
     pub(crate) range: Range,
 
     #[cfg_attr(feature = "serde", serde(default))]
-    pub(crate) padding: f32,
+    pub(crate) padding: f64,
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(untagged))]
 pub enum Domain {
-    Literal(Vec<f32>),
+    Literal(Vec<f64>),
     DataField { data: String, field: String },
 }
 
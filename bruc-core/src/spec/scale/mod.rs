@@ -39,6 +39,7 @@ pub enum ScaleKind {
     Log(LogScale),
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl ScaleKind {
     pub(crate) fn domain(&self) -> &Domain {
         match self {
@@ -129,6 +130,7 @@ mod serde_tests {
                     padding: 0.0
                 })
             )
-        )
+        );
+        assert_eq!(scale.kind.domain(), &Domain::Literal(vec![0.0, 100.0]));
     }
 }
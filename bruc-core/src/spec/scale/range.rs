@@ -2,7 +2,7 @@
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(untagged))]
 pub enum Range {
-    Literal(f32, f32),
+    Literal(f64, f64),
 }
 
 impl Range {
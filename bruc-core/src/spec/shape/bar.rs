@@ -1,13 +1,18 @@
-use super::{base::BaseShapeProperties, DataSource};
+use super::base::BaseShapeProperties;
+use super::DataSource;
 
+#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) struct BarPropertiesBuilder {
     width: Option<DataSource>,
     height: Option<DataSource>,
     x: Option<DataSource>,
     y: Option<DataSource>,
+    x2: Option<DataSource>,
+    y2: Option<DataSource>,
     fill: String,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl BarPropertiesBuilder {
     pub(crate) fn new() -> Self {
         BarPropertiesBuilder {
@@ -15,6 +20,8 @@ impl BarPropertiesBuilder {
             height: None,
             x: None,
             y: None,
+            x2: None,
+            y2: None,
             fill: default_fill(),
         }
     }
@@ -39,6 +46,16 @@ impl BarPropertiesBuilder {
         self
     }
 
+    pub(crate) fn with_x2(mut self, x2: DataSource) -> Self {
+        self.x2 = Some(x2);
+        self
+    }
+
+    pub(crate) fn with_y2(mut self, y2: DataSource) -> Self {
+        self.y2 = Some(y2);
+        self
+    }
+
     pub(crate) fn with_fill(mut self, fill: &str) -> Self {
         self.fill = fill.to_string();
         self
@@ -47,6 +64,8 @@ impl BarPropertiesBuilder {
     pub(crate) fn build(self) -> BarProperties {
         BarProperties {
             base: BaseShapeProperties::new(self.x, self.y, self.width, self.height),
+            x2: self.x2,
+            y2: self.y2,
             fill: self.fill,
         }
     }
@@ -59,6 +78,7 @@ pub struct BarShape {
     pub(crate) props: BarProperties,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl BarShape {
     pub(crate) fn new(props: BarProperties) -> Self {
         BarShape { props }
@@ -74,6 +94,12 @@ impl BarShape {
 pub(crate) struct BarProperties {
     #[cfg_attr(feature = "serde", serde(default = "default_fill"))]
     pub(crate) fill: String,
+    /// End of the bar's horizontal span, starting at `x`, e.g. for the bins of histograms. The
+    /// bar's width is ignored if given.
+    pub(crate) x2: Option<DataSource>,
+    /// End of the bar's vertical span, starting at `y`, e.g. for stacked bars. The bar's
+    /// height is ignored if given.
+    pub(crate) y2: Option<DataSource>,
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub(crate) base: BaseShapeProperties,
 }
@@ -112,4 +138,54 @@ mod serde_tests {
             )
         )
     }
+
+    #[test]
+    fn deserialize_binned_bar_shape() {
+        let bar_shape: BarShape = serde_json::from_str(
+            r#"{
+        "properties": {
+          "x": { "field": "bin0", "scale": "xscale" },
+          "x2": { "field": "bin1", "scale": "xscale" },
+          "y": { "field": "count", "scale": "yscale" }
+        }
+      }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            bar_shape,
+            BarShape::new(
+                BarPropertiesBuilder::new()
+                    .with_x(DataSource::field("bin0", Some("xscale")))
+                    .with_x2(DataSource::field("bin1", Some("xscale")))
+                    .with_y(DataSource::field("count", Some("yscale")))
+                    .build()
+            )
+        )
+    }
+
+    #[test]
+    fn deserialize_stacked_bar_shape() {
+        let bar_shape: BarShape = serde_json::from_str(
+            r#"{
+        "properties": {
+          "x": { "field": "x", "scale": "xscale" },
+          "y": { "field": "y0", "scale": "yscale" },
+          "y2": { "field": "y1", "scale": "yscale" }
+        }
+      }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            bar_shape,
+            BarShape::new(
+                BarPropertiesBuilder::new()
+                    .with_x(DataSource::field("x", Some("xscale")))
+                    .with_y(DataSource::field("y0", Some("yscale")))
+                    .with_y2(DataSource::field("y1", Some("yscale")))
+                    .build()
+            )
+        )
+    }
 }
@@ -2,6 +2,8 @@ use crate::spec::shape::DataSource;
 
 pub(crate) const X_AXIS_FIELD_NAME: &str = "x";
 pub(crate) const Y_AXIS_FIELD_NAME: &str = "y";
+pub(crate) const X2_AXIS_FIELD_NAME: &str = "x2";
+pub(crate) const Y2_AXIS_FIELD_NAME: &str = "y2";
 pub(crate) const WIDTH_FIELD_NAME: &str = "width";
 pub(crate) const HEIGHT_FIELD_NAME: &str = "height";
 
@@ -14,6 +16,7 @@ pub struct BaseShapeProperties {
     pub(crate) height: Option<DataSource>,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl BaseShapeProperties {
     pub fn new(
         x: Option<DataSource>,
@@ -1,14 +1,16 @@
 use crate::spec::shape::base::BaseShapeProperties;
 use crate::spec::shape::DataSource;
 
+#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) struct LinePropertiesBuilder {
     x: Option<DataSource>,
     y: Option<DataSource>,
     interpolate: Interpolate,
     stroke: Option<String>,
-    stroke_width: Option<f32>,
+    stroke_width: Option<f64>,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl LinePropertiesBuilder {
     pub(crate) fn new() -> Self {
         LinePropertiesBuilder {
@@ -40,7 +42,7 @@ impl LinePropertiesBuilder {
         self
     }
 
-    pub(crate) fn with_stroke_width(mut self, stroke_width: f32) -> Self {
+    pub(crate) fn with_stroke_width(mut self, stroke_width: f64) -> Self {
         self.stroke_width = Some(stroke_width);
         self
     }
@@ -62,6 +64,7 @@ pub struct LineShape {
     pub(crate) props: LineProperties,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl LineShape {
     pub(crate) fn new(props: LineProperties) -> LineShape {
         LineShape { props }
@@ -78,7 +81,7 @@ pub(crate) struct LineProperties {
     #[cfg_attr(feature = "serde", serde(default))]
     pub(crate) interpolate: Interpolate,
     pub(crate) stroke: Option<String>,
-    pub(crate) stroke_width: Option<f32>,
+    pub(crate) stroke_width: Option<f64>,
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub(crate) base: BaseShapeProperties,
 }
@@ -19,6 +19,7 @@ pub struct Shape {
     pub(crate) kind: ShapeKind,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl Shape {
     pub(crate) fn line(from: &str, line: LineShape) -> Self {
         Shape {
@@ -1,12 +1,14 @@
 use super::DataSource;
 
+#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) struct PiePropertiesBuilder {
     value: DataSource,
-    pad_angle: Option<f32>,
-    inner_radius: Option<f32>,
+    pad_angle: Option<f64>,
+    inner_radius: Option<f64>,
     outer_radius: Option<DataSource>,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl PiePropertiesBuilder {
     pub(crate) fn new(value: DataSource) -> Self {
         PiePropertiesBuilder {
@@ -17,12 +19,12 @@ impl PiePropertiesBuilder {
         }
     }
 
-    pub(crate) fn with_pad_angle(mut self, pad_angle: f32) -> Self {
+    pub(crate) fn with_pad_angle(mut self, pad_angle: f64) -> Self {
         self.pad_angle = Some(pad_angle);
         self
     }
 
-    pub(crate) fn with_inner_radius(mut self, inner_radius: f32) -> Self {
+    pub(crate) fn with_inner_radius(mut self, inner_radius: f64) -> Self {
         self.inner_radius = Some(inner_radius);
         self
     }
@@ -49,6 +51,7 @@ pub struct PieShape {
     pub(crate) props: PieProperties,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl PieShape {
     pub(crate) fn new(props: PieProperties) -> Self {
         PieShape { props }
@@ -64,9 +67,9 @@ impl PieShape {
 pub(crate) struct PieProperties {
     pub(crate) value: DataSource,
     #[cfg_attr(feature = "serde", serde(default))]
-    pub(crate) pad_angle: f32,
+    pub(crate) pad_angle: f64,
     #[cfg_attr(feature = "serde", serde(default))]
-    pub(crate) inner_radius: f32,
+    pub(crate) inner_radius: f64,
     #[cfg_attr(feature = "serde", serde(default))]
     pub(crate) outer_radius: Option<DataSource>,
 }
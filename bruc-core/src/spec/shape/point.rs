@@ -7,6 +7,7 @@ pub struct PointShape {
     pub(crate) props: PointProperties,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl PointShape {
     pub(crate) fn new(props: PointProperties) -> Self {
         PointShape { props }
@@ -26,6 +27,7 @@ pub(crate) struct PointProperties {
     pub(crate) y: Option<DataSource>,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) struct PointPropertiesBuilder {
     x: Option<DataSource>,
     y: Option<DataSource>,
@@ -33,6 +35,7 @@ pub(crate) struct PointPropertiesBuilder {
     size: Option<DataSource>,
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl PointPropertiesBuilder {
     pub(crate) fn new() -> Self {
         PointPropertiesBuilder {
@@ -0,0 +1,284 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::error::{Error, PipeError};
+
+/// `AggregatePipe` groups data values by the values of the `groupby` fields, and summarizes each
+/// group into a single value with the results of the aggregate `ops`. Without `groupby` fields,
+/// all data values are summarized into a single value.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct AggregatePipe {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) groupby: Vec<String>,
+    pub(crate) ops: Vec<AggregateField>,
+}
+
+impl AggregatePipe {
+    pub fn new(groupby: Vec<&str>, ops: Vec<AggregateField>) -> AggregatePipe {
+        AggregatePipe {
+            groupby: groupby.into_iter().map(str::to_string).collect(),
+            ops,
+        }
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.groupby
+            .iter()
+            .map(String::as_str)
+            .chain(self.ops.iter().filter_map(|op| op.field.as_deref()))
+            .collect()
+    }
+
+    /// Output fields of the resulting groups.
+    pub(crate) fn outputs(&self) -> BTreeSet<String> {
+        self.groupby
+            .iter()
+            .chain(self.ops.iter().map(|op| &op.output))
+            .cloned()
+            .collect()
+    }
+
+    /// Check that the `groupby` fields and the fields of the aggregate operations are present
+    /// in a certain `schema`, and replace the schema with the fields of the resulting groups.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let mut result = Schema::new();
+
+        for field in &self.groupby {
+            result.insert(field, find(schema, field)?);
+        }
+
+        for op in &self.ops {
+            result.insert(&op.output, op.check(schema)?);
+        }
+
+        *schema = result;
+
+        Ok(())
+    }
+}
+
+/// `AggregateField` computes an aggregate operation `op` over the values of a `field` for each
+/// group, and stores the result in an `output` field. The field may be omitted for `count`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct AggregateField {
+    pub(crate) op: AggregateOp,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) field: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "as"))]
+    pub(crate) output: String,
+}
+
+impl AggregateField {
+    pub fn new(op: AggregateOp, field: &str, output: &str) -> AggregateField {
+        AggregateField {
+            op,
+            field: Some(field.to_string()),
+            output: output.to_string(),
+        }
+    }
+
+    /// Count the values of each group into an `output` field.
+    pub fn count(output: &str) -> AggregateField {
+        AggregateField {
+            op: AggregateOp::Count,
+            field: None,
+            output: output.to_string(),
+        }
+    }
+
+    /// Check that the field of the operation is present in a certain `schema` with a type the
+    /// operation supports, and return the type of the operation's result.
+    pub(crate) fn check(&self, schema: &Schema) -> Result<DataType, Error> {
+        let Some(field) = &self.field else {
+            return match self.op {
+                AggregateOp::Count => Ok(DataType::Number),
+                _ => Err(Error::Pipe(PipeError::MissingField(self.output.clone()))),
+            };
+        };
+        let data_type = find(schema, field)?;
+
+        let expect = |expected: &[DataType]| {
+            if data_type == DataType::Null || expected.contains(&data_type) {
+                Ok(())
+            } else {
+                Err(ExpressionError::Type(TypeError::UnexpectedType(
+                    expected[0],
+                    data_type,
+                )))
+            }
+        };
+
+        match self.op {
+            AggregateOp::Count | AggregateOp::Distinct => Ok(DataType::Number),
+            AggregateOp::Min | AggregateOp::Max => {
+                expect(&[DataType::Number, DataType::Date])?;
+                Ok(data_type)
+            }
+            AggregateOp::Argmin | AggregateOp::Argmax => {
+                expect(&[DataType::Number, DataType::Date])?;
+                Ok(DataType::Object)
+            }
+            _ => {
+                expect(&[DataType::Number])?;
+                Ok(DataType::Number)
+            }
+        }
+    }
+}
+
+/// `AggregateOp` lists the operations that summarize the values of a field in a group.
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum AggregateOp {
+    /// Amount of values in the group.
+    Count,
+    /// Sum of the numbers.
+    Sum,
+    /// Arithmetic mean of the numbers.
+    Mean,
+    /// Median of the numbers.
+    Median,
+    /// Minimum number or date.
+    Min,
+    /// Maximum number or date.
+    Max,
+    /// Sample variance of the numbers.
+    Variance,
+    /// Sample standard deviation of the numbers.
+    Stdev,
+    /// Amount of distinct values.
+    Distinct,
+    /// Lower quartile of the numbers.
+    Q1,
+    /// Upper quartile of the numbers.
+    Q3,
+    /// Data value with the minimum number or date.
+    Argmin,
+    /// Data value with the maximum number or date.
+    Argmax,
+}
+
+impl AggregateOp {
+    pub fn from_string(string: &str) -> Option<AggregateOp> {
+        match string {
+            "count" => Some(AggregateOp::Count),
+            "sum" => Some(AggregateOp::Sum),
+            "mean" => Some(AggregateOp::Mean),
+            "median" => Some(AggregateOp::Median),
+            "min" => Some(AggregateOp::Min),
+            "max" => Some(AggregateOp::Max),
+            "variance" => Some(AggregateOp::Variance),
+            "stdev" => Some(AggregateOp::Stdev),
+            "distinct" => Some(AggregateOp::Distinct),
+            "q1" => Some(AggregateOp::Q1),
+            "q3" => Some(AggregateOp::Q3),
+            "argmin" => Some(AggregateOp::Argmin),
+            "argmax" => Some(AggregateOp::Argmax),
+            _ => None,
+        }
+    }
+}
+
+/// Find the type of a `field`, which is expected to be present in a certain `schema`.
+pub(crate) fn find(schema: &Schema, field: &str) -> Result<DataType, Error> {
+    schema.find(field).ok_or_else(|| {
+        Error::from(ExpressionError::Type(TypeError::UnknownVariable(
+            field.to_string(),
+        )))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::aggregate::{AggregateField, AggregateOp, AggregatePipe};
+
+    #[test]
+    fn checks_aggregate() {
+        let schema = Schema::from_pairs(vec![
+            ("a", DataType::Text),
+            ("b", DataType::Number),
+            ("c", DataType::Date),
+        ]);
+
+        let pipe = AggregatePipe::new(
+            vec!["a"],
+            vec![
+                AggregateField::count("count"),
+                AggregateField::new(AggregateOp::Mean, "b", "mean_b"),
+                AggregateField::new(AggregateOp::Max, "c", "max_c"),
+                AggregateField::new(AggregateOp::Argmin, "b", "argmin_b"),
+            ],
+        );
+        let mut result = schema.clone();
+        pipe.check(&mut result).unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![
+                ("a", DataType::Text),
+                ("count", DataType::Number),
+                ("mean_b", DataType::Number),
+                ("max_c", DataType::Date),
+                ("argmin_b", DataType::Object),
+            ])
+        );
+
+        let pipe = AggregatePipe::new(
+            Vec::new(),
+            vec![AggregateField::new(AggregateOp::Sum, "a", "sum_a")],
+        );
+        assert_eq!(
+            pipe.check(&mut schema.clone()).unwrap_err().to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected number, found text"
+        );
+
+        let pipe = AggregatePipe::new(
+            vec!["d"],
+            vec![AggregateField::new(AggregateOp::Sum, "b", "sum_b")],
+        );
+        assert_eq!(
+            pipe.check(&mut schema.clone()).unwrap_err().to_string(),
+            "PipeError::Expression: TypeError::UnknownVariable: d"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::aggregate::{AggregateField, AggregateOp, AggregatePipe};
+
+    #[test]
+    fn deserialize_aggregate() {
+        let aggregate = serde_json::from_str::<AggregatePipe>(
+            r#"{
+      "groupby": ["a", "b"],
+      "ops": [
+        { "op": "count", "as": "count" },
+        { "op": "q1", "field": "c", "as": "q1_c" },
+        { "op": "argmax", "field": "c", "as": "argmax_c" }
+      ]
+     }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            aggregate,
+            AggregatePipe::new(
+                vec!["a", "b"],
+                vec![
+                    AggregateField::count("count"),
+                    AggregateField::new(AggregateOp::Q1, "c", "q1_c"),
+                    AggregateField::new(AggregateOp::Argmax, "c", "argmax_c"),
+                ]
+            )
+        );
+    }
+}
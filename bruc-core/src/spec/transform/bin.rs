@@ -0,0 +1,317 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::aggregate::find;
+use crate::spec::transform::error::Error;
+
+/// `BinPipe` divides the numbers of a `field` into bins of equal size, e.g. for histograms. The
+/// bins cover the `extent` of the numbers, which is read from the data values if not given, in at
+/// most `maxbins` bins, unless an exact `step` is given. Steps that aren't finite positive
+/// numbers are ignored. With `nice`, the bins start and end at
+/// multiples of the step. The start and end of each value's bin are added as the `output`
+/// fields, `bin0` and `bin1` by default.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct BinPipe {
+    pub(crate) field: String,
+    #[cfg_attr(feature = "serde", serde(default = "BinPipe::default_maxbins"))]
+    pub(crate) maxbins: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) step: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) extent: Option<[f64; 2]>,
+    #[cfg_attr(feature = "serde", serde(default = "BinPipe::default_nice"))]
+    pub(crate) nice: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "as", default = "BinPipe::default_output")
+    )]
+    pub(crate) output: [String; 2],
+}
+
+impl BinPipe {
+    pub fn new(field: &str) -> BinPipe {
+        BinPipe {
+            field: field.to_string(),
+            maxbins: BinPipe::default_maxbins(),
+            step: None,
+            extent: None,
+            nice: BinPipe::default_nice(),
+            output: BinPipe::default_output(),
+        }
+    }
+
+    pub fn with_maxbins(mut self, maxbins: usize) -> Self {
+        self.maxbins = maxbins;
+        self
+    }
+
+    /// Use bins of an exact size, instead of deriving it from the maximum amount of bins. The
+    /// size is ignored if it isn't a finite positive number.
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Cover a certain extent, instead of the extent of the data values.
+    pub fn with_extent(mut self, min: f64, max: f64) -> Self {
+        self.extent = Some([min, max]);
+        self
+    }
+
+    pub fn with_nice(mut self, nice: bool) -> Self {
+        self.nice = nice;
+        self
+    }
+
+    /// Store the start and the end of each value's bin in certain fields.
+    pub fn with_output(mut self, start: &str, end: &str) -> Self {
+        self.output = [start.to_string(), end.to_string()];
+        self
+    }
+
+    fn default_maxbins() -> usize {
+        20
+    }
+
+    fn default_nice() -> bool {
+        true
+    }
+
+    fn default_output() -> [String; 2] {
+        ["bin0".to_string(), "bin1".to_string()]
+    }
+
+    /// Compute the bins covering a certain `extent`, as their start, end and size. The size is
+    /// the largest one of 1, 2 or 5 times a power of ten, which doesn't exceed the maximum
+    /// amount of bins.
+    pub(crate) fn bins(&self, extent: [f64; 2]) -> Bins {
+        let [mut start, mut stop] = extent;
+        let span = stop - start;
+
+        let step = match self.step.filter(|step| step.is_finite() && *step > 0.0) {
+            Some(step) => step,
+            None if span <= 0.0 => 1.0,
+            None => {
+                let maxbins = self.maxbins.max(1) as f64;
+                let level = maxbins.log10().ceil();
+                let mut step = 10f64.powf(span.log10().round() - level);
+                while (span / step).ceil() > maxbins {
+                    step *= 10.0;
+                }
+                for divisor in [5.0, 2.0] {
+                    if span / (step / divisor) <= maxbins {
+                        step /= divisor;
+                    }
+                }
+                step
+            }
+        };
+
+        if self.nice {
+            start = (start / step).floor() * step;
+            stop = (stop / step).ceil() * step;
+        }
+        if stop <= start {
+            stop = start + step;
+        }
+
+        Bins { start, stop, step }
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        BTreeSet::from([self.field.as_str()])
+    }
+
+    /// Check that the binned field is a number of a certain `schema`. The output fields are added
+    /// to the schema.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let data_type = find(schema, &self.field)?;
+        if !matches!(data_type, DataType::Number | DataType::Null) {
+            return Err(Error::from(ExpressionError::Type(
+                TypeError::UnexpectedType(DataType::Number, data_type),
+            )));
+        }
+
+        for output in &self.output {
+            schema.insert(output, DataType::Number);
+        }
+
+        Ok(())
+    }
+}
+
+/// `Bins` of a certain size, `step`, covering the numbers from `start` to `stop`.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub(crate) struct Bins {
+    pub(crate) start: f64,
+    pub(crate) stop: f64,
+    pub(crate) step: f64,
+}
+
+impl Bins {
+    /// Find the start and end of the bin of a certain `number`. Numbers outside of the bins
+    /// don't have a bin, and the last bin includes its end.
+    pub(crate) fn find(&self, number: f64) -> Option<(f64, f64)> {
+        if number < self.start || number > self.stop {
+            return None;
+        }
+
+        let last = ((self.stop - self.start) / self.step).ceil() - 1.0;
+        let index = ((number - self.start) / self.step).floor().min(last);
+
+        let start = self.start + index * self.step;
+        Some((start, (start + self.step).min(self.stop)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::bin::{BinPipe, Bins};
+
+    #[test]
+    fn computes_bins() {
+        assert_eq!(
+            BinPipe::new("a").bins([0.0, 100.0]),
+            Bins {
+                start: 0.0,
+                stop: 100.0,
+                step: 5.0
+            }
+        );
+        assert_eq!(
+            BinPipe::new("a").with_maxbins(10).bins([1.3, 48.2]),
+            Bins {
+                start: 0.0,
+                stop: 50.0,
+                step: 5.0
+            }
+        );
+        assert_eq!(
+            BinPipe::new("a")
+                .with_maxbins(10)
+                .with_nice(false)
+                .bins([1.0, 48.0]),
+            Bins {
+                start: 1.0,
+                stop: 48.0,
+                step: 5.0
+            }
+        );
+        assert_eq!(
+            BinPipe::new("a").with_step(3.0).bins([2.0, 10.0]),
+            Bins {
+                start: 0.0,
+                stop: 12.0,
+                step: 3.0
+            }
+        );
+        assert_eq!(
+            BinPipe::new("a").bins([4.0, 4.0]),
+            Bins {
+                start: 4.0,
+                stop: 5.0,
+                step: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_invalid_steps() {
+        for step in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            let bins = BinPipe::new("a")
+                .with_maxbins(10)
+                .with_step(step)
+                .bins([1.3, 48.2]);
+            assert_eq!(
+                bins,
+                Bins {
+                    start: 0.0,
+                    stop: 50.0,
+                    step: 5.0
+                }
+            );
+            assert_eq!(bins.find(12.0), Some((10.0, 15.0)));
+        }
+    }
+
+    #[test]
+    fn finds_bins() {
+        let bins = Bins {
+            start: 1.0,
+            stop: 48.0,
+            step: 5.0,
+        };
+
+        assert_eq!(bins.find(1.0), Some((1.0, 6.0)));
+        assert_eq!(bins.find(6.0), Some((6.0, 11.0)));
+        assert_eq!(bins.find(45.5), Some((41.0, 46.0)));
+        assert_eq!(bins.find(47.0), Some((46.0, 48.0)));
+        assert_eq!(bins.find(48.0), Some((46.0, 48.0)));
+        assert_eq!(bins.find(0.5), None);
+        assert_eq!(bins.find(48.5), None);
+    }
+
+    #[test]
+    fn checks_bin() {
+        let schema = Schema::from_pairs(vec![("a", DataType::Text), ("b", DataType::Number)]);
+
+        let mut result = schema.clone();
+        BinPipe::new("b").check(&mut result).unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![
+                ("a", DataType::Text),
+                ("b", DataType::Number),
+                ("bin0", DataType::Number),
+                ("bin1", DataType::Number),
+            ])
+        );
+
+        assert_eq!(
+            BinPipe::new("a")
+                .check(&mut schema.clone())
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected number, found text"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::bin::BinPipe;
+
+    #[test]
+    fn deserialize_bin() {
+        let bin = serde_json::from_str::<BinPipe>(
+            r#"{
+      "field": "amount",
+      "maxbins": 10,
+      "step": 2.5,
+      "extent": [0, 100],
+      "nice": false,
+      "as": ["start", "end"]
+     }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            bin,
+            BinPipe::new("amount")
+                .with_maxbins(10)
+                .with_step(2.5)
+                .with_extent(0.0, 100.0)
+                .with_nice(false)
+                .with_output("start", "end")
+        );
+
+        let bin = serde_json::from_str::<BinPipe>(r#"{ "field": "amount" }"#).unwrap();
+        assert_eq!(bin, BinPipe::new("amount"));
+    }
+}
@@ -3,11 +3,35 @@ use std::{error, fmt};
 #[derive(Debug)]
 pub enum Error {
     Pipe(PipeError),
+    Format(FormatError),
 }
 
 #[derive(Debug)]
 pub enum PipeError {
     Expression(bruc_expression::error::Error),
+    /// An aggregate operation, of a certain output, requires a field to aggregate.
+    MissingField(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FormatError {
+    /// Values are given as text, but no format is declared to read them.
+    MissingFormat,
+    /// Text isn't a valid JSON array of objects, for a certain reason.
+    InvalidJson(String),
+    /// A quoted field, opened in a certain line, isn't closed before the end of the text.
+    UnterminatedQuote(usize),
+    /// A quoted field is preceded by whitespace, or followed by characters other than a
+    /// delimiter or a line break, in a certain line.
+    InvalidQuote(usize),
+    /// The header declares a certain field more than once.
+    DuplicateField(String),
+    /// A row, starting in a certain line, has a different amount of fields than the header.
+    FieldCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
 }
 
 impl fmt::Display for Error {
@@ -15,6 +39,30 @@ impl fmt::Display for Error {
         match self {
             Error::Pipe(error) => match error {
                 PipeError::Expression(error) => write!(f, "PipeError::Expression: {error}"),
+                PipeError::MissingField(output) => write!(f, "PipeError::MissingField: {output}"),
+            },
+            Error::Format(error) => match error {
+                FormatError::MissingFormat => write!(f, "FormatError::MissingFormat"),
+                FormatError::InvalidJson(reason) => {
+                    write!(f, "FormatError::InvalidJson: {reason}")
+                }
+                FormatError::UnterminatedQuote(line) => {
+                    write!(f, "FormatError::UnterminatedQuote: line {line}")
+                }
+                FormatError::InvalidQuote(line) => {
+                    write!(f, "FormatError::InvalidQuote: line {line}")
+                }
+                FormatError::DuplicateField(field) => {
+                    write!(f, "FormatError::DuplicateField: {field}")
+                }
+                FormatError::FieldCount {
+                    line,
+                    expected,
+                    found,
+                } => write!(
+                    f,
+                    "FormatError::FieldCount: expected {expected} fields, found {found} in line {line}"
+                ),
             },
         }
     }
@@ -27,3 +75,9 @@ impl From<bruc_expression::error::Error> for Error {
         Error::Pipe(PipeError::Expression(error))
     }
 }
+
+impl From<FormatError> for Error {
+    fn from(error: FormatError) -> Self {
+        Error::Format(error)
+    }
+}
@@ -1,4 +1,9 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::compile::Program;
+use bruc_expression::error::{Error as ExpressionError, TypeError};
 use bruc_expression::expr::{Expression, Interpretable};
+use bruc_expression::types::{DataType, Schema, Typed};
 use bruc_expression::PredicateParser;
 
 use crate::data::DataValue;
@@ -17,21 +22,51 @@ impl FilterPipe {
         Ok(FilterPipe { predicate })
     }
 
+    /// Check if a data value passes the filter. Values for which the predicate can't be
+    /// interpreted, e.g. due to null or missing fields, are filtered out.
     #[inline]
     pub fn apply(&self, item: &DataValue) -> bool {
-        self.predicate.interpret(item).unwrap()
+        self.predicate.interpret(item).unwrap_or(false)
+    }
+
+    /// Fields of the data values read by the predicate.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.predicate.expression.variables()
+    }
+
+    /// Check if the predicate results in the same item for the same data value.
+    pub fn is_deterministic(&self) -> bool {
+        self.predicate.expression.is_deterministic()
+    }
+
+    /// Check that the predicate evaluates to a boolean for data of a certain `schema`.
+    pub fn check(&self, schema: &Schema) -> Result<(), Error> {
+        let data_type = self.predicate.expression.check(schema)?;
+
+        if data_type != DataType::Bool {
+            return Err(Error::from(ExpressionError::Type(
+                TypeError::UnexpectedType(DataType::Bool, data_type),
+            )));
+        }
+
+        Ok(())
     }
 }
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct FilterPredicate {
     expression: Expression,
+    program: Program,
 }
 
 impl FilterPredicate {
     pub fn new(input: &str) -> Result<FilterPredicate, Error> {
         let expression = PredicateParser::new(input).parse()?;
-        Ok(FilterPredicate { expression })
+        let program = Program::compile(&expression)?;
+        Ok(FilterPredicate {
+            expression,
+            program,
+        })
     }
 }
 
@@ -39,7 +74,7 @@ impl Predicate for FilterPredicate {
     type Value = bool;
 
     fn interpret(&self, vars: &DataValue) -> Result<Self::Value, Error> {
-        self.expression.interpret(vars).map_err(Into::into)
+        self.program.interpret(vars).map_err(Into::into)
     }
 }
 
@@ -91,4 +126,13 @@ mod serde_tests {
         let filter = serde_json::from_str::<FilterPipe>(r#"{ "fn": "a > 2.0" }"#).unwrap();
         assert_eq!(filter.predicate, FilterPredicate::new("a > 2.0").unwrap());
     }
+
+    #[test]
+    fn doesnt_deserialize_invalid_filter() {
+        let error = serde_json::from_str::<FilterPipe>(r#"{ "fn": "a >> 2" }"#).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "PipeError::Expression: ParseError::BindingPowerMissing at 1:4: unexpected `>`, \
+             expected an expression\na >> 2\n   ^"
+        ));
+    }
 }
@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::aggregate::find;
+use crate::spec::transform::error::Error;
+
+/// `FoldPipe` reshapes wide data values into long ones, by splitting each data value into one
+/// value per folded field. Each resulting value keeps the fields that aren't folded, and stores
+/// the name and the item of its folded field in the `output` fields, `key` and `value` by
+/// default.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct FoldPipe {
+    pub(crate) fields: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "as", default = "FoldPipe::default_output")
+    )]
+    pub(crate) output: [String; 2],
+}
+
+impl FoldPipe {
+    pub fn new(fields: Vec<&str>) -> FoldPipe {
+        FoldPipe {
+            fields: fields.into_iter().map(str::to_string).collect(),
+            output: FoldPipe::default_output(),
+        }
+    }
+
+    /// Store the name and the item of each folded field in certain fields.
+    pub fn with_output(mut self, key: &str, value: &str) -> Self {
+        self.output = [key.to_string(), value.to_string()];
+        self
+    }
+
+    fn default_output() -> [String; 2] {
+        ["key".to_string(), "value".to_string()]
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.fields.iter().map(String::as_str).collect()
+    }
+
+    /// Check that the folded fields are present in a certain `schema` with the same type, and
+    /// replace them in the schema with the output fields.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let mut value_type = DataType::Null;
+        for field in &self.fields {
+            let data_type = find(schema, field)?;
+
+            match value_type {
+                DataType::Null => value_type = data_type,
+                _ if data_type == DataType::Null || data_type == value_type => {}
+                _ => {
+                    return Err(Error::from(ExpressionError::Type(
+                        TypeError::UnexpectedType(value_type, data_type),
+                    )))
+                }
+            }
+        }
+
+        let mut result = Schema::new();
+        for field in schema.fields() {
+            if !self.fields.iter().any(|folded| folded == field) {
+                result.insert(field, find(schema, field)?);
+            }
+        }
+
+        let [key, value] = &self.output;
+        result.insert(key, DataType::Text);
+        result.insert(value, value_type);
+        *schema = result;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::fold::FoldPipe;
+
+    #[test]
+    fn checks_fold() {
+        let schema = Schema::from_pairs(vec![
+            ("date", DataType::Date),
+            ("cpu", DataType::Number),
+            ("mem", DataType::Number),
+            ("host", DataType::Text),
+        ]);
+
+        let mut result = schema.clone();
+        FoldPipe::new(vec!["cpu", "mem"])
+            .check(&mut result)
+            .unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![
+                ("date", DataType::Date),
+                ("host", DataType::Text),
+                ("key", DataType::Text),
+                ("value", DataType::Number),
+            ])
+        );
+
+        assert_eq!(
+            FoldPipe::new(vec!["cpu", "host"])
+                .check(&mut schema.clone())
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected number, found text"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::fold::FoldPipe;
+
+    #[test]
+    fn deserialize_fold() {
+        let fold = serde_json::from_str::<FoldPipe>(
+            r#"{
+      "fields": ["cpu", "mem"],
+      "as": ["metric", "amount"]
+     }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            fold,
+            FoldPipe::new(vec!["cpu", "mem"]).with_output("metric", "amount")
+        );
+
+        let fold = serde_json::from_str::<FoldPipe>(r#"{ "fields": ["cpu"] }"#).unwrap();
+        assert_eq!(fold, FoldPipe::new(vec!["cpu"]));
+    }
+}
@@ -1,3 +1,10 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::error::Error;
+
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct GroupPipe {
@@ -14,6 +21,23 @@ impl GroupPipe {
             output: output.to_string(),
         }
     }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        BTreeSet::from([self.by.as_str()])
+    }
+
+    /// Check that the `by` field is present in a certain `schema`, and replace the schema with
+    /// the fields of the resulting groups.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let by_type = schema
+            .find(&self.by)
+            .ok_or_else(|| ExpressionError::Type(TypeError::UnknownVariable(self.by.clone())))?;
+
+        *schema = Schema::from_pairs(vec![(&self.by, by_type), (&self.output, DataType::Number)]);
+
+        Ok(())
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -0,0 +1,273 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::aggregate::find;
+use crate::spec::transform::error::Error;
+
+/// `LoessPipe` fits a smooth curve through the numbers of the `x` and `y` fields of the values
+/// of each group, with locally weighted linear regressions. Each regression considers the
+/// nearest values around an `x` number, a `bandwidth` fraction of the group. Each group is
+/// replaced with the fitted points at its distinct `x` numbers, which are stored in the `output`
+/// fields, `x` and `y` by default.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct LoessPipe {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) groupby: Vec<String>,
+    pub(crate) x: String,
+    pub(crate) y: String,
+    #[cfg_attr(feature = "serde", serde(default = "LoessPipe::default_bandwidth"))]
+    pub(crate) bandwidth: f64,
+    #[cfg_attr(feature = "serde", serde(rename = "as", default))]
+    pub(crate) output: Option<[String; 2]>,
+}
+
+impl LoessPipe {
+    pub fn new(x: &str, y: &str) -> LoessPipe {
+        LoessPipe {
+            groupby: Vec::new(),
+            x: x.to_string(),
+            y: y.to_string(),
+            bandwidth: LoessPipe::default_bandwidth(),
+            output: None,
+        }
+    }
+
+    /// Fit a separate curve for the groups of certain fields.
+    pub fn with_groupby(mut self, groupby: Vec<&str>) -> Self {
+        self.groupby = groupby.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    pub fn with_bandwidth(mut self, bandwidth: f64) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    /// Store the fitted points in certain fields.
+    pub fn with_output(mut self, x: &str, y: &str) -> Self {
+        self.output = Some([x.to_string(), y.to_string()]);
+        self
+    }
+
+    fn default_bandwidth() -> f64 {
+        0.3
+    }
+
+    /// Fields storing the fitted points, which are named after the `x` and `y` fields if not
+    /// given.
+    pub(crate) fn outputs(&self) -> [&str; 2] {
+        match &self.output {
+            Some([x, y]) => [x, y],
+            None => [&self.x, &self.y],
+        }
+    }
+
+    /// Fit the curve to certain `points`, and return the fitted points at their distinct `x`
+    /// numbers, in ascending order. Each regression weighs the nearest points with the tricube
+    /// function of their distance, relative to the farthest one.
+    pub(crate) fn fit(&self, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let mut points: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+        points.sort_by(|left, right| left.0.total_cmp(&right.0));
+
+        let size = points.len();
+        if size == 0 {
+            return Vec::new();
+        }
+        let span = ((self.bandwidth * size as f64).ceil() as usize).clamp(2.min(size), size);
+
+        let mut result: Vec<(f64, f64)> = Vec::new();
+        let mut start = 0;
+        for &(x, _) in &points {
+            if result.last().is_some_and(|(last, _)| *last == x) {
+                continue;
+            }
+
+            // Slide the window of the nearest points along the sorted points
+            while start + span < size && x - points[start].0 > points[start + span].0 - x {
+                start += 1;
+            }
+            let window = &points[start..start + span];
+
+            let farthest = (x - window[0].0).max(window[span - 1].0 - x);
+            let weight = |other: f64| {
+                if farthest > 0.0 {
+                    (1.0 - ((other - x).abs() / farthest).powi(3)).powi(3)
+                } else {
+                    1.0
+                }
+            };
+
+            result.push((x, weighted_linear(window, weight, x)));
+        }
+
+        result
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.groupby
+            .iter()
+            .chain([&self.x, &self.y])
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Check that the fields read by the pipe are present in a certain `schema`, and that the
+    /// `x` and `y` fields are numbers. The schema is replaced with the `groupby` fields and the
+    /// fields of the fitted points.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let mut result = Schema::new();
+
+        for field in &self.groupby {
+            result.insert(field, find(schema, field)?);
+        }
+
+        for field in [&self.x, &self.y] {
+            let data_type = find(schema, field)?;
+            if !matches!(data_type, DataType::Number | DataType::Null) {
+                return Err(Error::from(ExpressionError::Type(
+                    TypeError::UnexpectedType(DataType::Number, data_type),
+                )));
+            }
+        }
+
+        for output in self.outputs() {
+            result.insert(output, DataType::Number);
+        }
+
+        *schema = result;
+
+        Ok(())
+    }
+}
+
+/// Predict the `y` number at a certain `x` number, with a linear regression of certain `points`
+/// weighted by a `weight` function of their `x` numbers. Falls back to the weighted mean of the
+/// `y` numbers, if the weighted `x` numbers don't vary.
+fn weighted_linear(points: &[(f64, f64)], weight: impl Fn(f64) -> f64, x: f64) -> f64 {
+    let (mut total, mut mean_x, mut mean_y) = (0.0, 0.0, 0.0);
+    for (px, py) in points {
+        let w = weight(*px);
+        total += w;
+        mean_x += w * px;
+        mean_y += w * py;
+    }
+    if total <= 0.0 {
+        return points.iter().map(|(_, y)| y).sum::<f64>() / points.len() as f64;
+    }
+    mean_x /= total;
+    mean_y /= total;
+
+    let (mut covariance, mut variance) = (0.0, 0.0);
+    for (px, py) in points {
+        let w = weight(*px);
+        covariance += w * (px - mean_x) * (py - mean_y);
+        variance += w * (px - mean_x).powi(2);
+    }
+
+    if variance.abs() < f64::EPSILON {
+        return mean_y;
+    }
+
+    mean_y + covariance / variance * (x - mean_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::loess::LoessPipe;
+
+    #[test]
+    fn fits_loess() {
+        // Local linear regressions reproduce linear points exactly
+        let points: Vec<(f64, f64)> = [3.0, 0.0, 5.0, 1.0, 2.0, 4.0, 2.0]
+            .into_iter()
+            .map(|x| (x, 2.0 * x + 1.0))
+            .collect();
+
+        let fitted = LoessPipe::new("x", "y").with_bandwidth(0.5).fit(&points);
+        assert_eq!(
+            fitted.iter().map(|(x, _)| *x).collect::<Vec<_>>(),
+            vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]
+        );
+        for (x, y) in fitted {
+            assert!((y - (2.0 * x + 1.0)).abs() < 1e-9, "{y} at {x}");
+        }
+
+        // An outlier is smoothed by its neighbours
+        let points = [(0.0, 0.0), (1.0, 0.0), (2.0, 10.0), (3.0, 0.0), (4.0, 0.0)];
+        let fitted = LoessPipe::new("x", "y").with_bandwidth(1.0).fit(&points);
+        assert!(fitted[2].1 < 10.0 && fitted[2].1 > 0.0);
+
+        assert_eq!(
+            LoessPipe::new("x", "y").fit(&[(1.0, 2.0)]),
+            vec![(1.0, 2.0)]
+        );
+        assert_eq!(LoessPipe::new("x", "y").fit(&[]), Vec::new());
+    }
+
+    #[test]
+    fn checks_loess() {
+        let schema = Schema::from_pairs(vec![
+            ("a", DataType::Text),
+            ("x", DataType::Number),
+            ("y", DataType::Number),
+        ]);
+
+        let mut result = schema.clone();
+        LoessPipe::new("x", "y")
+            .with_output("u", "v")
+            .check(&mut result)
+            .unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![("u", DataType::Number), ("v", DataType::Number)])
+        );
+
+        assert_eq!(
+            LoessPipe::new("a", "y")
+                .check(&mut schema.clone())
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected number, found text"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::loess::LoessPipe;
+
+    #[test]
+    fn deserialize_loess() {
+        let loess = serde_json::from_str::<LoessPipe>(
+            r#"{
+      "groupby": ["series"],
+      "x": "a",
+      "y": "b",
+      "bandwidth": 0.5,
+      "as": ["u", "v"]
+     }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            loess,
+            LoessPipe::new("a", "b")
+                .with_groupby(vec!["series"])
+                .with_bandwidth(0.5)
+                .with_output("u", "v")
+        );
+
+        let loess = serde_json::from_str::<LoessPipe>(r#"{ "x": "a", "y": "b" }"#).unwrap();
+        assert_eq!(loess, LoessPipe::new("a", "b"));
+    }
+}
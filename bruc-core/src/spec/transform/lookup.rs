@@ -0,0 +1,191 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::data::DataItem;
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::aggregate::find;
+use crate::spec::transform::error::Error;
+
+/// `LookupPipe` joins each data value with the first value of another data entry, `from`, whose
+/// `key` field matches the value's `field`. The `values` of the matching value are copied into
+/// the data value, or their defaults if there is no match.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct LookupPipe {
+    pub(crate) from: String,
+    pub(crate) key: String,
+    pub(crate) field: String,
+    pub(crate) values: Vec<LookupField>,
+}
+
+impl LookupPipe {
+    pub fn new(from: &str, key: &str, field: &str, values: Vec<LookupField>) -> LookupPipe {
+        LookupPipe {
+            from: from.to_string(),
+            key: key.to_string(),
+            field: field.to_string(),
+            values,
+        }
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        BTreeSet::from([self.field.as_str()])
+    }
+
+    /// Fields of the values of the `from` entry read by the pipe.
+    pub fn from_fields(&self) -> BTreeSet<&str> {
+        self.values
+            .iter()
+            .map(|value| value.field.as_str())
+            .chain([self.key.as_str()])
+            .collect()
+    }
+
+    /// Check that the `field` is present in a certain `schema`, and add the copied fields to the
+    /// schema. The types of the copied fields are the types of their defaults, since the schema
+    /// of the `from` entry isn't known.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        find(schema, &self.field)?;
+
+        for value in &self.values {
+            schema.insert(&value.output, DataType::from(&value.default));
+        }
+
+        Ok(())
+    }
+}
+
+/// `LookupField` copies a `field` of the matching value into an `output` field, which is set to a
+/// `default` item if there is no matching value.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "LookupFieldDefinition"))]
+pub struct LookupField {
+    pub(crate) field: String,
+    pub(crate) output: String,
+    pub(crate) default: DataItem,
+}
+
+impl LookupField {
+    pub fn new(field: &str) -> LookupField {
+        LookupField {
+            field: field.to_string(),
+            output: field.to_string(),
+            default: DataItem::Null,
+        }
+    }
+
+    /// Copy the field into an `output` field with another name.
+    pub fn with_output(mut self, output: &str) -> Self {
+        self.output = output.to_string();
+        self
+    }
+
+    /// Set the output field to a `default` item if there is no matching value, instead of null.
+    pub fn with_default(mut self, default: DataItem) -> Self {
+        self.default = default;
+        self
+    }
+}
+
+/// Definition of a `LookupField`, whose output field is named after the copied field if omitted.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LookupFieldDefinition {
+    field: String,
+    #[serde(rename = "as")]
+    output: Option<String>,
+    #[serde(default)]
+    default: Option<DataItem>,
+}
+
+#[cfg(feature = "serde")]
+impl From<LookupFieldDefinition> for LookupField {
+    fn from(definition: LookupFieldDefinition) -> Self {
+        LookupField {
+            output: definition
+                .output
+                .unwrap_or_else(|| definition.field.clone()),
+            field: definition.field,
+            default: definition.default.unwrap_or(DataItem::Null),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::lookup::{LookupField, LookupPipe};
+
+    #[test]
+    fn checks_lookup() {
+        let schema = Schema::from_pairs(vec![("id", DataType::Number), ("a", DataType::Number)]);
+
+        let mut result = schema.clone();
+        LookupPipe::new(
+            "dimensions",
+            "key",
+            "id",
+            vec![
+                LookupField::new("name").with_default("unknown".into()),
+                LookupField::new("color").with_output("fill"),
+            ],
+        )
+        .check(&mut result)
+        .unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![
+                ("id", DataType::Number),
+                ("a", DataType::Number),
+                ("name", DataType::Text),
+                ("fill", DataType::Null),
+            ])
+        );
+
+        assert_eq!(
+            LookupPipe::new("dimensions", "key", "b", vec![LookupField::new("name")])
+                .check(&mut schema.clone())
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnknownVariable: b"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::lookup::{LookupField, LookupPipe};
+
+    #[test]
+    fn deserialize_lookup() {
+        let lookup = serde_json::from_str::<LookupPipe>(
+            r#"{
+      "from": "categories",
+      "key": "id",
+      "field": "category",
+      "values": [
+        { "field": "name", "default": "unknown" },
+        { "field": "color", "as": "fill" }
+      ]
+     }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            lookup,
+            LookupPipe::new(
+                "categories",
+                "id",
+                "category",
+                vec![
+                    LookupField::new("name").with_default("unknown".into()),
+                    LookupField::new("color").with_output("fill"),
+                ]
+            )
+        );
+    }
+}
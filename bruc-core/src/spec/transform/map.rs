@@ -1,7 +1,12 @@
+use std::collections::BTreeSet;
+
 use crate::data::DataValue;
 use crate::spec::transform::error::Error;
 use crate::spec::transform::pipe::Predicate;
+use bruc_expression::compile::Program;
+use bruc_expression::data::DataItem;
 use bruc_expression::expr::{Expression, Interpretable};
+use bruc_expression::types::{Schema, Typed};
 use bruc_expression::PredicateParser;
 
 #[derive(PartialEq, Debug, Clone)]
@@ -20,30 +25,57 @@ impl MapPipe {
         })
     }
 
+    /// Apply the predicate to a data value and write the result into the `output` field. The
+    /// output is null, if the predicate can't be interpreted for the value, e.g. due to null or
+    /// missing fields.
     #[inline]
     pub fn apply(&self, item: &mut DataValue) {
-        let var = self.predicate.interpret(item).unwrap();
-        item.insert(&self.output, var.into());
+        let var = self.predicate.interpret(item).unwrap_or(DataItem::Null);
+        item.insert(&self.output, var);
+    }
+
+    /// Fields of the data values read by the predicate.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.predicate.expression.variables()
+    }
+
+    /// Check if the predicate results in the same item for the same data value.
+    pub fn is_deterministic(&self) -> bool {
+        self.predicate.expression.is_deterministic()
+    }
+
+    /// Check the predicate for data of a certain `schema`, and add the output field with the
+    /// predicate's resulting type to it.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let data_type = self.predicate.expression.check(schema)?;
+        schema.insert(&self.output, data_type);
+
+        Ok(())
     }
 }
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct MapPredicate {
     expression: Expression,
+    program: Program,
 }
 
 impl MapPredicate {
     pub fn new(input: &str) -> Result<MapPredicate, Error> {
         let expression = PredicateParser::new(input).parse()?;
-        Ok(MapPredicate { expression })
+        let program = Program::compile(&expression)?;
+        Ok(MapPredicate {
+            expression,
+            program,
+        })
     }
 }
 
 impl Predicate for MapPredicate {
-    type Value = f32;
+    type Value = DataItem;
 
     fn interpret(&self, vars: &DataValue) -> Result<Self::Value, Error> {
-        self.expression.interpret(vars).map_err(Into::into)
+        self.program.interpret(vars).map_err(Into::into)
     }
 }
 
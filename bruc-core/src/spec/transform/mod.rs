@@ -1,9 +1,19 @@
 use crate::spec::transform::pipe::Pipe;
 
+pub mod aggregate;
+pub mod bin;
 pub mod error;
 pub mod filter;
+pub mod fold;
 pub mod group;
+pub mod loess;
+pub mod lookup;
 pub mod map;
 pub mod pipe;
+pub mod pivot;
+pub mod regression;
+pub mod sort;
+pub mod stack;
+pub mod window;
 
 pub type Transform = Vec<Pipe>;
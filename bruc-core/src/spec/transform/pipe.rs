@@ -1,8 +1,23 @@
+use std::collections::BTreeSet;
+
 use crate::data::DataValue;
+use crate::spec::transform::aggregate::AggregatePipe;
+use crate::spec::transform::bin::BinPipe;
 use crate::spec::transform::error::Error;
 use crate::spec::transform::filter::FilterPipe;
+use crate::spec::transform::fold::FoldPipe;
 use crate::spec::transform::group::GroupPipe;
+use crate::spec::transform::loess::LoessPipe;
+use crate::spec::transform::lookup::LookupPipe;
 use crate::spec::transform::map::MapPipe;
+use crate::spec::transform::pivot::PivotPipe;
+use crate::spec::transform::regression::{
+    RegressionPipe, COEFFICIENTS_FIELD_NAME, R_SQUARED_FIELD_NAME,
+};
+use crate::spec::transform::sort::SortPipe;
+use crate::spec::transform::stack::StackPipe;
+use crate::spec::transform::window::WindowPipe;
+use bruc_expression::types::Schema;
 
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
@@ -12,6 +27,110 @@ pub enum Pipe {
     Filter(FilterPipe),
     Map(MapPipe),
     Group(GroupPipe),
+    Aggregate(AggregatePipe),
+    Sort(SortPipe),
+    Window(WindowPipe),
+    Stack(StackPipe),
+    Bin(BinPipe),
+    Lookup(LookupPipe),
+    Fold(FoldPipe),
+    Pivot(PivotPipe),
+    Regression(RegressionPipe),
+    Loess(LoessPipe),
+}
+
+impl Pipe {
+    /// Check that the pipe can be applied to data of a certain `schema`, and update the schema
+    /// with the fields the pipe produces.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        match self {
+            Pipe::Filter(filter) => filter.check(schema),
+            Pipe::Map(map) => map.check(schema),
+            Pipe::Group(group) => group.check(schema),
+            Pipe::Aggregate(aggregate) => aggregate.check(schema),
+            Pipe::Sort(sort) => sort.check(schema),
+            Pipe::Window(window) => window.check(schema),
+            Pipe::Stack(stack) => stack.check(schema),
+            Pipe::Bin(bin) => bin.check(schema),
+            Pipe::Lookup(lookup) => lookup.check(schema),
+            Pipe::Fold(fold) => fold.check(schema),
+            Pipe::Pivot(pivot) => pivot.check(schema),
+            Pipe::Regression(regression) => regression.check(schema),
+            Pipe::Loess(loess) => loess.check(schema),
+        }
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        match self {
+            Pipe::Filter(filter) => filter.fields(),
+            Pipe::Map(map) => map.fields(),
+            Pipe::Group(group) => group.fields(),
+            Pipe::Aggregate(aggregate) => aggregate.fields(),
+            Pipe::Sort(sort) => sort.fields(),
+            Pipe::Window(window) => window.fields(),
+            Pipe::Stack(stack) => stack.fields(),
+            Pipe::Bin(bin) => bin.fields(),
+            Pipe::Lookup(lookup) => lookup.fields(),
+            Pipe::Fold(fold) => fold.fields(),
+            Pipe::Pivot(pivot) => pivot.fields(),
+            Pipe::Regression(regression) => regression.fields(),
+            Pipe::Loess(loess) => loess.fields(),
+        }
+    }
+
+    /// Whether the fields present in the data values after applying the pipe are only known once
+    /// the data values are read, e.g. the fields added by a pivot.
+    pub(crate) fn has_dynamic_fields(&self) -> bool {
+        matches!(self, Pipe::Pivot(_))
+    }
+
+    /// Update the `fields` present in the data values after applying the pipe.
+    pub(crate) fn update_fields(&self, fields: &mut BTreeSet<String>) {
+        match self {
+            Pipe::Filter(_) | Pipe::Sort(_) => {}
+            Pipe::Map(map) => {
+                fields.insert(map.output.clone());
+            }
+            Pipe::Group(group) => {
+                *fields = BTreeSet::from([group.by.clone(), group.output.clone()]);
+            }
+            Pipe::Aggregate(aggregate) => {
+                *fields = aggregate.outputs();
+            }
+            Pipe::Window(window) => {
+                fields.extend(window.ops.iter().map(|op| op.output.clone()));
+            }
+            Pipe::Stack(stack) => {
+                fields.extend(stack.output.iter().cloned());
+            }
+            Pipe::Bin(bin) => {
+                fields.extend(bin.output.iter().cloned());
+            }
+            Pipe::Lookup(lookup) => {
+                fields.extend(lookup.values.iter().map(|value| value.output.clone()));
+            }
+            Pipe::Fold(fold) => {
+                fields.retain(|field| !fold.fields.contains(field));
+                fields.extend(fold.output.iter().cloned());
+            }
+            Pipe::Pivot(pivot) => {
+                *fields = pivot.groupby.iter().cloned().collect();
+            }
+            Pipe::Regression(regression) => {
+                *fields = regression.groupby.iter().cloned().collect();
+                fields.extend(regression.outputs().map(str::to_string));
+                if regression.params {
+                    fields.insert(COEFFICIENTS_FIELD_NAME.to_string());
+                    fields.insert(R_SQUARED_FIELD_NAME.to_string());
+                }
+            }
+            Pipe::Loess(loess) => {
+                *fields = loess.groupby.iter().cloned().collect();
+                fields.extend(loess.outputs().map(str::to_string));
+            }
+        }
+    }
 }
 
 pub trait Predicate {
@@ -30,10 +149,20 @@ mod serde_tests {
         let pipes_json = r#"[
       { "type": "filter", "fn": "a > 2" },
       { "type": "map", "fn": "a + 2", "output": "b" },
-      { "type": "group", "by": "b", "op": "count", "output": "count" }
+      { "type": "group", "by": "b", "op": "count", "output": "count" },
+      { "type": "aggregate", "groupby": ["b"], "ops": [{ "op": "sum", "field": "a", "as": "sum" }] },
+      { "type": "sort", "by": [{ "field": "b", "order": "descending" }] },
+      { "type": "window", "sort": [{ "field": "b" }], "ops": [{ "op": "rank", "as": "rank" }] },
+      { "type": "stack", "groupby": ["b"], "field": "a", "offset": "center" },
+      { "type": "bin", "field": "a", "maxbins": 10 },
+      { "type": "lookup", "from": "c", "key": "id", "field": "b", "values": [{ "field": "d" }] },
+      { "type": "fold", "fields": ["a", "b"] },
+      { "type": "pivot", "groupby": ["a"], "field": "key", "value": "value" },
+      { "type": "regression", "x": "a", "y": "b", "method": "exp" },
+      { "type": "loess", "x": "a", "y": "b", "bandwidth": 0.5 }
     ]"#;
         let pipes: Vec<Pipe> = serde_json::from_str(pipes_json).unwrap();
 
-        assert_eq!(pipes.len(), 3);
+        assert_eq!(pipes.len(), 13);
     }
 }
@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::types::Schema;
+
+use crate::spec::transform::aggregate::{find, AggregateField, AggregateOp};
+use crate::spec::transform::error::Error;
+
+/// `PivotPipe` reshapes long data values into wide ones, by grouping data values by the values
+/// of the `groupby` fields, and adding a field for each distinct item of the `field` to each
+/// group. The new fields summarize the items of the `value` field with an aggregate `op`, `sum`
+/// by default, so that colliding values are combined.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct PivotPipe {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) groupby: Vec<String>,
+    pub(crate) field: String,
+    pub(crate) value: String,
+    #[cfg_attr(feature = "serde", serde(default = "PivotPipe::default_op"))]
+    pub(crate) op: AggregateOp,
+}
+
+impl PivotPipe {
+    pub fn new(field: &str, value: &str) -> PivotPipe {
+        PivotPipe {
+            groupby: Vec::new(),
+            field: field.to_string(),
+            value: value.to_string(),
+            op: PivotPipe::default_op(),
+        }
+    }
+
+    /// Pivot the values of each group of certain fields separately.
+    pub fn with_groupby(mut self, groupby: Vec<&str>) -> Self {
+        self.groupby = groupby.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    /// Combine the colliding values of a field with a certain aggregate operation.
+    pub fn with_op(mut self, op: AggregateOp) -> Self {
+        self.op = op;
+        self
+    }
+
+    fn default_op() -> AggregateOp {
+        AggregateOp::Sum
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.groupby
+            .iter()
+            .chain([&self.field, &self.value])
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Check that the fields read by the pipe are present in a certain `schema`, and that the
+    /// aggregate operation supports the type of the `value` field. The schema is replaced with
+    /// the `groupby` fields, since the pivoted fields are only known once the data values are
+    /// read.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let mut result = Schema::new();
+
+        for field in &self.groupby {
+            result.insert(field, find(schema, field)?);
+        }
+
+        find(schema, &self.field)?;
+        AggregateField::new(self.op, &self.value, &self.value).check(schema)?;
+
+        *schema = result;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::aggregate::AggregateOp;
+    use crate::spec::transform::pivot::PivotPipe;
+
+    #[test]
+    fn checks_pivot() {
+        let schema = Schema::from_pairs(vec![
+            ("date", DataType::Date),
+            ("key", DataType::Text),
+            ("value", DataType::Number),
+        ]);
+
+        let mut result = schema.clone();
+        PivotPipe::new("key", "value")
+            .with_groupby(vec!["date"])
+            .check(&mut result)
+            .unwrap();
+        assert_eq!(result, Schema::from_pairs(vec![("date", DataType::Date)]));
+
+        assert_eq!(
+            PivotPipe::new("value", "key")
+                .with_op(AggregateOp::Mean)
+                .check(&mut schema.clone())
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected number, found text"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::aggregate::AggregateOp;
+    use crate::spec::transform::pivot::PivotPipe;
+
+    #[test]
+    fn deserialize_pivot() {
+        let pivot = serde_json::from_str::<PivotPipe>(
+            r#"{
+      "groupby": ["date"],
+      "field": "key",
+      "value": "value",
+      "op": "max"
+     }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pivot,
+            PivotPipe::new("key", "value")
+                .with_groupby(vec!["date"])
+                .with_op(AggregateOp::Max)
+        );
+
+        let pivot =
+            serde_json::from_str::<PivotPipe>(r#"{ "field": "key", "value": "value" }"#).unwrap();
+        assert_eq!(pivot, PivotPipe::new("key", "value"));
+    }
+}
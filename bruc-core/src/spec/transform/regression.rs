@@ -0,0 +1,489 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::aggregate::find;
+use crate::spec::transform::error::Error;
+
+pub(crate) const COEFFICIENTS_FIELD_NAME: &str = "coef";
+pub(crate) const R_SQUARED_FIELD_NAME: &str = "rSquared";
+
+/// Amount of points sampled along the fitted curves of non-linear methods.
+const SAMPLES: usize = 100;
+
+/// `RegressionPipe` fits a regression model of a certain `method`, predicting the numbers of
+/// the `y` field from the numbers of the `x` field, for the values of each group. Each group is
+/// replaced with points sampled along the fitted curve, over the `extent` of the `x` numbers if
+/// none is given, which are stored in the `output` fields, `x` and `y` by default. With
+/// `params`, the coefficients of the model and its coefficient of determination are added to
+/// the points as `coef` and `rSquared`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct RegressionPipe {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) groupby: Vec<String>,
+    pub(crate) x: String,
+    pub(crate) y: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) method: RegressionMethod,
+    #[cfg_attr(feature = "serde", serde(default = "RegressionPipe::default_order"))]
+    pub(crate) order: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) extent: Option<[f64; 2]>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) params: bool,
+    #[cfg_attr(feature = "serde", serde(rename = "as", default))]
+    pub(crate) output: Option<[String; 2]>,
+}
+
+impl RegressionPipe {
+    pub fn new(x: &str, y: &str) -> RegressionPipe {
+        RegressionPipe {
+            groupby: Vec::new(),
+            x: x.to_string(),
+            y: y.to_string(),
+            method: RegressionMethod::default(),
+            order: RegressionPipe::default_order(),
+            extent: None,
+            params: false,
+            output: None,
+        }
+    }
+
+    /// Fit a separate model for the groups of certain fields.
+    pub fn with_groupby(mut self, groupby: Vec<&str>) -> Self {
+        self.groupby = groupby.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    pub fn with_method(mut self, method: RegressionMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Fit a polynomial of a certain `order`, used by the `poly` method.
+    pub fn with_order(mut self, order: usize) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sample the fitted curve over a certain extent, instead of the extent of the `x` numbers.
+    pub fn with_extent(mut self, min: f64, max: f64) -> Self {
+        self.extent = Some([min, max]);
+        self
+    }
+
+    /// Add the coefficients of the model and its coefficient of determination to the points.
+    pub fn with_params(mut self, params: bool) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Store the sampled points in certain fields.
+    pub fn with_output(mut self, x: &str, y: &str) -> Self {
+        self.output = Some([x.to_string(), y.to_string()]);
+        self
+    }
+
+    fn default_order() -> usize {
+        3
+    }
+
+    /// Fields storing the sampled points, which are named after the `x` and `y` fields if not
+    /// given.
+    pub(crate) fn outputs(&self) -> [&str; 2] {
+        match &self.output {
+            Some([x, y]) => [x, y],
+            None => [&self.x, &self.y],
+        }
+    }
+
+    /// Fit the model to certain `points`. Points outside of the domain of the method are
+    /// skipped, e.g. non-positive `x` numbers for `log`. No model is fitted if there aren't
+    /// enough points to determine its coefficients.
+    pub(crate) fn fit(&self, points: &[(f64, f64)]) -> Option<Fit> {
+        let order = match self.method {
+            RegressionMethod::Quad => 2,
+            RegressionMethod::Poly => self.order,
+            _ => 1,
+        };
+
+        let valid: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|(x, y)| {
+                x.is_finite() && y.is_finite() && self.method.linearize(*x, *y).is_some()
+            })
+            .collect();
+        let transformed: Vec<(f64, f64)> = valid
+            .iter()
+            .filter_map(|(x, y)| self.method.linearize(*x, *y))
+            .collect();
+
+        let mut coefficients = least_squares(&transformed, order)?;
+        if matches!(self.method, RegressionMethod::Exp | RegressionMethod::Pow) {
+            coefficients[0] = coefficients[0].exp();
+        }
+
+        let mean = valid.iter().map(|(_, y)| y).sum::<f64>() / valid.len() as f64;
+        let (residual, total) = valid.iter().fold((0.0, 0.0), |(residual, total), (x, y)| {
+            let predicted = self.method.predict(&coefficients, *x);
+            (
+                residual + (y - predicted).powi(2),
+                total + (y - mean).powi(2),
+            )
+        });
+        let r_squared = if total > 0.0 {
+            1.0 - residual / total
+        } else {
+            1.0
+        };
+
+        let extent = self.extent.unwrap_or_else(|| {
+            valid
+                .iter()
+                .fold([f64::INFINITY, f64::NEG_INFINITY], |[min, max], (x, _)| {
+                    [min.min(*x), max.max(*x)]
+                })
+        });
+
+        Some(Fit {
+            coefficients,
+            r_squared,
+            extent,
+        })
+    }
+
+    /// Sample points along the curve of a fitted model over its extent. Linear models are
+    /// sampled at the ends of the extent.
+    pub(crate) fn sample(&self, fit: &Fit) -> Vec<(f64, f64)> {
+        let [min, max] = fit.extent;
+        let samples = match self.method {
+            RegressionMethod::Linear => 2,
+            _ => SAMPLES,
+        };
+
+        (0..samples)
+            .map(|index| {
+                let x = min + (max - min) * index as f64 / (samples - 1) as f64;
+                (x, self.method.predict(&fit.coefficients, x))
+            })
+            .collect()
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.groupby
+            .iter()
+            .chain([&self.x, &self.y])
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Check that the fields read by the pipe are present in a certain `schema`, and that the
+    /// `x` and `y` fields are numbers. The schema is replaced with the `groupby` fields and the
+    /// fields of the sampled points.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        let mut result = Schema::new();
+
+        for field in &self.groupby {
+            result.insert(field, find(schema, field)?);
+        }
+
+        for field in [&self.x, &self.y] {
+            let data_type = find(schema, field)?;
+            if !matches!(data_type, DataType::Number | DataType::Null) {
+                return Err(Error::from(ExpressionError::Type(
+                    TypeError::UnexpectedType(DataType::Number, data_type),
+                )));
+            }
+        }
+
+        for output in self.outputs() {
+            result.insert(output, DataType::Number);
+        }
+        if self.params {
+            result.insert(COEFFICIENTS_FIELD_NAME, DataType::Array);
+            result.insert(R_SQUARED_FIELD_NAME, DataType::Number);
+        }
+
+        *schema = result;
+
+        Ok(())
+    }
+}
+
+/// `RegressionMethod` lists the models fitted by a regression. Coefficients are listed from the
+/// intercept, `a`, onwards.
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum RegressionMethod {
+    /// `y = a + b * x`
+    #[default]
+    Linear,
+    /// `y = a + b * ln(x)`
+    Log,
+    /// `y = a * e^(b * x)`
+    Exp,
+    /// `y = a * x^b`
+    Pow,
+    /// `y = a + b * x + c * x^2`
+    Quad,
+    /// `y = a + b * x + ... + k * x^order`
+    Poly,
+}
+
+impl RegressionMethod {
+    /// Transform a point, so that the model is a polynomial of the transformed point. Points
+    /// outside of the domain of the method aren't transformed.
+    fn linearize(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        match self {
+            RegressionMethod::Log => (x > 0.0).then(|| (x.ln(), y)),
+            RegressionMethod::Exp => (y > 0.0).then(|| (x, y.ln())),
+            RegressionMethod::Pow => (x > 0.0 && y > 0.0).then(|| (x.ln(), y.ln())),
+            RegressionMethod::Linear | RegressionMethod::Quad | RegressionMethod::Poly => {
+                Some((x, y))
+            }
+        }
+    }
+
+    /// Predict the `y` number of a certain `x` number with the coefficients of a model.
+    fn predict(&self, coefficients: &[f64], x: f64) -> f64 {
+        match self {
+            RegressionMethod::Log => coefficients[0] + coefficients[1] * x.ln(),
+            RegressionMethod::Exp => coefficients[0] * (coefficients[1] * x).exp(),
+            RegressionMethod::Pow => coefficients[0] * x.powf(coefficients[1]),
+            RegressionMethod::Linear | RegressionMethod::Quad | RegressionMethod::Poly => {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0.0, |acc, coef| acc * x + coef)
+            }
+        }
+    }
+}
+
+/// `Fit` is a model fitted by a regression, with its `coefficients`, its coefficient of
+/// determination, `r_squared`, and the `extent` its curve is sampled over.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct Fit {
+    pub(crate) coefficients: Vec<f64>,
+    pub(crate) r_squared: f64,
+    pub(crate) extent: [f64; 2],
+}
+
+/// Fit a polynomial of a certain `order` to certain `points` with the method of least squares,
+/// by solving its normal equations with Gaussian elimination. Returns the coefficients from the
+/// lowest degree, or none if the equations have no unique solution.
+fn least_squares(points: &[(f64, f64)], order: usize) -> Option<Vec<f64>> {
+    let size = order + 1;
+    if points.len() < size {
+        return None;
+    }
+
+    // Augmented matrix of the normal equations, with the sums of the powers of `x`
+    let mut matrix = vec![vec![0.0; size + 1]; size];
+    for (x, y) in points {
+        for (row, equation) in matrix.iter_mut().enumerate() {
+            for (column, cell) in equation.iter_mut().take(size).enumerate() {
+                *cell += x.powi((row + column) as i32);
+            }
+            equation[size] += y * x.powi(row as i32);
+        }
+    }
+
+    for column in 0..size {
+        let pivot = (column..size).max_by(|left, right| {
+            matrix[*left][column]
+                .abs()
+                .total_cmp(&matrix[*right][column].abs())
+        })?;
+        if matrix[pivot][column].abs() < f64::EPSILON {
+            return None;
+        }
+        matrix.swap(column, pivot);
+
+        let (eliminated, rows) = matrix.split_at_mut(column + 1);
+        let pivot = &eliminated[column];
+        for row in rows {
+            let factor = row[column] / pivot[column];
+            for (cell, pivot) in row.iter_mut().zip(pivot).skip(column) {
+                *cell -= factor * pivot;
+            }
+        }
+    }
+
+    let mut coefficients = vec![0.0; size];
+    for row in (0..size).rev() {
+        let sum: f64 = (row + 1..size)
+            .map(|column| matrix[row][column] * coefficients[column])
+            .sum();
+        coefficients[row] = (matrix[row][size] - sum) / matrix[row][row];
+    }
+
+    Some(coefficients)
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::regression::{Fit, RegressionMethod, RegressionPipe};
+
+    fn assert_fit(fit: Option<Fit>, coefficients: &[f64], r_squared: f64) {
+        let fit = fit.unwrap();
+
+        assert_eq!(fit.coefficients.len(), coefficients.len());
+        for (coefficient, expected) in fit.coefficients.iter().zip(coefficients) {
+            assert!(
+                (coefficient - expected).abs() < 1e-6,
+                "{coefficient} != {expected}"
+            );
+        }
+        assert!((fit.r_squared - r_squared).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fits_regressions() {
+        let points = |f: fn(f64) -> f64| -> Vec<(f64, f64)> {
+            (1..=6).map(|x| (x as f64, f(x as f64))).collect()
+        };
+
+        let pipe = RegressionPipe::new("x", "y");
+        assert_fit(pipe.fit(&points(|x| 1.0 + 2.0 * x)), &[1.0, 2.0], 1.0);
+        assert_fit(
+            pipe.fit(&[(0.0, 1.0), (1.0, 2.0), (2.0, 2.0), (3.0, 3.0)]),
+            &[1.1, 0.6],
+            0.9,
+        );
+        assert_fit(
+            pipe.clone()
+                .with_method(RegressionMethod::Log)
+                .fit(&points(|x| 3.0 - 2.0 * x.ln())),
+            &[3.0, -2.0],
+            1.0,
+        );
+        assert_fit(
+            pipe.clone()
+                .with_method(RegressionMethod::Exp)
+                .fit(&points(|x| 2.0 * (0.5 * x).exp())),
+            &[2.0, 0.5],
+            1.0,
+        );
+        assert_fit(
+            pipe.clone()
+                .with_method(RegressionMethod::Pow)
+                .fit(&points(|x| 3.0 * x.powf(1.5))),
+            &[3.0, 1.5],
+            1.0,
+        );
+        assert_fit(
+            pipe.clone()
+                .with_method(RegressionMethod::Quad)
+                .fit(&points(|x| 1.0 - x + 0.5 * x * x)),
+            &[1.0, -1.0, 0.5],
+            1.0,
+        );
+        assert_fit(
+            pipe.clone()
+                .with_method(RegressionMethod::Poly)
+                .with_order(3)
+                .fit(&points(|x| 2.0 + x * x * x)),
+            &[2.0, 0.0, 0.0, 1.0],
+            1.0,
+        );
+
+        assert_eq!(pipe.fit(&[(1.0, 2.0)]), None);
+        assert_eq!(pipe.fit(&[(1.0, 2.0), (1.0, 3.0)]), None);
+    }
+
+    #[test]
+    fn samples_regressions() {
+        let pipe = RegressionPipe::new("x", "y");
+        let fit = pipe.fit(&[(1.0, 3.0), (3.0, 7.0), (2.0, 5.0)]).unwrap();
+        assert_eq!(pipe.sample(&fit), vec![(1.0, 3.0), (3.0, 7.0)]);
+
+        let pipe = pipe
+            .with_method(RegressionMethod::Quad)
+            .with_extent(0.0, 99.0);
+        let fit = pipe.fit(&[(1.0, 1.0), (2.0, 4.0), (3.0, 9.0)]).unwrap();
+        let samples = pipe.sample(&fit);
+        assert_eq!(samples.len(), 100);
+        assert_eq!(samples[0].0, 0.0);
+        assert!((samples[10].1 - 100.0).abs() < 1e-6);
+        assert_eq!(samples[99].0, 99.0);
+    }
+
+    #[test]
+    fn checks_regression() {
+        let schema = Schema::from_pairs(vec![
+            ("a", DataType::Text),
+            ("x", DataType::Number),
+            ("y", DataType::Number),
+        ]);
+
+        let mut result = schema.clone();
+        RegressionPipe::new("x", "y")
+            .with_groupby(vec!["a"])
+            .with_params(true)
+            .check(&mut result)
+            .unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![
+                ("a", DataType::Text),
+                ("x", DataType::Number),
+                ("y", DataType::Number),
+                ("coef", DataType::Array),
+                ("rSquared", DataType::Number),
+            ])
+        );
+
+        assert_eq!(
+            RegressionPipe::new("x", "a")
+                .check(&mut schema.clone())
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected number, found text"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::regression::{RegressionMethod, RegressionPipe};
+
+    #[test]
+    fn deserialize_regression() {
+        let regression = serde_json::from_str::<RegressionPipe>(
+            r#"{
+      "groupby": ["series"],
+      "x": "a",
+      "y": "b",
+      "method": "poly",
+      "order": 4,
+      "extent": [0, 10],
+      "params": true,
+      "as": ["u", "v"]
+     }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            regression,
+            RegressionPipe::new("a", "b")
+                .with_groupby(vec!["series"])
+                .with_method(RegressionMethod::Poly)
+                .with_order(4)
+                .with_extent(0.0, 10.0)
+                .with_params(true)
+                .with_output("u", "v")
+        );
+
+        let regression =
+            serde_json::from_str::<RegressionPipe>(r#"{ "x": "a", "y": "b" }"#).unwrap();
+        assert_eq!(regression, RegressionPipe::new("a", "b"));
+    }
+}
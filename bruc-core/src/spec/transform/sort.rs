@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use bruc_expression::data::{DataItem, DataSource};
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::Schema;
+
+use crate::data::DataValue;
+use crate::spec::transform::error::Error;
+
+/// `SortPipe` orders data values by a list of fields, each in ascending or descending order.
+/// Later fields break the ties of the former ones, and values that compare equal keep their
+/// order.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct SortPipe {
+    pub(crate) by: Vec<SortField>,
+}
+
+impl SortPipe {
+    pub fn new(by: Vec<SortField>) -> SortPipe {
+        SortPipe { by }
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.by.iter().map(|by| by.field.as_str()).collect()
+    }
+
+    /// Check that the fields to sort by are present in a certain `schema`.
+    pub fn check(&self, schema: &Schema) -> Result<(), Error> {
+        for by in &self.by {
+            if schema.find(&by.field).is_none() {
+                return Err(Error::from(ExpressionError::Type(
+                    TypeError::UnknownVariable(by.field.clone()),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sort certain data values in place.
+    pub fn apply(&self, values: &mut [DataValue]) {
+        values.sort_by(|left, right| self.compare(left, right));
+    }
+
+    /// Compare two data values by the fields to sort by.
+    pub fn compare(&self, left: &DataValue, right: &DataValue) -> Ordering {
+        self.by
+            .iter()
+            .map(|by| by.compare(left, right))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// `SortField` sorts data values by a certain `field`, in a certain `order`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct SortField {
+    pub(crate) field: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) order: SortOrder,
+}
+
+impl SortField {
+    pub fn new(field: &str, order: SortOrder) -> SortField {
+        SortField {
+            field: field.to_string(),
+            order,
+        }
+    }
+
+    pub fn ascending(field: &str) -> SortField {
+        SortField::new(field, SortOrder::Ascending)
+    }
+
+    pub fn descending(field: &str) -> SortField {
+        SortField::new(field, SortOrder::Descending)
+    }
+
+    /// Compare two data values by the field. Null and missing values are sorted last in both
+    /// orders.
+    fn compare(&self, left: &DataValue, right: &DataValue) -> Ordering {
+        match (self.item(left), self.item(right)) {
+            (Some(left), Some(right)) => match self.order {
+                SortOrder::Ascending => compare(left, right),
+                SortOrder::Descending => compare(right, left),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    fn item<'a>(&self, value: &'a DataValue) -> Option<&'a DataItem> {
+        value.get(&self.field).filter(|item| !item.is_null())
+    }
+}
+
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Compare two data items. Items of the same type follow their natural order, where `false`
+/// comes before `true` and text is compared lexicographically. Items of different types are
+/// ordered by type: bools, numbers, dates, text, arrays, objects and nulls.
+pub(crate) fn compare(left: &DataItem, right: &DataItem) -> Ordering {
+    match (left, right) {
+        (DataItem::Bool(left), DataItem::Bool(right)) => left.cmp(right),
+        (DataItem::Number(left), DataItem::Number(right)) => left.total_cmp(right),
+        (DataItem::Date(left), DataItem::Date(right)) => left.cmp(right),
+        (DataItem::Text(left), DataItem::Text(right)) => left.cmp(right),
+        (DataItem::Array(left), DataItem::Array(right)) => left
+            .iter()
+            .zip(right)
+            .map(|(left, right)| compare(left, right))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| left.len().cmp(&right.len())),
+        (DataItem::Object(left), DataItem::Object(right)) => left
+            .iter()
+            .zip(right)
+            .map(|((left_key, left), (right_key, right))| {
+                left_key.cmp(right_key).then_with(|| compare(left, right))
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| left.len().cmp(&right.len())),
+        _ => rank(left).cmp(&rank(right)),
+    }
+}
+
+fn rank(item: &DataItem) -> u8 {
+    match item {
+        DataItem::Bool(_) => 0,
+        DataItem::Number(_) => 1,
+        DataItem::Date(_) => 2,
+        DataItem::Text(_) => 3,
+        DataItem::Array(_) => 4,
+        DataItem::Object(_) => 5,
+        DataItem::Null => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::data::DataItem;
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::data::DataValue;
+    use crate::spec::transform::sort::{SortField, SortPipe};
+
+    #[test]
+    fn sorts_by_several_fields() {
+        let value = |a: DataItem, b: f64| DataValue::from_pairs(vec![("a", a), ("b", b.into())]);
+        let mut values = vec![
+            value("y".into(), 1.0),
+            value(DataItem::Null, 2.0),
+            value(2.0.into(), 3.0),
+            value("x".into(), 4.0),
+            value(true.into(), 5.0),
+            value("y".into(), 6.0),
+            value(2.0.into(), 7.0),
+            DataValue::from_pairs(vec![("b", 8.0.into())]),
+        ];
+
+        SortPipe::new(vec![SortField::ascending("a"), SortField::descending("b")])
+            .apply(&mut values);
+
+        assert_eq!(
+            values,
+            vec![
+                value(true.into(), 5.0),
+                value(2.0.into(), 7.0),
+                value(2.0.into(), 3.0),
+                value("x".into(), 4.0),
+                value("y".into(), 6.0),
+                value("y".into(), 1.0),
+                DataValue::from_pairs(vec![("b", 8.0.into())]),
+                value(DataItem::Null, 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn sorts_stably_with_nulls_last() {
+        let value = |a: DataItem, b: f64| DataValue::from_pairs(vec![("a", a), ("b", b.into())]);
+        let mut values = vec![
+            value(DataItem::Null, 1.0),
+            value(1.0.into(), 2.0),
+            value(3.0.into(), 3.0),
+            value(1.0.into(), 4.0),
+        ];
+
+        SortPipe::new(vec![SortField::descending("a")]).apply(&mut values);
+
+        assert_eq!(
+            values,
+            vec![
+                value(3.0.into(), 3.0),
+                value(1.0.into(), 2.0),
+                value(1.0.into(), 4.0),
+                value(DataItem::Null, 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_sort() {
+        let schema = Schema::from_pairs(vec![("a", DataType::Number)]);
+
+        assert!(SortPipe::new(vec![SortField::ascending("a")])
+            .check(&schema)
+            .is_ok());
+        assert_eq!(
+            SortPipe::new(vec![SortField::ascending("b")])
+                .check(&schema)
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnknownVariable: b"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::sort::{SortField, SortPipe};
+
+    #[test]
+    fn deserialize_sort() {
+        let sort = serde_json::from_str::<SortPipe>(
+            r#"{
+      "by": [{ "field": "a" }, { "field": "b", "order": "descending" }]
+     }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sort,
+            SortPipe::new(vec![SortField::ascending("a"), SortField::descending("b")])
+        );
+    }
+}
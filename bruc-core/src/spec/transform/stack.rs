@@ -0,0 +1,185 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::error::{Error as ExpressionError, TypeError};
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::aggregate::find;
+use crate::spec::transform::error::Error;
+use crate::spec::transform::sort::SortField;
+
+/// `StackPipe` stacks the numbers of a `field` on top of each other, for the values of each
+/// group, e.g. the categories of a bar at a certain position. Values are stacked in the order of
+/// the `sort` fields, and the start and end of each value in its stack are added as the `output`
+/// fields, `y0` and `y1` by default.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct StackPipe {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) groupby: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) sort: Vec<SortField>,
+    pub(crate) field: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) offset: StackOffset,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "as", default = "StackPipe::default_output")
+    )]
+    pub(crate) output: [String; 2],
+}
+
+impl StackPipe {
+    pub fn new(field: &str) -> StackPipe {
+        StackPipe {
+            groupby: Vec::new(),
+            sort: Vec::new(),
+            field: field.to_string(),
+            offset: StackOffset::default(),
+            output: StackPipe::default_output(),
+        }
+    }
+
+    /// Stack the values of each group of certain fields separately.
+    pub fn with_groupby(mut self, groupby: Vec<&str>) -> Self {
+        self.groupby = groupby.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    /// Stack the values of each group in the order of certain fields.
+    pub fn with_sort(mut self, sort: Vec<SortField>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: StackOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Store the start and the end of each value in certain fields.
+    pub fn with_output(mut self, start: &str, end: &str) -> Self {
+        self.output = [start.to_string(), end.to_string()];
+        self
+    }
+
+    fn default_output() -> [String; 2] {
+        ["y0".to_string(), "y1".to_string()]
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.groupby
+            .iter()
+            .chain(self.sort.iter().map(|sort| &sort.field))
+            .chain([&self.field])
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Check that the fields read by the pipe are present in a certain `schema`, and that the
+    /// stacked field is a number. The output fields are added to the schema.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        for field in self
+            .groupby
+            .iter()
+            .chain(self.sort.iter().map(|sort| &sort.field))
+        {
+            find(schema, field)?;
+        }
+
+        let data_type = find(schema, &self.field)?;
+        if !matches!(data_type, DataType::Number | DataType::Null) {
+            return Err(Error::from(ExpressionError::Type(
+                TypeError::UnexpectedType(DataType::Number, data_type),
+            )));
+        }
+
+        for output in &self.output {
+            schema.insert(output, DataType::Number);
+        }
+
+        Ok(())
+    }
+}
+
+/// `StackOffset` positions the stacks relative to each other.
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum StackOffset {
+    /// Stacks start at zero. Negative numbers are stacked downwards, separately from positive
+    /// ones.
+    #[default]
+    Zero,
+    /// Stacks are centered around the middle of the tallest stack.
+    Center,
+    /// Stacks are scaled to span from zero to one.
+    Normalize,
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::stack::StackPipe;
+
+    #[test]
+    fn checks_stack() {
+        let schema = Schema::from_pairs(vec![("a", DataType::Text), ("b", DataType::Number)]);
+
+        let mut result = schema.clone();
+        StackPipe::new("b")
+            .with_groupby(vec!["a"])
+            .check(&mut result)
+            .unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![
+                ("a", DataType::Text),
+                ("b", DataType::Number),
+                ("y0", DataType::Number),
+                ("y1", DataType::Number),
+            ])
+        );
+
+        assert_eq!(
+            StackPipe::new("a")
+                .check(&mut schema.clone())
+                .unwrap_err()
+                .to_string(),
+            "PipeError::Expression: TypeError::UnexpectedType: expected number, found text"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::sort::SortField;
+    use crate::spec::transform::stack::{StackOffset, StackPipe};
+
+    #[test]
+    fn deserialize_stack() {
+        let stack = serde_json::from_str::<StackPipe>(
+            r#"{
+      "groupby": ["week"],
+      "sort": [{ "field": "category" }],
+      "field": "amount",
+      "offset": "normalize",
+      "as": ["start", "end"]
+     }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            stack,
+            StackPipe::new("amount")
+                .with_groupby(vec!["week"])
+                .with_sort(vec![SortField::ascending("category")])
+                .with_offset(StackOffset::Normalize)
+                .with_output("start", "end")
+        );
+
+        let stack = serde_json::from_str::<StackPipe>(r#"{ "field": "amount" }"#).unwrap();
+        assert_eq!(stack, StackPipe::new("amount"));
+    }
+}
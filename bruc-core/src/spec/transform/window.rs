@@ -0,0 +1,321 @@
+use std::collections::BTreeSet;
+
+use bruc_expression::types::{DataType, Schema};
+
+use crate::spec::transform::aggregate::{find, AggregateField, AggregateOp};
+use crate::spec::transform::error::{Error, PipeError};
+use crate::spec::transform::sort::SortField;
+
+/// `WindowPipe` computes the `ops` for each data value over the values of its group, which is
+/// ordered by the `sort` fields. Aggregate operations, together with `first_value` and
+/// `last_value`, only consider the values of a sliding `frame` around each value. Data values
+/// keep their order, and the results are added as new fields.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct WindowPipe {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) groupby: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) sort: Vec<SortField>,
+    #[cfg_attr(feature = "serde", serde(default = "Frame::cumulative"))]
+    pub(crate) frame: Frame,
+    pub(crate) ops: Vec<WindowField>,
+}
+
+impl WindowPipe {
+    pub fn new(ops: Vec<WindowField>) -> WindowPipe {
+        WindowPipe {
+            groupby: Vec::new(),
+            sort: Vec::new(),
+            frame: Frame::cumulative(),
+            ops,
+        }
+    }
+
+    /// Compute the operations separately for the groups of certain fields.
+    pub fn with_groupby(mut self, groupby: Vec<&str>) -> Self {
+        self.groupby = groupby.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    /// Order the values of each group by certain fields.
+    pub fn with_sort(mut self, sort: Vec<SortField>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Consider a certain frame of values around each value.
+    pub fn with_frame(mut self, frame: Frame) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    /// Fields of the data values read by the pipe.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        self.groupby
+            .iter()
+            .map(String::as_str)
+            .chain(self.sort.iter().map(|sort| sort.field.as_str()))
+            .chain(self.ops.iter().filter_map(|op| op.field.as_deref()))
+            .collect()
+    }
+
+    /// Check that the fields read by the pipe are present in a certain `schema`, and add the
+    /// outputs of the operations to the schema.
+    pub fn check(&self, schema: &mut Schema) -> Result<(), Error> {
+        for field in self
+            .groupby
+            .iter()
+            .chain(self.sort.iter().map(|sort| &sort.field))
+        {
+            find(schema, field)?;
+        }
+
+        let outputs = self
+            .ops
+            .iter()
+            .map(|op| Ok((op.output.as_str(), op.check(schema)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for (output, data_type) in outputs {
+            schema.insert(output, data_type);
+        }
+
+        Ok(())
+    }
+}
+
+/// `Frame` bounds the values considered for each value, by offsets relative to its position in
+/// the sorted group. A missing `start` or `end` extends the frame to the start or the end of the
+/// group, respectively.
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "[Option<i64>; 2]"))]
+pub struct Frame {
+    pub(crate) start: Option<i64>,
+    pub(crate) end: Option<i64>,
+}
+
+impl Frame {
+    pub fn new(start: Option<i64>, end: Option<i64>) -> Frame {
+        Frame { start, end }
+    }
+
+    /// Frame from the start of the group up to each value, used by default.
+    pub fn cumulative() -> Frame {
+        Frame::new(None, Some(0))
+    }
+
+    /// Positions of the values in the frame of the value at a certain `position`, for a group of
+    /// a certain `size`.
+    pub(crate) fn range(&self, position: usize, size: usize) -> std::ops::Range<usize> {
+        let offset = |offset: i64| (position as i64 + offset).clamp(0, size as i64) as usize;
+
+        let start = self.start.map_or(0, offset);
+        let end = self.end.map_or(size, |end| offset(end.saturating_add(1)));
+
+        start..end.max(start)
+    }
+}
+
+impl From<[Option<i64>; 2]> for Frame {
+    fn from([start, end]: [Option<i64>; 2]) -> Self {
+        Frame::new(start, end)
+    }
+}
+
+/// `WindowField` computes a window operation `op`, which may read a certain `field`, and stores
+/// the result in an `output` field. `param` is the offset of `lag` and `lead`, which defaults to
+/// one.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct WindowField {
+    pub(crate) op: WindowOp,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) field: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) param: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(rename = "as"))]
+    pub(crate) output: String,
+}
+
+impl WindowField {
+    pub fn new(op: WindowOp, field: Option<&str>, output: &str) -> WindowField {
+        WindowField {
+            op,
+            field: field.map(str::to_string),
+            param: None,
+            output: output.to_string(),
+        }
+    }
+
+    pub fn with_param(mut self, param: usize) -> Self {
+        self.param = Some(param);
+        self
+    }
+
+    /// Check that the field of the operation is present in a certain `schema`, and return the
+    /// type of the operation's result.
+    fn check(&self, schema: &Schema) -> Result<DataType, Error> {
+        let function = match self.op {
+            WindowOp::Function(function) => function,
+            WindowOp::Aggregate(op) => {
+                return AggregateField {
+                    op,
+                    field: self.field.clone(),
+                    output: self.output.clone(),
+                }
+                .check(schema);
+            }
+        };
+
+        match function {
+            WindowFunction::RowNumber | WindowFunction::Rank | WindowFunction::DenseRank => {
+                Ok(DataType::Number)
+            }
+            _ => {
+                let field = self
+                    .field
+                    .as_ref()
+                    .ok_or_else(|| Error::Pipe(PipeError::MissingField(self.output.clone())))?;
+                find(schema, field)
+            }
+        }
+    }
+}
+
+/// `WindowOp` is either a window function, or an aggregate operation over the frame.
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum WindowOp {
+    Function(WindowFunction),
+    Aggregate(AggregateOp),
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum WindowFunction {
+    /// Position of the value in its group, starting at one.
+    RowNumber,
+    /// Rank of the value in its group, where ties share the same rank, leaving gaps after them.
+    Rank,
+    /// Rank of the value in its group, where ties share the same rank, without gaps.
+    DenseRank,
+    /// Field of the value a certain amount of positions before.
+    Lag,
+    /// Field of the value a certain amount of positions after.
+    Lead,
+    /// Field of the first value of the frame.
+    FirstValue,
+    /// Field of the last value of the frame.
+    LastValue,
+}
+
+#[cfg(test)]
+mod tests {
+    use bruc_expression::types::{DataType, Schema};
+
+    use crate::spec::transform::aggregate::AggregateOp;
+    use crate::spec::transform::sort::SortField;
+    use crate::spec::transform::window::{
+        Frame, WindowField, WindowFunction, WindowOp, WindowPipe,
+    };
+
+    #[test]
+    fn computes_frame_ranges() {
+        assert_eq!(Frame::cumulative().range(2, 5), 0..3);
+        assert_eq!(Frame::new(Some(-1), Some(1)).range(0, 5), 0..2);
+        assert_eq!(Frame::new(Some(-1), Some(1)).range(4, 5), 3..5);
+        assert_eq!(Frame::new(Some(1), None).range(4, 5), 5..5);
+        assert_eq!(Frame::new(Some(2), Some(1)).range(1, 5), 3..3);
+        assert_eq!(Frame::new(None, None).range(1, 5), 0..5);
+    }
+
+    #[test]
+    fn checks_window() {
+        let schema = Schema::from_pairs(vec![("a", DataType::Text), ("b", DataType::Number)]);
+
+        let pipe = WindowPipe::new(vec![
+            WindowField::new(WindowOp::Function(WindowFunction::Rank), None, "rank"),
+            WindowField::new(WindowOp::Function(WindowFunction::Lag), Some("a"), "lag"),
+            WindowField::new(WindowOp::Aggregate(AggregateOp::Sum), Some("b"), "sum"),
+        ])
+        .with_groupby(vec!["a"])
+        .with_sort(vec![SortField::ascending("b")]);
+        let mut result = schema.clone();
+        pipe.check(&mut result).unwrap();
+        assert_eq!(
+            result,
+            Schema::from_pairs(vec![
+                ("a", DataType::Text),
+                ("b", DataType::Number),
+                ("rank", DataType::Number),
+                ("lag", DataType::Text),
+                ("sum", DataType::Number),
+            ])
+        );
+
+        let pipe = WindowPipe::new(vec![WindowField::new(
+            WindowOp::Function(WindowFunction::Lead),
+            None,
+            "lead",
+        )]);
+        assert_eq!(
+            pipe.check(&mut schema.clone()).unwrap_err().to_string(),
+            "PipeError::MissingField: lead"
+        );
+
+        let pipe = WindowPipe::new(Vec::new()).with_sort(vec![SortField::ascending("c")]);
+        assert_eq!(
+            pipe.check(&mut schema.clone()).unwrap_err().to_string(),
+            "PipeError::Expression: TypeError::UnknownVariable: c"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod serde_tests {
+    use crate::spec::transform::aggregate::AggregateOp;
+    use crate::spec::transform::sort::SortField;
+    use crate::spec::transform::window::{
+        Frame, WindowField, WindowFunction, WindowOp, WindowPipe,
+    };
+
+    #[test]
+    fn deserialize_window() {
+        let window = serde_json::from_str::<WindowPipe>(
+            r#"{
+      "groupby": ["a"],
+      "sort": [{ "field": "b" }],
+      "frame": [-6, null],
+      "ops": [
+        { "op": "row_number", "as": "row" },
+        { "op": "lag", "field": "b", "param": 2, "as": "lag_b" },
+        { "op": "mean", "field": "b", "as": "mean_b" }
+      ]
+     }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            window,
+            WindowPipe::new(vec![
+                WindowField::new(WindowOp::Function(WindowFunction::RowNumber), None, "row"),
+                WindowField::new(WindowOp::Function(WindowFunction::Lag), Some("b"), "lag_b")
+                    .with_param(2),
+                WindowField::new(WindowOp::Aggregate(AggregateOp::Mean), Some("b"), "mean_b"),
+            ])
+            .with_groupby(vec!["a"])
+            .with_sort(vec![SortField::ascending("b")])
+            .with_frame(Frame::new(Some(-6), None))
+        );
+
+        let window =
+            serde_json::from_str::<WindowPipe>(r#"{ "ops": [{ "op": "rank", "as": "rank" }] }"#)
+                .unwrap();
+        assert_eq!(window.frame, Frame::cumulative());
+    }
+}
@@ -1,5 +1,5 @@
-use core::f32;
+use core::f64;
 
-pub(crate) fn radians_to_degrees(radians: f32) -> f32 {
-    radians * 180.0 / f32::consts::PI
+pub(crate) fn radians_to_degrees(radians: f64) -> f64 {
+    radians * 180.0 / f64::consts::PI
 }
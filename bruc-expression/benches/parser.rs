@@ -4,6 +4,7 @@ extern crate test;
 
 use test::Bencher;
 
+use bruc_expression::compile::Program;
 use bruc_expression::expr::Interpretable;
 use bruc_expression::vars::Variables;
 use bruc_expression::PredicateParser;
@@ -61,8 +62,8 @@ fn bench_interpret_numeric_predicate_with_vars(b: &mut Bencher) {
 #[bench]
 fn bench_interpret_hybrid_predicate_with_struct_vars(b: &mut Bencher) {
     struct Data {
-        a: f32,
-        b: f32,
+        a: f64,
+        b: f64,
         c: bool,
     }
 
@@ -83,3 +84,68 @@ fn bench_interpret_hybrid_predicate_with_struct_vars(b: &mut Bencher) {
         result
     });
 }
+
+#[bench]
+fn bench_compiled_hybrid_predicate_with_struct_vars(b: &mut Bencher) {
+    let vars = Variables::from_pairs(vec![
+        ("a", 3.0.into()),
+        ("b", 2.0.into()),
+        ("c", true.into()),
+    ]);
+    let expression = PredicateParser::new("(a <= b) && c").parse().unwrap();
+    let program = Program::compile(&expression).unwrap();
+    b.iter(|| {
+        let result: bool = program.interpret(&vars).unwrap();
+        result
+    });
+}
+
+#[bench]
+fn bench_interpret_range_predicate_with_vars(b: &mut Bencher) {
+    let expression = PredicateParser::new("a > 1 && a < 4 && a != 3 * (2 - 1)")
+        .parse()
+        .unwrap();
+    let vars = Variables::from_pairs(vec![("a", 2.0.into())]);
+    b.iter(|| {
+        let result: bool = expression.interpret(&vars).unwrap();
+        result
+    });
+}
+
+#[bench]
+fn bench_compiled_range_predicate_with_vars(b: &mut Bencher) {
+    let expression = PredicateParser::new("a > 1 && a < 4 && a != 3 * (2 - 1)")
+        .parse()
+        .unwrap();
+    let program = Program::compile(&expression).unwrap();
+    let vars = Variables::from_pairs(vec![("a", 2.0.into())]);
+    b.iter(|| {
+        let result: bool = program.interpret(&vars).unwrap();
+        result
+    });
+}
+
+#[bench]
+fn bench_interpret_numeric_expression_with_vars(b: &mut Bencher) {
+    let expression = PredicateParser::new("a * (2 + 3) - round(b / (4 * 2.5), 1)")
+        .parse()
+        .unwrap();
+    let vars = Variables::from_pairs(vec![("a", 3.0.into()), ("b", 42.0.into())]);
+    b.iter(|| {
+        let result: f64 = expression.interpret(&vars).unwrap();
+        result
+    });
+}
+
+#[bench]
+fn bench_compiled_numeric_expression_with_vars(b: &mut Bencher) {
+    let expression = PredicateParser::new("a * (2 + 3) - round(b / (4 * 2.5), 1)")
+        .parse()
+        .unwrap();
+    let program = Program::compile(&expression).unwrap();
+    let vars = Variables::from_pairs(vec![("a", 3.0.into()), ("b", 42.0.into())]);
+    b.iter(|| {
+        let result: f64 = program.interpret(&vars).unwrap();
+        result
+    });
+}
@@ -0,0 +1,729 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::data::{DataItem, DataSource};
+use crate::error::{ConstructionError, Error, InterpretationError, Result};
+use crate::expr::{divide, modulo, Cons, Expression, Interpretable};
+use crate::symbols::{Operator, Symbol};
+use crate::vars::Variables;
+
+/// Maximum amount of slots, whose values are kept inline while evaluating a program.
+const INLINE_SLOTS: usize = 8;
+
+/// Maximum amount of arguments, whose values are kept inline while calling a function.
+const INLINE_ARGS: usize = 4;
+
+/// `Node` is a compiled sub-expression of a `Program`, which evaluates its value for a certain
+/// frame.
+type Node = Arc<dyn for<'a> Fn(&Frame<'a>) -> Eval<'a> + Send + Sync>;
+
+type Eval<'a> = std::result::Result<Value<'a>, Fault>;
+
+/// `Operand` is a compiled sub-expression of a `Program`. Slots and constants are evaluated in
+/// place, so that only operations need a node.
+#[derive(Clone)]
+enum Operand {
+    Slot(usize),
+    Constant(usize),
+    Node(Node),
+}
+
+impl Operand {
+    #[inline]
+    fn eval<'a>(&self, frame: &Frame<'a>) -> Eval<'a> {
+        match self {
+            Operand::Slot(slot) => frame.load(*slot),
+            Operand::Constant(index) => Ok(Value::from(&frame.constants[*index])),
+            Operand::Node(node) => node(frame),
+        }
+    }
+}
+
+/// `Program` is the compiled form of an `Expression`. The expression tree is flattened into a
+/// tree of closures, specialized for each operation, where variables are resolved to slots, so
+/// that each variable is looked up only once per evaluation, and literal sub-expressions are
+/// folded into constants.
+#[derive(Clone)]
+pub struct Program {
+    expression: Expression,
+    root: Operand,
+    constants: Vec<DataItem>,
+    slots: Vec<String>,
+}
+
+impl Program {
+    /// Compile an `expression` into a new program.
+    pub fn compile(expression: &Expression) -> Result<Program> {
+        let mut compiler = Compiler {
+            constants: Vec::new(),
+            slots: Vec::new(),
+        };
+        let root = compiler.compile(expression)?;
+
+        Ok(Program {
+            expression: expression.clone(),
+            root,
+            constants: compiler.constants,
+            slots: compiler.slots,
+        })
+    }
+
+    /// Names of the variables referenced by the program, in the order of their slots.
+    pub fn slots(&self) -> &[String] {
+        &self.slots
+    }
+
+    /// Evaluate the program for the variables found in a certain `source`.
+    pub fn evaluate(&self, source: &impl DataSource) -> Result<DataItem> {
+        self.run(source, |value| Ok(value.into()))
+    }
+
+    /// Run the program for a certain `source` and hand over the resulting value to `output`.
+    #[inline]
+    fn run<T>(
+        &self,
+        source: &impl DataSource,
+        output: impl FnOnce(Value) -> Result<T>,
+    ) -> Result<T> {
+        // Programs with few slots keep their values without allocating
+        if self.slots.len() <= INLINE_SLOTS {
+            let mut values = [None; INLINE_SLOTS];
+            for (value, name) in values.iter_mut().zip(&self.slots) {
+                *value = source.get(name);
+            }
+
+            self.execute(&values, output)
+        } else {
+            let values: Vec<Option<&DataItem>> =
+                self.slots.iter().map(|name| source.get(name)).collect();
+
+            self.execute(&values, output)
+        }
+    }
+
+    #[inline]
+    fn execute<'a, T>(
+        &'a self,
+        slots: &[Option<&'a DataItem>],
+        output: impl FnOnce(Value) -> Result<T>,
+    ) -> Result<T> {
+        let frame = Frame {
+            slots,
+            constants: &self.constants,
+        };
+
+        match self.root.eval(&frame) {
+            Ok(value) => output(value),
+            Err(fault) => Err(fault.into()),
+        }
+    }
+}
+
+impl fmt::Debug for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Program")
+            .field("expression", &self.expression)
+            .field("constants", &self.constants)
+            .field("slots", &self.slots)
+            .finish()
+    }
+}
+
+impl PartialEq for Program {
+    fn eq(&self, other: &Self) -> bool {
+        // Programs compiled from the same expression behave the same
+        self.expression == other.expression
+    }
+}
+
+impl Interpretable<bool> for Program {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<bool> {
+        self.run(source, |value| match value {
+            Value::Bool(value) => Ok(value),
+            _ => Err(Error::Construction(
+                ConstructionError::InvalidBooleanConstruction,
+            )),
+        })
+    }
+}
+
+impl Interpretable<f64> for Program {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<f64> {
+        self.run(source, |value| match value {
+            Value::Number(value) => Ok(value),
+            _ => Err(Error::Construction(
+                ConstructionError::InvalidNumericConstruction,
+            )),
+        })
+    }
+}
+
+impl Interpretable<String> for Program {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<String> {
+        self.run(source, |value| match value {
+            Value::Text(value) => Ok(value.into_owned()),
+            _ => Err(Error::Construction(
+                ConstructionError::InvalidTextConstruction,
+            )),
+        })
+    }
+}
+
+impl Interpretable<DataItem> for Program {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<DataItem> {
+        self.evaluate(source)
+    }
+}
+
+/// `Compiler` builds the nodes of a `Program`, while collecting its constants and slots.
+struct Compiler {
+    constants: Vec<DataItem>,
+    slots: Vec<String>,
+}
+
+impl Compiler {
+    fn compile(&mut self, expression: &Expression) -> Result<Operand> {
+        if is_constant(expression) {
+            if let Ok(value) = Interpretable::<DataItem>::interpret(expression, &Variables::new()) {
+                return Ok(self.constant(value));
+            }
+        }
+
+        match expression {
+            Expression::Atom(Symbol::Variable(name)) => Ok(Operand::Slot(self.slot(name))),
+            Expression::Atom(_) => Err(Error::Interpretation(
+                InterpretationError::InvalidExpression,
+            )),
+            Expression::Cons(cons) => match cons.as_ref() {
+                Cons::Binary(operator, (left, right)) => {
+                    let left = self.compile(left)?;
+                    let right = self.compile(right)?;
+                    Ok(Operand::Node(binary(*operator, left, right)))
+                }
+                Cons::Unary(operator, root) => {
+                    let root = self.compile(root)?;
+                    Ok(Operand::Node(unary(*operator, root)))
+                }
+                Cons::Conditional(condition, (then, otherwise)) => {
+                    if is_constant(condition) {
+                        let value: Result<bool> = condition.interpret(&Variables::new());
+                        if let Ok(value) = value {
+                            return self.compile(if value { then } else { otherwise });
+                        }
+                    }
+
+                    let condition = self.compile(condition)?;
+                    let then = self.compile(then)?;
+                    let otherwise = self.compile(otherwise)?;
+
+                    Ok(Operand::Node(Arc::new(
+                        move |frame: &Frame| match condition.eval(frame)? {
+                            Value::Bool(true) => then.eval(frame),
+                            Value::Bool(false) => otherwise.eval(frame),
+                            _ => Err(Fault::Construction(
+                                ConstructionError::InvalidBooleanConstruction,
+                            )),
+                        },
+                    )))
+                }
+            },
+            Expression::Call(call) => {
+                let function = call.function;
+                let args = call
+                    .args
+                    .iter()
+                    .map(|arg| self.compile(arg))
+                    .collect::<Result<Vec<Operand>>>()?;
+
+                if !function.is_numeric() {
+                    return Ok(Operand::Node(Arc::new(move |frame: &Frame| {
+                        const NULL: DataItem = DataItem::Null;
+
+                        let result = if args.len() <= INLINE_ARGS {
+                            let mut values = [NULL; INLINE_ARGS];
+                            for (value, arg) in values.iter_mut().zip(&args) {
+                                *value = DataItem::from(arg.eval(frame)?);
+                            }
+
+                            function.call(&values[..args.len()])
+                        } else {
+                            let values = args
+                                .iter()
+                                .map(|arg| arg.eval(frame).map(DataItem::from))
+                                .collect::<std::result::Result<Vec<DataItem>, Fault>>()?;
+
+                            function.call(&values)
+                        };
+
+                        result.map(Value::from_item).ok_or(Fault::Interpretation(
+                            InterpretationError::InvalidExpression,
+                        ))
+                    })));
+                }
+
+                Ok(Operand::Node(Arc::new(move |frame: &Frame| {
+                    // Calls with few arguments keep their values without allocating
+                    let result = if args.len() <= INLINE_ARGS {
+                        let mut values = [0.0; INLINE_ARGS];
+                        for (value, arg) in values.iter_mut().zip(&args) {
+                            *value = number(arg.eval(frame)?)?;
+                        }
+
+                        function.apply(&values[..args.len()])
+                    } else {
+                        let values = args
+                            .iter()
+                            .map(|arg| number(arg.eval(frame)?))
+                            .collect::<std::result::Result<Vec<f64>, Fault>>()?;
+
+                        function.apply(&values)
+                    };
+
+                    result.map(Value::Number).ok_or(Fault::Construction(
+                        ConstructionError::InvalidNumericConstruction,
+                    ))
+                })))
+            }
+        }
+    }
+
+    fn constant(&mut self, value: DataItem) -> Operand {
+        self.constants.push(value);
+        Operand::Constant(self.constants.len() - 1)
+    }
+
+    /// Find the slot of a variable, or assign a new one if the variable wasn't referenced yet.
+    fn slot(&mut self, name: &str) -> usize {
+        match self.slots.iter().position(|slot| slot == name) {
+            Some(slot) => slot,
+            None => {
+                self.slots.push(name.to_string());
+                self.slots.len() - 1
+            }
+        }
+    }
+}
+
+/// `Frame` holds the values a `Program` is evaluated with.
+struct Frame<'a> {
+    slots: &'a [Option<&'a DataItem>],
+    constants: &'a [DataItem],
+}
+
+impl<'a> Frame<'a> {
+    /// Load the value of a slot. Variables missing in the data source are loaded as null.
+    #[inline]
+    fn load(&self, slot: usize) -> Eval<'a> {
+        Ok(self.slots[slot].map_or(Value::Null, Value::from))
+    }
+}
+
+/// `Value` is the result of evaluating a node of a `Program`. Text values are borrowed from the
+/// program's constants or the data source, whenever possible.
+enum Value<'a> {
+    Bool(bool),
+    Number(f64),
+    Text(Cow<'a, str>),
+    Date(i64),
+    /// Arrays and objects, which are only compared as a whole.
+    Nested(Cow<'a, DataItem>),
+    Null,
+}
+
+impl Value<'_> {
+    /// Take ownership of a data item, e.g. the result of a function call.
+    #[inline]
+    fn from_item(item: DataItem) -> Self {
+        match item {
+            DataItem::Bool(value) => Value::Bool(value),
+            DataItem::Number(value) => Value::Number(value),
+            DataItem::Text(value) => Value::Text(Cow::Owned(value)),
+            DataItem::Date(value) => Value::Date(value),
+            DataItem::Array(_) | DataItem::Object(_) => Value::Nested(Cow::Owned(item)),
+            DataItem::Null => Value::Null,
+        }
+    }
+}
+
+impl<'a> From<&'a DataItem> for Value<'a> {
+    #[inline]
+    fn from(item: &'a DataItem) -> Self {
+        match item {
+            DataItem::Bool(value) => Value::Bool(*value),
+            DataItem::Number(value) => Value::Number(*value),
+            DataItem::Text(value) => Value::Text(Cow::Borrowed(value)),
+            DataItem::Date(value) => Value::Date(*value),
+            DataItem::Array(_) | DataItem::Object(_) => Value::Nested(Cow::Borrowed(item)),
+            DataItem::Null => Value::Null,
+        }
+    }
+}
+
+impl From<Value<'_>> for DataItem {
+    #[inline]
+    fn from(value: Value<'_>) -> Self {
+        match value {
+            Value::Bool(value) => DataItem::Bool(value),
+            Value::Number(value) => DataItem::Number(value),
+            Value::Text(value) => DataItem::Text(value.into_owned()),
+            Value::Date(value) => DataItem::Date(value),
+            Value::Nested(value) => value.into_owned(),
+            Value::Null => DataItem::Null,
+        }
+    }
+}
+
+/// `Fault` is the error of evaluating a node of a `Program`, which is kept small, so that it's
+/// cheap to pass around between nodes.
+enum Fault {
+    Interpretation(InterpretationError),
+    Construction(ConstructionError),
+}
+
+impl From<Fault> for Error {
+    #[inline]
+    fn from(fault: Fault) -> Self {
+        match fault {
+            Fault::Interpretation(error) => Error::Interpretation(error),
+            Fault::Construction(error) => Error::Construction(error),
+        }
+    }
+}
+
+fn binary(operator: Operator, left: Operand, right: Operand) -> Node {
+    match operator {
+        Operator::And => Arc::new(move |frame: &Frame| {
+            let left = boolean(left.eval(frame)?)?;
+            Ok(Value::Bool(left && boolean(right.eval(frame)?)?))
+        }),
+        Operator::Or => Arc::new(move |frame: &Frame| {
+            let left = boolean(left.eval(frame)?)?;
+            Ok(Value::Bool(left || boolean(right.eval(frame)?)?))
+        }),
+        Operator::Equal => Arc::new(move |frame: &Frame| {
+            Ok(Value::Bool(equals(
+                &left.eval(frame)?,
+                &right.eval(frame)?,
+            )?))
+        }),
+        Operator::NotEqual => Arc::new(move |frame: &Frame| {
+            Ok(Value::Bool(!equals(
+                &left.eval(frame)?,
+                &right.eval(frame)?,
+            )?))
+        }),
+        Operator::Greater => Arc::new(move |frame: &Frame| {
+            let ordering = compare(&left.eval(frame)?, &right.eval(frame)?)?;
+            Ok(Value::Bool(matches!(ordering, Some(Ordering::Greater))))
+        }),
+        Operator::GreaterOrEqual => Arc::new(move |frame: &Frame| {
+            let ordering = compare(&left.eval(frame)?, &right.eval(frame)?)?;
+            Ok(Value::Bool(matches!(
+                ordering,
+                Some(Ordering::Greater | Ordering::Equal)
+            )))
+        }),
+        Operator::Less => Arc::new(move |frame: &Frame| {
+            let ordering = compare(&left.eval(frame)?, &right.eval(frame)?)?;
+            Ok(Value::Bool(matches!(ordering, Some(Ordering::Less))))
+        }),
+        Operator::LessOrEqual => Arc::new(move |frame: &Frame| {
+            let ordering = compare(&left.eval(frame)?, &right.eval(frame)?)?;
+            Ok(Value::Bool(matches!(
+                ordering,
+                Some(Ordering::Less | Ordering::Equal)
+            )))
+        }),
+        Operator::Sum => {
+            Arc::new(
+                move |frame: &Frame| match (left.eval(frame)?, right.eval(frame)?) {
+                    (Value::Number(left), Value::Number(right)) => Ok(Value::Number(left + right)),
+                    (Value::Text(left), Value::Text(right)) => {
+                        Ok(Value::Text(Cow::Owned(left.into_owned() + &right)))
+                    }
+                    _ => Err(Fault::Interpretation(
+                        InterpretationError::InvalidBinaryExpression,
+                    )),
+                },
+            )
+        }
+        Operator::Sub => Arc::new(move |frame: &Frame| {
+            let left = number(left.eval(frame)?)?;
+            let right = number(right.eval(frame)?)?;
+            Ok(Value::Number(left - right))
+        }),
+        Operator::Mul => Arc::new(move |frame: &Frame| {
+            let left = number(left.eval(frame)?)?;
+            let right = number(right.eval(frame)?)?;
+            Ok(Value::Number(left * right))
+        }),
+        Operator::Div => Arc::new(move |frame: &Frame| {
+            let left = number(left.eval(frame)?)?;
+            let right = number(right.eval(frame)?)?;
+            divide(left, right)
+                .map(Value::Number)
+                .map_err(Fault::Interpretation)
+        }),
+        Operator::Mod => Arc::new(move |frame: &Frame| {
+            let left = number(left.eval(frame)?)?;
+            let right = number(right.eval(frame)?)?;
+            modulo(left, right)
+                .map(Value::Number)
+                .map_err(Fault::Interpretation)
+        }),
+        Operator::Pow => Arc::new(move |frame: &Frame| {
+            let left = number(left.eval(frame)?)?;
+            let right = number(right.eval(frame)?)?;
+            Ok(Value::Number(left.powf(right)))
+        }),
+        Operator::Not => Arc::new(|_: &Frame| {
+            Err(Fault::Interpretation(
+                InterpretationError::InvalidBinaryExpression,
+            ))
+        }),
+    }
+}
+
+fn unary(operator: Operator, root: Operand) -> Node {
+    match operator {
+        Operator::Not => {
+            Arc::new(move |frame: &Frame| Ok(Value::Bool(!boolean(root.eval(frame)?)?)))
+        }
+        Operator::Sum => {
+            Arc::new(move |frame: &Frame| Ok(Value::Number(number(root.eval(frame)?)?)))
+        }
+        Operator::Sub => {
+            Arc::new(move |frame: &Frame| Ok(Value::Number(-number(root.eval(frame)?)?)))
+        }
+        _ => Arc::new(|_: &Frame| {
+            Err(Fault::Interpretation(
+                InterpretationError::InvalidExpression,
+            ))
+        }),
+    }
+}
+
+#[inline]
+fn boolean(value: Value) -> std::result::Result<bool, Fault> {
+    match value {
+        Value::Bool(value) => Ok(value),
+        _ => Err(Fault::Construction(
+            ConstructionError::InvalidBooleanConstruction,
+        )),
+    }
+}
+
+#[inline]
+fn number(value: Value) -> std::result::Result<f64, Fault> {
+    match value {
+        Value::Number(value) => Ok(value),
+        _ => Err(Fault::Construction(
+            ConstructionError::InvalidNumericConstruction,
+        )),
+    }
+}
+
+#[inline]
+fn equals(left: &Value, right: &Value) -> std::result::Result<bool, Fault> {
+    match (left, right) {
+        (Value::Bool(left), Value::Bool(right)) => Ok(left == right),
+        (Value::Number(left), Value::Number(right)) => Ok((left - right).abs() < f64::EPSILON),
+        (Value::Text(left), Value::Text(right)) => Ok(left == right),
+        (Value::Date(left), Value::Date(right)) => Ok(left == right),
+        (Value::Nested(left), Value::Nested(right)) => Ok(left == right),
+        (Value::Null, value) | (value, Value::Null) => Ok(matches!(value, Value::Null)),
+        _ => Err(Fault::Interpretation(
+            InterpretationError::InvalidBinaryExpression,
+        )),
+    }
+}
+
+#[inline]
+fn compare(left: &Value, right: &Value) -> std::result::Result<Option<Ordering>, Fault> {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => Ok(left.partial_cmp(right)),
+        (Value::Text(left), Value::Text(right)) => Ok(Some(left.cmp(right))),
+        (Value::Date(left), Value::Date(right)) => Ok(Some(left.cmp(right))),
+        _ => Err(Fault::Interpretation(
+            InterpretationError::InvalidBinaryExpression,
+        )),
+    }
+}
+
+/// Check if an expression doesn't reference any variable, nor calls a non-deterministic function,
+/// e.g. `now()`, so that it can be folded.
+fn is_constant(expression: &Expression) -> bool {
+    match expression {
+        Expression::Atom(symbol) => !matches!(symbol, Symbol::Variable(_)),
+        Expression::Cons(cons) => match cons.as_ref() {
+            Cons::Binary(_, (left, right)) => is_constant(left) && is_constant(right),
+            Cons::Unary(_, root) => is_constant(root),
+            Cons::Conditional(condition, (then, otherwise)) => {
+                is_constant(condition) && is_constant(then) && is_constant(otherwise)
+            }
+        },
+        Expression::Call(call) => {
+            call.function.is_deterministic() && call.args.iter().all(is_constant)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::compile::Program;
+    use crate::data::DataItem;
+    use crate::expr::Interpretable;
+    use crate::vars::Variables;
+    use crate::PredicateParser;
+
+    fn compile(input: &str) -> Program {
+        Program::compile(&PredicateParser::new(input).parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn resolves_variables_to_slots() {
+        let program = compile("(a > 1) && (a < 4) && (b != 3)");
+        assert_eq!(program.slots(), &["a".to_string(), "b".to_string()]);
+        assert_eq!(
+            program.constants,
+            vec![
+                DataItem::Number(1.0),
+                DataItem::Number(4.0),
+                DataItem::Number(3.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn folds_constants() {
+        let program = compile("1 + 2 * 3");
+        assert_eq!(program.constants, vec![DataItem::Number(7.0)]);
+        assert!(program.slots().is_empty());
+
+        let program = compile("a * (sqrt(16) - 1)");
+        assert_eq!(program.constants, vec![DataItem::Number(3.0)]);
+        assert_eq!(program.slots(), &["a".to_string()]);
+
+        let program = compile("1 > 2 ? a : b");
+        assert!(program.constants.is_empty());
+        assert_eq!(program.slots(), &["b".to_string()]);
+
+        let program = compile("'a' + 1");
+        assert_eq!(
+            program.constants,
+            vec![DataItem::Text("a".to_string()), DataItem::Number(1.0)]
+        );
+    }
+
+    #[test]
+    fn evaluates_like_interpreter() {
+        let vars = Variables::from_pairs(vec![
+            ("a", 3.0.into()),
+            ("b", true.into()),
+            ("c", "fruit".into()),
+            ("d", DataItem::Date(1_709_296_200_250)),
+            (
+                "e",
+                DataItem::Object(BTreeMap::from([(
+                    "f".to_string(),
+                    DataItem::Array(vec![1.0.into(), "g".into()]),
+                )])),
+            ),
+        ]);
+
+        for input in [
+            "(a + 1) / (a * 4) - (a + 2)",
+            "-a + +a * 2",
+            "a > 1 && a < 4 && a != 3",
+            "!b || a == 3",
+            "c == 'fruit' && c >= 'apple'",
+            "c + 's'",
+            "b ? c : 'none'",
+            "a > 5 ? 1 : a > 2 ? 2 : 3",
+            "round(pow(a, 2) / 7, 1) + min(a, 1, 2)",
+            "max(a, 1, 2, 3, 4, a * 2)",
+            "a % 2 + 2 ** a ** 2 - -a ** 2",
+            "isValid(a) && isValid(missing) == false && missing == null",
+            "b ? null : a",
+            "year(d) * 100 + month(d) + date(d) + hours(d)",
+            "d > datetime(2024, 0) && d != datetime(2024, 2, 1)",
+            "datetime(year(d), month(d) + 1, date(d), 0, 0, 0, a)",
+            "datetime(1e18, 0) == null && datetime(2024, 0, 1, a * 1e18) == null",
+            "e.f[0] + a > 3 && e.f[1] == 'g' && e == e",
+            "e.f[2] == null ? e : null",
+        ] {
+            let expression = PredicateParser::new(input).parse().unwrap();
+            let expected: DataItem = expression.interpret(&vars).unwrap();
+            let program = Program::compile(&expression).unwrap();
+
+            assert_eq!(program.evaluate(&vars).unwrap(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn evaluates_only_chosen_branch() {
+        let program = compile("a > 2 ? a : missing");
+        let result: f64 = program
+            .interpret(&Variables::from_pairs(vec![("a", 3.0.into())]))
+            .unwrap();
+        assert_eq!(result, 3.0);
+    }
+
+    #[test]
+    fn evaluates_short_circuit_operators() {
+        let vars = Variables::from_pairs(vec![("a", DataItem::Null)]);
+
+        let result: bool = compile("isValid(a) && a > 2").interpret(&vars).unwrap();
+        assert!(!result);
+
+        let result: bool = compile("a == null || a > 2").interpret(&vars).unwrap();
+        assert!(result);
+
+        assert!(compile("a > 2 || true").evaluate(&vars).is_err());
+    }
+
+    #[test]
+    fn doesnt_fold_non_deterministic_calls() {
+        let program = compile("now()");
+        let result = program.evaluate(&Variables::new()).unwrap();
+        assert!(result.get_date().is_some_and(|date| *date > 0));
+        assert!(program.constants.is_empty());
+    }
+
+    #[test]
+    fn evaluates_many_slots() {
+        let program = compile("a + b + c + d + e + f + g + h + i + j");
+        let vars = Variables::from_pairs(
+            ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]
+                .into_iter()
+                .map(|name| (name, 1.0.into()))
+                .collect(),
+        );
+
+        assert_eq!(program.evaluate(&vars).unwrap(), DataItem::Number(10.0));
+    }
+
+    #[test]
+    fn doesnt_evaluate_invalid_programs() {
+        let vars = Variables::from_pairs(vec![("a", 3.0.into()), ("b", true.into())]);
+
+        assert!(compile("a + b").evaluate(&vars).is_err());
+        assert!(compile("a + missing").evaluate(&vars).is_err());
+        assert!(compile("b ? 1 : 'a' + 1").evaluate(&vars).is_ok());
+        assert!(compile("a ? 1 : 2").evaluate(&vars).is_err());
+        assert!(compile("sqrt(b)").evaluate(&vars).is_err());
+        assert!(compile("a / (a - 3)").evaluate(&vars).is_err());
+        assert!(compile("1 % 0").evaluate(&vars).is_err());
+
+        let result: Result<bool, _> = compile("a * 2").interpret(&vars);
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,13 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
 
+use crate::date;
+
 pub trait DataSource {
+    /// Find the item of a field. The key can be a path to a nested item, e.g. `geo.lat` or
+    /// `tags[0]`, see `path::resolve`.
     fn get(&self, key: &str) -> Option<&DataItem>;
 }
 
@@ -11,12 +16,19 @@ pub trait DataSource {
 #[cfg_attr(feature = "serde", serde(untagged))]
 pub enum DataItem {
     Bool(bool),
-    Number(f32),
+    Number(f64),
     Text(String),
+    /// Timestamp in milliseconds since the Unix epoch, in UTC. Dates are parsed out of text
+    /// values explicitly, so that they are never deserialized directly.
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+    Date(i64),
+    Array(Vec<DataItem>),
+    Object(BTreeMap<String, DataItem>),
+    Null,
 }
 
 impl DataItem {
-    pub fn get_number(&self) -> Option<&f32> {
+    pub fn get_number(&self) -> Option<&f64> {
         if let DataItem::Number(value) = &self {
             Some(value)
         } else {
@@ -39,6 +51,43 @@ impl DataItem {
             None
         }
     }
+
+    pub fn get_date(&self) -> Option<&i64> {
+        if let DataItem::Date(value) = &self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_array(&self) -> Option<&Vec<DataItem>> {
+        if let DataItem::Array(value) = &self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_object(&self) -> Option<&BTreeMap<String, DataItem>> {
+        if let DataItem::Object(value) = &self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, DataItem::Null)
+    }
+
+    /// Check if the item holds a valid value, i.e. it's neither null nor a `NaN` number.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            DataItem::Number(value) => !value.is_nan(),
+            DataItem::Null => false,
+            _ => true,
+        }
+    }
 }
 
 impl Display for DataItem {
@@ -47,6 +96,27 @@ impl Display for DataItem {
             DataItem::Bool(value) => write!(f, "{value}"),
             DataItem::Number(value) => write!(f, "{value}"),
             DataItem::Text(value) => write!(f, "{value}"),
+            DataItem::Date(value) => write!(f, "{}", date::format(*value)),
+            DataItem::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>();
+                write!(f, "[{}]", items.join(", "))
+            }
+            DataItem::Object(object) => {
+                let pairs = object
+                    .iter()
+                    .map(|(key, item)| format!("\"{key}\": {item}"))
+                    .collect::<Vec<String>>();
+
+                if pairs.is_empty() {
+                    write!(f, "{{}}")
+                } else {
+                    write!(f, "{{ {} }}", pairs.join(", "))
+                }
+            }
+            DataItem::Null => write!(f, "null"),
         }
     }
 }
@@ -57,6 +127,10 @@ impl Hash for DataItem {
             DataItem::Bool(value) => hasher.write_i8(i8::from(*value)),
             DataItem::Number(value) => hasher.write(&value.to_be_bytes()),
             DataItem::Text(value) => value.hash(hasher),
+            DataItem::Date(value) => hasher.write_i64(*value),
+            DataItem::Array(items) => items.hash(hasher),
+            DataItem::Object(object) => object.hash(hasher),
+            DataItem::Null => hasher.write_u8(0),
         }
     }
 }
@@ -85,6 +159,28 @@ impl PartialEq for DataItem {
                     false
                 }
             }
+            DataItem::Date(value) => {
+                if let DataItem::Date(other_value) = other {
+                    value == other_value
+                } else {
+                    false
+                }
+            }
+            DataItem::Array(items) => {
+                if let DataItem::Array(other_items) = other {
+                    items == other_items
+                } else {
+                    false
+                }
+            }
+            DataItem::Object(object) => {
+                if let DataItem::Object(other_object) = other {
+                    object == other_object
+                } else {
+                    false
+                }
+            }
+            DataItem::Null => other.is_null(),
         }
     }
 }
@@ -97,8 +193,8 @@ impl From<bool> for DataItem {
     }
 }
 
-impl From<f32> for DataItem {
-    fn from(value: f32) -> Self {
+impl From<f64> for DataItem {
+    fn from(value: f64) -> Self {
         DataItem::Number(value)
     }
 }
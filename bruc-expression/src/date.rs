@@ -0,0 +1,373 @@
+const MILLIS_PER_SECOND: i64 = 1000;
+const MILLIS_PER_MINUTE: i64 = 60 * MILLIS_PER_SECOND;
+const MILLIS_PER_HOUR: i64 = 60 * MILLIS_PER_MINUTE;
+const MILLIS_PER_DAY: i64 = 24 * MILLIS_PER_HOUR;
+
+/// `DateTime` splits a timestamp, in milliseconds since the Unix epoch, into its UTC calendar
+/// parts. Months are zero-based, while days start at `1`.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: i64,
+    pub day: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    pub seconds: i64,
+    pub millis: i64,
+}
+
+impl DateTime {
+    /// Split a timestamp, in milliseconds since the Unix epoch, into its calendar parts.
+    pub fn from_timestamp(timestamp: i64) -> Self {
+        let days = timestamp.div_euclid(MILLIS_PER_DAY);
+        let time = timestamp.rem_euclid(MILLIS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+
+        DateTime {
+            year,
+            month: month - 1,
+            day,
+            hours: time / MILLIS_PER_HOUR,
+            minutes: time % MILLIS_PER_HOUR / MILLIS_PER_MINUTE,
+            seconds: time % MILLIS_PER_MINUTE / MILLIS_PER_SECOND,
+            millis: time % MILLIS_PER_SECOND,
+        }
+    }
+
+    /// Join the calendar parts into a timestamp, in milliseconds since the Unix epoch. Parts out
+    /// of their range overflow into the next part, e.g. month `12` is January of the next year.
+    /// No timestamp is returned, if the date doesn't fit in a timestamp.
+    pub fn timestamp(&self) -> Option<i64> {
+        let year = self.year.checked_add(self.month.div_euclid(12))?;
+        let month = self.month.rem_euclid(12) + 1;
+        let days = days_from_civil(year, month, 1)?
+            .checked_add(self.day)?
+            .checked_sub(1)?;
+
+        [
+            (days, MILLIS_PER_DAY),
+            (self.hours, MILLIS_PER_HOUR),
+            (self.minutes, MILLIS_PER_MINUTE),
+            (self.seconds, MILLIS_PER_SECOND),
+            (self.millis, 1),
+        ]
+        .into_iter()
+        .try_fold(0i64, |timestamp, (part, millis)| {
+            timestamp.checked_add(part.checked_mul(millis)?)
+        })
+    }
+}
+
+/// Parse an ISO-8601 date, e.g. `2024-03-01`, `2024-03-01T12:30:00Z` or
+/// `2024-03-01 12:30:00.250+02:00`, into milliseconds since the Unix epoch. Dates without a time
+/// zone offset are read as UTC.
+pub fn parse(text: &str) -> Option<i64> {
+    let mut reader = Reader::new(text);
+
+    let year = reader.digits(4)?;
+    reader.expect(b'-')?;
+    let month = reader.digits(2)?;
+    reader.expect(b'-')?;
+    let day = reader.digits(2)?;
+
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let mut date = DateTime {
+        year,
+        month: month - 1,
+        day,
+        hours: 0,
+        minutes: 0,
+        seconds: 0,
+        millis: 0,
+    };
+    let mut offset = 0;
+
+    if reader.eat(b'T') || reader.eat(b' ') {
+        date.hours = reader.digits(2)?;
+        reader.expect(b':')?;
+        date.minutes = reader.digits(2)?;
+
+        if reader.eat(b':') {
+            date.seconds = reader.digits(2)?;
+
+            if reader.eat(b'.') {
+                date.millis = reader.fraction()?;
+            }
+        }
+
+        if date.hours > 23 || date.minutes > 59 || date.seconds > 59 {
+            return None;
+        }
+
+        offset = reader.offset()?;
+    }
+
+    reader.end()?;
+
+    date.timestamp()?.checked_sub(offset)
+}
+
+/// Format a timestamp, in milliseconds since the Unix epoch, as an ISO-8601 date in UTC.
+pub fn format(timestamp: i64) -> String {
+    let date = DateTime::from_timestamp(timestamp);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        date.year,
+        date.month + 1,
+        date.day,
+        date.hours,
+        date.minutes,
+        date.seconds,
+        date.millis
+    )
+}
+
+/// Current time, in milliseconds since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
+
+/// Current time, in milliseconds since the Unix epoch.
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> i64 {
+    js_sys::Date::now() as i64
+}
+
+/// Amount of days since the Unix epoch of a certain date in the proleptic Gregorian calendar. No
+/// amount is returned, if the year is too far from the epoch. The `month` and `day` are expected
+/// to be in their range.
+fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    let year = if month <= 2 {
+        year.checked_sub(1)?
+    } else {
+        year
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era.checked_mul(146_097)?.checked_add(day_of_era - 719_468)
+}
+
+/// Date in the proleptic Gregorian calendar of a certain amount of days since the Unix epoch.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_of_year = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_of_year + 2) / 5 + 1;
+    let month = if month_of_year < 10 {
+        month_of_year + 3
+    } else {
+        month_of_year - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// `Reader` consumes the bytes of a date's text.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(text: &'a str) -> Self {
+        Reader {
+            bytes: text.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.eat(byte).then_some(())
+    }
+
+    fn end(&self) -> Option<()> {
+        (self.position == self.bytes.len()).then_some(())
+    }
+
+    /// Read a number of exactly `count` digits.
+    fn digits(&mut self, count: usize) -> Option<i64> {
+        let mut value = 0;
+        for _ in 0..count {
+            let digit = self.peek().filter(u8::is_ascii_digit)?;
+            value = value * 10 + i64::from(digit - b'0');
+            self.position += 1;
+        }
+
+        Some(value)
+    }
+
+    /// Read the fraction of a second as milliseconds. Digits beyond milliseconds are truncated.
+    fn fraction(&mut self) -> Option<i64> {
+        let start = self.position;
+        let mut millis = 0;
+
+        while let Some(digit) = self.peek().filter(u8::is_ascii_digit) {
+            if self.position - start < 3 {
+                millis = millis * 10 + i64::from(digit - b'0');
+            }
+            self.position += 1;
+        }
+
+        match self.position - start {
+            0 => None,
+            length if length < 3 => Some(millis * 10_i64.pow(3 - length as u32)),
+            _ => Some(millis),
+        }
+    }
+
+    /// Read the time zone offset as milliseconds. A missing offset is read as UTC.
+    fn offset(&mut self) -> Option<i64> {
+        let sign = match self.peek() {
+            Some(b'Z') => {
+                self.position += 1;
+                return Some(0);
+            }
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return Some(0),
+        };
+        self.position += 1;
+
+        let hours = self.digits(2)?;
+        let minutes = if self.eat(b':') || self.peek().is_some() {
+            self.digits(2)?
+        } else {
+            0
+        };
+
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+
+        Some(sign * (hours * MILLIS_PER_HOUR + minutes * MILLIS_PER_MINUTE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::date::{format, parse, DateTime};
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(parse("1970-01-01"), Some(0));
+        assert_eq!(parse("2024-02-29"), Some(1_709_164_800_000));
+        assert_eq!(parse("2024-03-01T12:30:00Z"), Some(1_709_296_200_000));
+        assert_eq!(parse("2024-03-01T12:30Z"), Some(1_709_296_200_000));
+        assert_eq!(parse("2024-03-01 12:30:00.25"), Some(1_709_296_200_250));
+        assert_eq!(parse("2024-03-01T14:30:00+02:00"), Some(1_709_296_200_000));
+        assert_eq!(parse("2024-03-01T10:00:00-0230"), Some(1_709_296_200_000));
+        assert_eq!(parse("1969-12-31T23:59:59.999Z"), Some(-1));
+    }
+
+    #[test]
+    fn doesnt_parse_invalid_dates() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("2024"), None);
+        assert_eq!(parse("2023-02-29"), None);
+        assert_eq!(parse("2024-13-01"), None);
+        assert_eq!(parse("2024-03-01T24:00"), None);
+        assert_eq!(parse("2024-03-01T12"), None);
+        assert_eq!(parse("2024-03-01T12:30:00.Z"), None);
+        assert_eq!(parse("2024-03-01T12:30:00+2"), None);
+        assert_eq!(parse("2024-03-01 and more"), None);
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format(1_709_296_200_250), "2024-03-01T12:30:00.250Z");
+        assert_eq!(format(-1), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn splits_and_joins_timestamps() {
+        let date = DateTime::from_timestamp(1_709_296_200_250);
+        assert_eq!(
+            date,
+            DateTime {
+                year: 2024,
+                month: 2,
+                day: 1,
+                hours: 12,
+                minutes: 30,
+                seconds: 0,
+                millis: 250
+            }
+        );
+        assert_eq!(date.timestamp(), Some(1_709_296_200_250));
+
+        let overflow = DateTime {
+            year: 2023,
+            month: 12,
+            day: 32,
+            hours: 0,
+            minutes: 0,
+            seconds: 0,
+            millis: 0,
+        };
+        assert_eq!(overflow.timestamp(), parse("2024-02-01"));
+
+        let year = DateTime {
+            year: 1_000_000_000_000_000_000,
+            ..overflow
+        };
+        assert_eq!(year.timestamp(), None);
+
+        let hours = DateTime {
+            year: 2024,
+            month: 0,
+            day: 1,
+            hours: 1_000_000_000_000_000_000,
+            ..overflow
+        };
+        assert_eq!(hours.timestamp(), None);
+        assert_eq!(
+            DateTime {
+                hours: -10_000_000_000_000_000,
+                ..hours
+            }
+            .timestamp(),
+            None
+        );
+    }
+}
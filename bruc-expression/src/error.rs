@@ -1,12 +1,17 @@
 use std::{error, fmt, result};
 
+use crate::function::Function;
+use crate::symbols::{Operator, Span, Symbol};
+use crate::types::DataType;
+
 pub type Result<T> = result::Result<T, Error>;
 
 #[derive(Debug)]
 pub enum Error {
     Interpretation(InterpretationError),
     Construction(ConstructionError),
-    Parse(ParseError),
+    Parse(ParseError, Location),
+    Type(TypeError),
 }
 
 #[derive(Debug)]
@@ -14,18 +19,69 @@ pub enum InterpretationError {
     InvalidBinaryExpression,
     InvalidBooleanExpression,
     InvalidNumericExpression,
+    InvalidTextExpression,
+    InvalidExpression,
+    DivisionByZero,
 }
 
 #[derive(Debug)]
 pub enum ConstructionError {
     InvalidBooleanConstruction,
     InvalidNumericConstruction,
+    InvalidTextConstruction,
+}
+
+/// `TypeError` collects the type mismatches found when checking an expression against a schema.
+#[derive(Debug)]
+pub enum TypeError {
+    UnknownVariable(String),
+    InvalidAtom(Symbol),
+    InvalidOperands(Operator, DataType, DataType),
+    InvalidOperand(Operator, DataType),
+    InvalidArgument(Function, DataType),
+    InvalidCondition(DataType),
+    MismatchedBranches(DataType, DataType),
+    UnexpectedType(DataType, DataType),
+}
+
+/// `Expected` hints which kind of token was expected in the place where a parse error was found.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Expected {
+    Expression,
+    Operator,
+    Close,
+    Colon,
+    CommaOrClose,
+}
+
+/// `Location` points to the place of an expression's source text where a parse error was found.
+/// `token` is the offending token, if any, and `expected` hints what was expected instead.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Location {
+    pub source: String,
+    pub span: Span,
+    pub token: Option<String>,
+    pub expected: Option<Expected>,
+}
+
+impl Location {
+    pub fn new(source: &str, span: Span, token: Option<&str>, expected: Option<Expected>) -> Self {
+        Location {
+            source: source.to_string(),
+            span,
+            token: token.map(str::to_string),
+            expected,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum ParseError {
     InvalidExpression,
+    InvalidToken,
     BindingPowerMissing,
+    UnknownFunction(String),
+    InvalidArguments(Function, usize),
 }
 
 impl fmt::Display for Error {
@@ -41,6 +97,15 @@ impl fmt::Display for Error {
                 InterpretationError::InvalidNumericExpression => {
                     write!(f, "InterpretationError::InvalidNumericExpression")
                 }
+                InterpretationError::InvalidTextExpression => {
+                    write!(f, "InterpretationError::InvalidTextExpression")
+                }
+                InterpretationError::InvalidExpression => {
+                    write!(f, "InterpretationError::InvalidExpression")
+                }
+                InterpretationError::DivisionByZero => {
+                    write!(f, "InterpretationError::DivisionByZero")
+                }
             },
             Error::Construction(error) => match error {
                 ConstructionError::InvalidBooleanConstruction => {
@@ -49,13 +114,114 @@ impl fmt::Display for Error {
                 ConstructionError::InvalidNumericConstruction => {
                     write!(f, "ConstructionError::InvalidNumericConstruction")
                 }
+                ConstructionError::InvalidTextConstruction => {
+                    write!(f, "ConstructionError::InvalidTextConstruction")
+                }
             },
-            Error::Parse(error) => match error {
-                ParseError::InvalidExpression => write!(f, "ParseError::InvalidExpression"),
-                ParseError::BindingPowerMissing => write!(f, "ParseError::BindingPowerMissing"),
+            Error::Parse(error, location) => write!(f, "{error}{location}"),
+            Error::Type(error) => match error {
+                TypeError::UnknownVariable(name) => {
+                    write!(f, "TypeError::UnknownVariable: {name}")
+                }
+                TypeError::InvalidAtom(symbol) => write!(f, "TypeError::InvalidAtom: {symbol}"),
+                TypeError::InvalidOperands(operator, left, right) => {
+                    write!(f, "TypeError::InvalidOperands: {left} {operator} {right}")
+                }
+                TypeError::InvalidOperand(operator, root) => {
+                    write!(f, "TypeError::InvalidOperand: {operator}{root}")
+                }
+                TypeError::InvalidArgument(function, arg) => match function.parameter() {
+                    Some(expected) => write!(
+                        f,
+                        "TypeError::InvalidArgument: {function} expects {expected}, found {arg}"
+                    ),
+                    None => write!(f, "TypeError::InvalidArgument: {function} found {arg}"),
+                },
+                TypeError::InvalidCondition(condition) => {
+                    write!(
+                        f,
+                        "TypeError::InvalidCondition: expected bool, found {condition}"
+                    )
+                }
+                TypeError::MismatchedBranches(then, otherwise) => {
+                    write!(f, "TypeError::MismatchedBranches: {then} and {otherwise}")
+                }
+                TypeError::UnexpectedType(expected, found) => {
+                    write!(
+                        f,
+                        "TypeError::UnexpectedType: expected {expected}, found {found}"
+                    )
+                }
             },
         }
     }
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidExpression => write!(f, "ParseError::InvalidExpression"),
+            ParseError::InvalidToken => write!(f, "ParseError::InvalidToken"),
+            ParseError::BindingPowerMissing => write!(f, "ParseError::BindingPowerMissing"),
+            ParseError::UnknownFunction(name) => {
+                write!(f, "ParseError::UnknownFunction: {name}")
+            }
+            ParseError::InvalidArguments(function, count) => write!(
+                f,
+                "ParseError::InvalidArguments: {function} expects {} arguments, found {count}",
+                function.arity()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Expression => write!(f, "an expression"),
+            Expected::Operator => write!(f, "an operator"),
+            Expected::Close => write!(f, "`)`"),
+            Expected::Colon => write!(f, "`:`"),
+            Expected::CommaOrClose => write!(f, "`,` or `)`"),
+        }
+    }
+}
+
+/// Render the location as the line and column of the error, followed by the source line and a
+/// caret under the offending span.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = self.span.start.min(self.source.len());
+        let end = self.span.end.clamp(start, self.source.len());
+
+        let line_start = self.source[..start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let line_end = self.source[start..]
+            .find('\n')
+            .map_or(self.source.len(), |index| start + index);
+        let line_number = self.source[..start].matches('\n').count() + 1;
+        let column = self.source[line_start..start].chars().count();
+        let width = self.source[start..end.min(line_end)].chars().count().max(1);
+
+        write!(f, " at {line_number}:{}", column + 1)?;
+        match &self.token {
+            Some(token) => write!(f, ": unexpected `{token}`")?,
+            None if start == self.source.len() => write!(f, ": unexpected end of input")?,
+            None => {}
+        }
+        if let Some(expected) = &self.expected {
+            write!(f, ", expected {expected}")?;
+        }
+
+        write!(
+            f,
+            "\n{}\n{}{}",
+            &self.source[line_start..line_end],
+            " ".repeat(column),
+            "^".repeat(width)
+        )
+    }
+}
+
 impl error::Error for Error {}
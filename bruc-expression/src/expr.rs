@@ -1,8 +1,12 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::fmt;
 
 use crate::data::{DataItem, DataSource};
 use crate::error::{ConstructionError, Error, InterpretationError, Result};
+use crate::function::Function;
 use crate::symbols::{Operator, Symbol};
+use crate::types::DataType;
 
 pub trait Interpretable<T> {
     fn interpret(&self, variables: &impl DataSource) -> Result<T>;
@@ -12,6 +16,7 @@ pub trait Interpretable<T> {
 pub enum Cons {
     Binary(Operator, (Expression, Expression)),
     Unary(Operator, Expression),
+    Conditional(Expression, (Expression, Expression)),
 }
 
 impl fmt::Display for Cons {
@@ -19,6 +24,9 @@ impl fmt::Display for Cons {
         match self {
             Cons::Binary(operator, (left, right)) => write!(f, "({operator} {left} {right})"),
             Cons::Unary(operator, root) => write!(f, "({operator} {root})"),
+            Cons::Conditional(condition, (then, otherwise)) => {
+                write!(f, "(? {condition} {then} {otherwise})")
+            }
         }
     }
 }
@@ -28,67 +36,54 @@ impl Interpretable<bool> for Cons {
     fn interpret(&self, source: &impl DataSource) -> Result<bool> {
         match self {
             Cons::Binary(operator, (left, right)) => match operator {
+                // Boolean operators short-circuit, so that the right operand can be guarded by
+                // the left one, e.g. in `isValid(a) && a > 2`
                 Operator::And => {
                     let left_value: bool = left.interpret(source)?;
-                    let right_value: bool = right.interpret(source)?;
+                    if !left_value {
+                        return Ok(false);
+                    }
 
-                    Ok(left_value && right_value)
+                    right.interpret(source)
                 }
                 Operator::Or => {
                     let left_value: bool = left.interpret(source)?;
-                    let right_value: bool = right.interpret(source)?;
+                    if left_value {
+                        return Ok(true);
+                    }
 
-                    Ok(left_value || right_value)
+                    right.interpret(source)
                 }
                 Operator::Equal => {
-                    let left_bool: Result<bool> = left.interpret(source);
-                    if let Ok(left_value) = left_bool {
-                        let right_value: bool = right.interpret(source)?;
+                    let left_value: DataItem = left.interpret(source)?;
+                    let right_value: DataItem = right.interpret(source)?;
 
-                        Ok(left_value == right_value)
-                    } else {
-                        let left_value: f32 = left.interpret(source)?;
-                        let right_value: f32 = right.interpret(source)?;
-
-                        Ok((left_value - right_value).abs() < f32::EPSILON)
-                    }
+                    equals(&left_value, &right_value)
                 }
                 Operator::NotEqual => {
-                    let left_bool: Result<bool> = left.interpret(source);
-                    if let Ok(left_value) = left_bool {
-                        let right_value: bool = right.interpret(source)?;
+                    let left_value: DataItem = left.interpret(source)?;
+                    let right_value: DataItem = right.interpret(source)?;
 
-                        Ok(left_value != right_value)
-                    } else {
-                        let left_value: f32 = left.interpret(source)?;
-                        let right_value: f32 = right.interpret(source)?;
-
-                        Ok((left_value - right_value).abs() > f32::EPSILON)
-                    }
+                    equals(&left_value, &right_value).map(|equal| !equal)
                 }
                 Operator::Greater => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
-
-                    Ok(left_value > right_value)
+                    let ordering = compare(left, right, source)?;
+                    Ok(matches!(ordering, Some(Ordering::Greater)))
                 }
                 Operator::GreaterOrEqual => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
-
-                    Ok(left_value >= right_value)
+                    let ordering = compare(left, right, source)?;
+                    Ok(matches!(
+                        ordering,
+                        Some(Ordering::Greater | Ordering::Equal)
+                    ))
                 }
                 Operator::Less => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
-
-                    Ok(left_value < right_value)
+                    let ordering = compare(left, right, source)?;
+                    Ok(matches!(ordering, Some(Ordering::Less)))
                 }
                 Operator::LessOrEqual => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
-
-                    Ok(left_value <= right_value)
+                    let ordering = compare(left, right, source)?;
+                    Ok(matches!(ordering, Some(Ordering::Less | Ordering::Equal)))
                 }
                 _ => Err(Error::Construction(
                     ConstructionError::InvalidBooleanConstruction,
@@ -100,49 +95,320 @@ impl Interpretable<bool> for Cons {
                     ConstructionError::InvalidBooleanConstruction,
                 )),
             },
+            Cons::Conditional(condition, (then, otherwise)) => {
+                conditional(condition, then, otherwise, source)
+            }
         }
     }
 }
 
-impl Interpretable<f32> for Cons {
+impl Interpretable<String> for Cons {
     #[inline]
-    fn interpret(&self, source: &impl DataSource) -> Result<f32> {
+    fn interpret(&self, source: &impl DataSource) -> Result<String> {
+        match self {
+            Cons::Binary(Operator::Sum, (left, right)) => {
+                let left_value: String = left.interpret(source)?;
+                let right_value: String = right.interpret(source)?;
+
+                Ok(left_value + &right_value)
+            }
+            Cons::Conditional(condition, (then, otherwise)) => {
+                conditional(condition, then, otherwise, source)
+            }
+            _ => Err(Error::Construction(
+                ConstructionError::InvalidTextConstruction,
+            )),
+        }
+    }
+}
+
+/// Interpret a conditional expression. Only the branch chosen by the condition is interpreted.
+#[inline]
+fn conditional<T>(
+    condition: &Expression,
+    then: &Expression,
+    otherwise: &Expression,
+    source: &impl DataSource,
+) -> Result<T>
+where
+    Expression: Interpretable<T>,
+{
+    let condition_value = Interpretable::<bool>::interpret(condition, source)?;
+
+    if condition_value {
+        then.interpret(source)
+    } else {
+        otherwise.interpret(source)
+    }
+}
+
+/// Check if two items are equal. Numbers are equal within `f64::EPSILON`, and null is only equal
+/// to null, while items of any other different types can't be compared.
+#[inline]
+fn equals(left: &DataItem, right: &DataItem) -> Result<bool> {
+    match (left, right) {
+        (DataItem::Bool(left), DataItem::Bool(right)) => Ok(left == right),
+        (DataItem::Number(left), DataItem::Number(right)) => {
+            Ok((left - right).abs() < f64::EPSILON)
+        }
+        (DataItem::Text(left), DataItem::Text(right)) => Ok(left == right),
+        (DataItem::Date(left), DataItem::Date(right)) => Ok(left == right),
+        (DataItem::Array(_) | DataItem::Object(_), DataItem::Array(_) | DataItem::Object(_)) => {
+            Ok(left == right)
+        }
+        (DataItem::Null, item) | (item, DataItem::Null) => Ok(item.is_null()),
+        _ => Err(Error::Interpretation(
+            InterpretationError::InvalidBinaryExpression,
+        )),
+    }
+}
+
+/// Compare two expressions, numerically, chronologically in case both are interpreted as dates,
+/// or lexicographically in case both are interpreted as text.
+#[inline]
+fn compare(
+    left: &Expression,
+    right: &Expression,
+    source: &impl DataSource,
+) -> Result<Option<Ordering>> {
+    let left_number: Result<f64> = left.interpret(source);
+
+    match left_number {
+        Ok(left_value) => {
+            let right_value: f64 = right.interpret(source)?;
+            Ok(left_value.partial_cmp(&right_value))
+        }
+        Err(error) => {
+            let left_value: DataItem = left.interpret(source)?;
+            let right_value: DataItem = right.interpret(source)?;
+
+            match (left_value, right_value) {
+                (DataItem::Text(left_value), DataItem::Text(right_value)) => {
+                    Ok(Some(left_value.cmp(&right_value)))
+                }
+                (DataItem::Date(left_value), DataItem::Date(right_value)) => {
+                    Ok(Some(left_value.cmp(&right_value)))
+                }
+                _ => Err(error),
+            }
+        }
+    }
+}
+
+/// Divide two numbers. Dividing by zero is an error, instead of an infinite or `NaN` result.
+#[inline]
+pub(crate) fn divide(left: f64, right: f64) -> std::result::Result<f64, InterpretationError> {
+    if right == 0.0 {
+        return Err(InterpretationError::DivisionByZero);
+    }
+
+    Ok(left / right)
+}
+
+/// Remainder of dividing two numbers, with the sign of the dividend. As with `divide`, dividing
+/// by zero is an error.
+#[inline]
+pub(crate) fn modulo(left: f64, right: f64) -> std::result::Result<f64, InterpretationError> {
+    if right == 0.0 {
+        return Err(InterpretationError::DivisionByZero);
+    }
+
+    Ok(left % right)
+}
+
+impl Interpretable<f64> for Cons {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<f64> {
         match self {
             Cons::Binary(operator, (left, right)) => match operator {
                 Operator::Sum => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
+                    let left_value: f64 = left.interpret(source)?;
+                    let right_value: f64 = right.interpret(source)?;
 
                     Ok(left_value + right_value)
                 }
                 Operator::Sub => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
+                    let left_value: f64 = left.interpret(source)?;
+                    let right_value: f64 = right.interpret(source)?;
 
                     Ok(left_value - right_value)
                 }
                 Operator::Mul => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
+                    let left_value: f64 = left.interpret(source)?;
+                    let right_value: f64 = right.interpret(source)?;
 
                     Ok(left_value * right_value)
                 }
                 Operator::Div => {
-                    let left_value: f32 = left.interpret(source)?;
-                    let right_value: f32 = right.interpret(source)?;
+                    let left_value: f64 = left.interpret(source)?;
+                    let right_value: f64 = right.interpret(source)?;
+
+                    divide(left_value, right_value).map_err(Error::Interpretation)
+                }
+                Operator::Mod => {
+                    let left_value: f64 = left.interpret(source)?;
+                    let right_value: f64 = right.interpret(source)?;
+
+                    modulo(left_value, right_value).map_err(Error::Interpretation)
+                }
+                Operator::Pow => {
+                    let left_value: f64 = left.interpret(source)?;
+                    let right_value: f64 = right.interpret(source)?;
 
-                    Ok(left_value / right_value)
+                    Ok(left_value.powf(right_value))
                 }
                 _ => Err(Error::Construction(
                     ConstructionError::InvalidNumericConstruction,
                 )),
             },
             Cons::Unary(operator, root) => match operator {
-                Operator::Sub => root.interpret(source).map(|value: f32| -value),
+                Operator::Sum => root.interpret(source),
+                Operator::Sub => root.interpret(source).map(|value: f64| -value),
                 _ => Err(Error::Construction(
                     ConstructionError::InvalidNumericConstruction,
                 )),
             },
+            Cons::Conditional(condition, (then, otherwise)) => {
+                conditional(condition, then, otherwise, source)
+            }
+        }
+    }
+}
+
+impl Interpretable<DataItem> for Cons {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<DataItem> {
+        match self {
+            Cons::Binary(operator, (left, right)) => match operator {
+                Operator::Sum => {
+                    let left_value: DataItem = left.interpret(source)?;
+                    let right_value: DataItem = right.interpret(source)?;
+
+                    match (left_value, right_value) {
+                        (DataItem::Number(left_value), DataItem::Number(right_value)) => {
+                            Ok(DataItem::Number(left_value + right_value))
+                        }
+                        (DataItem::Text(left_value), DataItem::Text(right_value)) => {
+                            Ok(DataItem::Text(left_value + &right_value))
+                        }
+                        _ => Err(Error::Interpretation(
+                            InterpretationError::InvalidBinaryExpression,
+                        )),
+                    }
+                }
+                Operator::Sub | Operator::Mul | Operator::Div | Operator::Mod | Operator::Pow => {
+                    Interpretable::<f64>::interpret(self, source).map(DataItem::Number)
+                }
+                _ => Interpretable::<bool>::interpret(self, source).map(DataItem::Bool),
+            },
+            Cons::Unary(operator, _) => match operator {
+                Operator::Not => Interpretable::<bool>::interpret(self, source).map(DataItem::Bool),
+                _ => Interpretable::<f64>::interpret(self, source).map(DataItem::Number),
+            },
+            Cons::Conditional(condition, (then, otherwise)) => {
+                conditional(condition, then, otherwise, source)
+            }
+        }
+    }
+}
+
+/// `Call` represents a call of a built-in `Function` with a list of argument expressions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Call {
+    pub function: Function,
+    pub args: Vec<Expression>,
+}
+
+impl Call {
+    pub fn new(function: Function, args: Vec<Expression>) -> Self {
+        Call { function, args }
+    }
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}", self.function)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Call {
+    /// Interpret the call for arguments of any type, see `Function::call`.
+    #[inline]
+    fn call(&self, source: &impl DataSource) -> Result<DataItem> {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.interpret(source))
+            .collect::<Result<Vec<DataItem>>>()?;
+
+        self.function.call(&args).ok_or(Error::Interpretation(
+            InterpretationError::InvalidExpression,
+        ))
+    }
+}
+
+impl Interpretable<bool> for Call {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<bool> {
+        if self.function.returns() != DataType::Bool {
+            return Err(Error::Construction(
+                ConstructionError::InvalidBooleanConstruction,
+            ));
+        }
+
+        match self.call(source)? {
+            DataItem::Bool(value) => Ok(value),
+            _ => Err(Error::Construction(
+                ConstructionError::InvalidBooleanConstruction,
+            )),
+        }
+    }
+}
+
+impl Interpretable<f64> for Call {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<f64> {
+        if !self.function.is_numeric() {
+            return match self.call(source)? {
+                DataItem::Number(value) => Ok(value),
+                _ => Err(Error::Construction(
+                    ConstructionError::InvalidNumericConstruction,
+                )),
+            };
+        }
+
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.interpret(source))
+            .collect::<Result<Vec<f64>>>()?;
+
+        self.function.apply(&args).ok_or(Error::Construction(
+            ConstructionError::InvalidNumericConstruction,
+        ))
+    }
+}
+
+impl Interpretable<String> for Call {
+    #[inline]
+    fn interpret(&self, _source: &impl DataSource) -> Result<String> {
+        Err(Error::Construction(
+            ConstructionError::InvalidTextConstruction,
+        ))
+    }
+}
+
+impl Interpretable<DataItem> for Call {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<DataItem> {
+        if self.function.is_numeric() {
+            Interpretable::<f64>::interpret(self, source).map(DataItem::Number)
+        } else {
+            self.call(source)
         }
     }
 }
@@ -151,6 +417,7 @@ impl Interpretable<f32> for Cons {
 pub enum Expression {
     Atom(Symbol),
     Cons(Box<Cons>),
+    Call(Call),
 }
 
 impl fmt::Display for Expression {
@@ -158,6 +425,66 @@ impl fmt::Display for Expression {
         match self {
             Expression::Atom(symbol) => write!(f, "{symbol}"),
             Expression::Cons(cons) => write!(f, "{cons}"),
+            Expression::Call(call) => write!(f, "{call}"),
+        }
+    }
+}
+
+impl Expression {
+    /// Names of the variables referenced by the expression, e.g. the fields of a data value the
+    /// expression reads when interpreted.
+    pub fn variables(&self) -> BTreeSet<&str> {
+        let mut variables = BTreeSet::new();
+        self.collect_variables(&mut variables);
+        variables
+    }
+
+    /// Check if the expression results in the same value for the same variables, i.e. it
+    /// doesn't call any non-deterministic function, such as `now`.
+    pub fn is_deterministic(&self) -> bool {
+        match self {
+            Expression::Atom(_) => true,
+            Expression::Cons(cons) => match cons.as_ref() {
+                Cons::Binary(_, (left, right)) => {
+                    left.is_deterministic() && right.is_deterministic()
+                }
+                Cons::Unary(_, root) => root.is_deterministic(),
+                Cons::Conditional(condition, (then, otherwise)) => {
+                    condition.is_deterministic()
+                        && then.is_deterministic()
+                        && otherwise.is_deterministic()
+                }
+            },
+            Expression::Call(call) => {
+                call.function.is_deterministic()
+                    && call.args.iter().all(Expression::is_deterministic)
+            }
+        }
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut BTreeSet<&'a str>) {
+        match self {
+            Expression::Atom(Symbol::Variable(name)) => {
+                variables.insert(name);
+            }
+            Expression::Atom(_) => {}
+            Expression::Cons(cons) => match cons.as_ref() {
+                Cons::Binary(_, (left, right)) => {
+                    left.collect_variables(variables);
+                    right.collect_variables(variables);
+                }
+                Cons::Unary(_, root) => root.collect_variables(variables),
+                Cons::Conditional(condition, (then, otherwise)) => {
+                    condition.collect_variables(variables);
+                    then.collect_variables(variables);
+                    otherwise.collect_variables(variables);
+                }
+            },
+            Expression::Call(call) => {
+                for arg in &call.args {
+                    arg.collect_variables(variables);
+                }
+            }
         }
     }
 }
@@ -185,13 +512,14 @@ impl Interpretable<bool> for Expression {
                 )),
             },
             Expression::Cons(cons) => cons.interpret(source),
+            Expression::Call(call) => call.interpret(source),
         }
     }
 }
 
-impl Interpretable<f32> for Expression {
+impl Interpretable<f64> for Expression {
     #[inline]
-    fn interpret(&self, source: &impl DataSource) -> Result<f32> {
+    fn interpret(&self, source: &impl DataSource) -> Result<f64> {
         match self {
             Expression::Atom(symbol) => match symbol {
                 Symbol::Number(number) => Ok(*number),
@@ -212,6 +540,58 @@ impl Interpretable<f32> for Expression {
                 )),
             },
             Expression::Cons(cons) => cons.interpret(source),
+            Expression::Call(call) => call.interpret(source),
+        }
+    }
+}
+
+impl Interpretable<String> for Expression {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<String> {
+        match self {
+            Expression::Atom(symbol) => match symbol {
+                Symbol::Text(text) => Ok(text.clone()),
+                Symbol::Variable(name) => {
+                    let variable = source.get(name).ok_or(Error::Interpretation(
+                        InterpretationError::InvalidTextExpression,
+                    ))?;
+
+                    match variable {
+                        DataItem::Text(text) => Ok(text.clone()),
+                        _ => Err(Error::Construction(
+                            ConstructionError::InvalidTextConstruction,
+                        )),
+                    }
+                }
+                _ => Err(Error::Construction(
+                    ConstructionError::InvalidTextConstruction,
+                )),
+            },
+            Expression::Cons(cons) => cons.interpret(source),
+            Expression::Call(call) => call.interpret(source),
+        }
+    }
+}
+
+/// Interpret an expression without knowing its type in advance. The resulting `DataItem` type
+/// is inferred at runtime, depending on the atoms, operators and functions of the expression.
+/// Variables missing in the data source are interpreted as null.
+impl Interpretable<DataItem> for Expression {
+    #[inline]
+    fn interpret(&self, source: &impl DataSource) -> Result<DataItem> {
+        match self {
+            Expression::Atom(symbol) => match symbol {
+                Symbol::Boolean(boolean) => Ok(DataItem::Bool(*boolean)),
+                Symbol::Number(number) => Ok(DataItem::Number(*number)),
+                Symbol::Text(text) => Ok(DataItem::Text(text.clone())),
+                Symbol::Null => Ok(DataItem::Null),
+                Symbol::Variable(name) => Ok(source.get(name).cloned().unwrap_or(DataItem::Null)),
+                _ => Err(Error::Interpretation(
+                    InterpretationError::InvalidExpression,
+                )),
+            },
+            Expression::Cons(cons) => cons.interpret(source),
+            Expression::Call(call) => call.interpret(source),
         }
     }
 }
@@ -228,8 +608,8 @@ impl From<bool> for Expression {
     }
 }
 
-impl From<f32> for Expression {
-    fn from(value: f32) -> Self {
+impl From<f64> for Expression {
+    fn from(value: f64) -> Self {
         Expression::Atom(Symbol::Number(value))
     }
 }
@@ -246,13 +626,31 @@ impl From<Cons> for Expression {
     }
 }
 
+impl From<Call> for Expression {
+    fn from(call: Call) -> Self {
+        Expression::Call(call)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::error::Result;
-    use crate::expr::{Cons, Expression, Interpretable};
-    use crate::symbols::Operator;
+    use std::collections::BTreeMap;
+
+    use crate::data::DataItem;
+    use crate::error::{Error, InterpretationError, Result};
+    use crate::expr::{Call, Cons, Expression, Interpretable};
+    use crate::function::Function;
+    use crate::symbols::{Operator, Symbol};
     use crate::vars::Variables;
 
+    fn call(function: Function, args: Vec<Expression>) -> Expression {
+        Expression::from(Call::new(function, args))
+    }
+
+    fn text(value: &str) -> Expression {
+        Expression::Atom(Symbol::Text(value.to_string()))
+    }
+
     fn unary(root: Expression, operator: Operator) -> Expression {
         Expression::from(Cons::Unary(operator, root))
     }
@@ -261,6 +659,10 @@ mod tests {
         Expression::from(Cons::Binary(operator, (left, right)))
     }
 
+    fn conditional(condition: Expression, then: Expression, otherwise: Expression) -> Expression {
+        Expression::from(Cons::Conditional(condition, (then, otherwise)))
+    }
+
     #[test]
     fn interprets_boolean_literals() {
         let expression = Expression::from(true);
@@ -561,24 +963,24 @@ mod tests {
     #[test]
     fn interprets_numeric_literals() {
         let expression = Expression::from(3.0);
-        let result: f32 = expression.interpret(&Variables::new()).unwrap();
+        let result: f64 = expression.interpret(&Variables::new()).unwrap();
         assert_eq!(result, 3.0);
 
         let expression = Expression::from(-3.0);
-        let result: f32 = expression.interpret(&Variables::new()).unwrap();
+        let result: f64 = expression.interpret(&Variables::new()).unwrap();
         assert_eq!(result, -3.0);
     }
 
     #[test]
     fn interprets_numeric_variables() {
         let expression = Expression::from("a");
-        let result: f32 = expression
+        let result: f64 = expression
             .interpret(&Variables::from_pairs(vec![("a", 3.0.into())]))
             .unwrap();
         assert_eq!(result, 3.0);
 
         let expression = Expression::from("a");
-        let result: f32 = expression
+        let result: f64 = expression
             .interpret(&Variables::from_pairs(vec![("a", (-3.0).into())]))
             .unwrap();
         assert_eq!(result, -3.0);
@@ -934,6 +1336,48 @@ mod tests {
         assert!(result);
     }
 
+    #[test]
+    fn interprets_modulo_and_power_expressions() {
+        let vars = Variables::from_pairs(vec![("a", 7.0.into())]);
+
+        let expression = binary(Expression::from("a"), Operator::Mod, Expression::from(3.0));
+        let result: f64 = expression.interpret(&vars).unwrap();
+        assert_eq!(result, 1.0);
+
+        let expression = binary(Expression::from(-7.0), Operator::Mod, Expression::from(3.0));
+        let result: f64 = expression.interpret(&vars).unwrap();
+        assert_eq!(result, -1.0);
+
+        let expression = binary(
+            Expression::from(2.0),
+            Operator::Pow,
+            binary(Expression::from(3.0), Operator::Pow, Expression::from(2.0)),
+        );
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Number(512.0));
+    }
+
+    #[test]
+    fn doesn_interpret_division_by_zero() {
+        let vars = Variables::from_pairs(vec![("a", 0.0.into())]);
+
+        for operator in [Operator::Div, Operator::Mod] {
+            let expression = binary(Expression::from(1.0), operator, Expression::from("a"));
+
+            let result: Result<f64> = expression.interpret(&vars);
+            assert!(matches!(
+                result,
+                Err(Error::Interpretation(InterpretationError::DivisionByZero))
+            ));
+
+            let result: Result<DataItem> = expression.interpret(&vars);
+            assert!(matches!(
+                result,
+                Err(Error::Interpretation(InterpretationError::DivisionByZero))
+            ));
+        }
+    }
+
     #[test]
     fn interprets_multiple_binary_mixed_expressions() {
         let expression = binary(
@@ -987,4 +1431,538 @@ mod tests {
             .unwrap();
         assert!(!result);
     }
+
+    #[test]
+    fn interprets_text_literals() {
+        let expression = text("fruit");
+        let result: String = expression.interpret(&Variables::new()).unwrap();
+        assert_eq!(result, "fruit");
+
+        let expression = Expression::from("a");
+        let result: String = expression
+            .interpret(&Variables::from_pairs(vec![("a", "fruit".into())]))
+            .unwrap();
+        assert_eq!(result, "fruit");
+
+        let expression = Expression::from("a");
+        let result: Result<String> =
+            expression.interpret(&Variables::from_pairs(vec![("a", 1.0.into())]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_binary_eq_text_expressions() {
+        let expression = binary(text("fruit"), Operator::Equal, text("fruit"));
+        let result: bool = expression.interpret(&Variables::new()).unwrap();
+        assert!(result);
+
+        let expression = binary(text("fruit"), Operator::Equal, text("Fruit"));
+        let result: bool = expression.interpret(&Variables::new()).unwrap();
+        assert!(!result);
+
+        let expression = binary(Expression::from("a"), Operator::Equal, text("fruit"));
+        let result: bool = expression
+            .interpret(&Variables::from_pairs(vec![("a", "fruit".into())]))
+            .unwrap();
+        assert!(result);
+
+        let expression = binary(
+            Expression::from("a"),
+            Operator::Equal,
+            Expression::from("b"),
+        );
+        let result: bool = expression
+            .interpret(&Variables::from_pairs(vec![
+                ("a", "fruit".into()),
+                ("b", "vegetable".into()),
+            ]))
+            .unwrap();
+        assert!(!result);
+    }
+
+    #[test]
+    fn interprets_binary_neq_text_expressions() {
+        let expression = binary(text("fruit"), Operator::NotEqual, text("fruit"));
+        let result: bool = expression.interpret(&Variables::new()).unwrap();
+        assert!(!result);
+
+        let expression = binary(Expression::from("a"), Operator::NotEqual, text("fruit"));
+        let result: bool = expression
+            .interpret(&Variables::from_pairs(vec![("a", "vegetable".into())]))
+            .unwrap();
+        assert!(result);
+    }
+
+    #[test]
+    fn interprets_binary_comparison_text_expressions() {
+        let expression = binary(text("apple"), Operator::Less, text("banana"));
+        let result: bool = expression.interpret(&Variables::new()).unwrap();
+        assert!(result);
+
+        let expression = binary(text("apple"), Operator::Greater, text("banana"));
+        let result: bool = expression.interpret(&Variables::new()).unwrap();
+        assert!(!result);
+
+        let expression = binary(text("apple"), Operator::GreaterOrEqual, text("apple"));
+        let result: bool = expression.interpret(&Variables::new()).unwrap();
+        assert!(result);
+
+        let expression = binary(Expression::from("a"), Operator::LessOrEqual, text("B"));
+        let result: bool = expression
+            .interpret(&Variables::from_pairs(vec![("a", "a".into())]))
+            .unwrap();
+        assert!(!result);
+    }
+
+    #[test]
+    fn doesn_interpret_mixed_text_expressions() {
+        let expression = binary(text("1"), Operator::Equal, Expression::from(1.0));
+        let result: Result<bool> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+
+        let expression = binary(Expression::from(1.0), Operator::Less, text("2"));
+        let result: Result<bool> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+
+        let expression = binary(text("a"), Operator::Greater, Expression::from(true));
+        let result: Result<bool> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+
+        let expression = binary(text("a"), Operator::Sum, text("b"));
+        let result: Result<f64> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_function_calls() {
+        let expression = call(Function::Sqrt, vec![Expression::from("a")]);
+        let result: f64 = expression
+            .interpret(&Variables::from_pairs(vec![("a", 9.0.into())]))
+            .unwrap();
+        assert_eq!(result, 3.0);
+
+        let expression = call(
+            Function::Abs,
+            vec![binary(
+                Expression::from("b"),
+                Operator::Sub,
+                Expression::from("c"),
+            )],
+        );
+        let result: f64 = expression
+            .interpret(&Variables::from_pairs(vec![
+                ("b", 2.0.into()),
+                ("c", 5.0.into()),
+            ]))
+            .unwrap();
+        assert_eq!(result, 3.0);
+
+        let expression = binary(
+            call(
+                Function::Min,
+                vec![Expression::from("a"), Expression::from(2.0)],
+            ),
+            Operator::Less,
+            Expression::from(3.0),
+        );
+        let result: bool = expression
+            .interpret(&Variables::from_pairs(vec![("a", 4.0.into())]))
+            .unwrap();
+        assert!(result);
+    }
+
+    #[test]
+    fn doesn_interpret_function_calls_with_invalid_arguments() {
+        let expression = call(Function::Sqrt, vec![Expression::from(true)]);
+        let result: Result<f64> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+
+        let expression = call(Function::Abs, vec![Expression::from(1.0)]);
+        let result: Result<bool> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+
+        let expression = call(Function::Pow, vec![Expression::from(2.0)]);
+        let result: Result<f64> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+
+        let expression = call(Function::IsValid, vec![]);
+        let result: Result<DataItem> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_conditional_expressions() {
+        let expression = conditional(
+            binary(
+                Expression::from("a"),
+                Operator::Greater,
+                Expression::from(10.0),
+            ),
+            Expression::from("a"),
+            Expression::from(0.0),
+        );
+        let result: f64 = expression
+            .interpret(&Variables::from_pairs(vec![("a", 12.0.into())]))
+            .unwrap();
+        assert_eq!(result, 12.0);
+
+        let result: f64 = expression
+            .interpret(&Variables::from_pairs(vec![("a", 8.0.into())]))
+            .unwrap();
+        assert_eq!(result, 0.0);
+
+        let expression = conditional(Expression::from("a"), text("yes"), text("no"));
+        let result: String = expression
+            .interpret(&Variables::from_pairs(vec![("a", false.into())]))
+            .unwrap();
+        assert_eq!(result, "no");
+
+        let expression = conditional(
+            Expression::from(true),
+            Expression::from("b"),
+            Expression::from(false),
+        );
+        let result: bool = expression
+            .interpret(&Variables::from_pairs(vec![("b", true.into())]))
+            .unwrap();
+        assert!(result);
+    }
+
+    #[test]
+    fn interprets_only_chosen_conditional_branch() {
+        let expression = conditional(
+            Expression::from(true),
+            Expression::from(1.0),
+            Expression::from("missing"),
+        );
+        let result: f64 = expression.interpret(&Variables::new()).unwrap();
+        assert_eq!(result, 1.0);
+
+        let expression = conditional(
+            Expression::from(false),
+            text("invalid"),
+            Expression::from(2.0),
+        );
+        let result: f64 = expression.interpret(&Variables::new()).unwrap();
+        assert_eq!(result, 2.0);
+    }
+
+    #[test]
+    fn doesn_interpret_conditional_with_invalid_condition() {
+        let expression = conditional(
+            Expression::from(1.0),
+            Expression::from(1.0),
+            Expression::from(2.0),
+        );
+        let result: Result<f64> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_text_concatenation() {
+        let expression = binary(Expression::from("a"), Operator::Sum, text(" apple"));
+        let result: String = expression
+            .interpret(&Variables::from_pairs(vec![("a", "red".into())]))
+            .unwrap();
+        assert_eq!(result, "red apple");
+
+        let expression = binary(text("a"), Operator::Sum, Expression::from(1.0));
+        let result: Result<String> = expression.interpret(&Variables::new());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_dynamic_expressions() {
+        let vars = Variables::from_pairs(vec![
+            ("a", 6.0.into()),
+            ("b", true.into()),
+            ("c", "fruit".into()),
+        ]);
+
+        let expression = binary(Expression::from("a"), Operator::Mul, Expression::from(2.0));
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Number(12.0));
+
+        let expression = binary(
+            Expression::from("a"),
+            Operator::Greater,
+            Expression::from(5.0),
+        );
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Bool(true));
+
+        let expression = unary(Expression::from("b"), Operator::Not);
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Bool(false));
+
+        let expression = binary(Expression::from("c"), Operator::Sum, text("s"));
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Text("fruits".to_string()));
+
+        let expression = binary(Expression::from("a"), Operator::Sum, Expression::from(1.0));
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Number(7.0));
+
+        let expression = call(Function::Sqrt, vec![Expression::from(4.0)]);
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Number(2.0));
+
+        let expression = conditional(Expression::from("b"), Expression::from("c"), text("none"));
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Text("fruit".to_string()));
+
+        let expression = Expression::from("b");
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Bool(true));
+    }
+
+    #[test]
+    fn doesn_interpret_invalid_dynamic_expressions() {
+        let vars = Variables::from_pairs(vec![("a", 6.0.into()), ("c", "fruit".into())]);
+
+        let expression = binary(
+            Expression::from("missing"),
+            Operator::Sum,
+            Expression::from(1.0),
+        );
+        let result: Result<DataItem> = expression.interpret(&vars);
+        assert!(result.is_err());
+
+        let expression = binary(Expression::from("a"), Operator::Sum, Expression::from("c"));
+        let result: Result<DataItem> = expression.interpret(&vars);
+        assert!(result.is_err());
+
+        let expression = binary(Expression::from("c"), Operator::Mul, Expression::from(2.0));
+        let result: Result<DataItem> = expression.interpret(&vars);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_short_circuit_boolean_expressions() {
+        let vars = Variables::from_pairs(vec![("n", DataItem::Null)]);
+        let invalid = binary(
+            Expression::from("n"),
+            Operator::Greater,
+            Expression::from(2.0),
+        );
+
+        let expression = binary(Expression::from(false), Operator::And, invalid.clone());
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(!result);
+
+        let expression = binary(Expression::from(true), Operator::Or, invalid.clone());
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(result);
+
+        let expression = binary(Expression::from(true), Operator::And, invalid);
+        let result: Result<bool> = expression.interpret(&vars);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_null_expressions() {
+        let vars = Variables::from_pairs(vec![("a", 6.0.into()), ("n", DataItem::Null)]);
+
+        let expression = Expression::from("missing");
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Null);
+
+        let expression = binary(
+            Expression::from("n"),
+            Operator::Equal,
+            Expression::Atom(Symbol::Null),
+        );
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(result);
+
+        let expression = binary(
+            Expression::from("a"),
+            Operator::NotEqual,
+            Expression::Atom(Symbol::Null),
+        );
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(result);
+
+        let expression = binary(
+            Expression::from("n"),
+            Operator::Equal,
+            Expression::from(6.0),
+        );
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(!result);
+
+        let expression =
+            Expression::from(Call::new(Function::IsValid, vec![Expression::from("a")]));
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Bool(true));
+
+        for name in ["n", "missing"] {
+            let expression =
+                Expression::from(Call::new(Function::IsValid, vec![Expression::from(name)]));
+            let result: bool = expression.interpret(&vars).unwrap();
+            assert!(!result);
+        }
+
+        let expression = binary(Expression::from("n"), Operator::Sum, Expression::from(1.0));
+        let result: Result<DataItem> = expression.interpret(&vars);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_path_expressions() {
+        let geo = DataItem::Object(BTreeMap::from([
+            ("lat".to_string(), 4.0.into()),
+            (
+                "tags".to_string(),
+                DataItem::Array(vec!["a".into(), "b".into()]),
+            ),
+        ]));
+        let vars = Variables::from_pairs(vec![("geo", geo.clone()), ("other", geo)]);
+
+        let expression = binary(
+            Expression::from("geo.lat"),
+            Operator::Mul,
+            Expression::from(2.0),
+        );
+        let result: f64 = expression.interpret(&vars).unwrap();
+        assert_eq!(result, 8.0);
+
+        let expression = binary(Expression::from("geo.tags[1]"), Operator::Equal, text("b"));
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(result);
+
+        let expression = binary(
+            Expression::from("geo"),
+            Operator::Equal,
+            Expression::from("other"),
+        );
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(result);
+
+        let expression = Expression::from("geo.tags[2]");
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Null);
+
+        let expression = binary(
+            Expression::from("geo"),
+            Operator::Sum,
+            Expression::from(1.0),
+        );
+        let result: Result<DataItem> = expression.interpret(&vars);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interprets_date_expressions() {
+        let vars = Variables::from_pairs(vec![
+            ("d", DataItem::Date(1_709_296_200_250)),
+            ("e", DataItem::Date(0)),
+        ]);
+
+        let expression = call(Function::Year, vec![Expression::from("d")]);
+        let result: f64 = expression.interpret(&vars).unwrap();
+        assert_eq!(result, 2024.0);
+
+        let expression = binary(
+            call(Function::Month, vec![Expression::from("d")]),
+            Operator::Sum,
+            call(Function::Hours, vec![Expression::from("d")]),
+        );
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Number(14.0));
+
+        let expression = call(
+            Function::Datetime,
+            vec![
+                Expression::from(2024.0),
+                Expression::from(2.0),
+                Expression::from(1.0),
+                Expression::from(12.0),
+                Expression::from(30.0),
+            ],
+        );
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert_eq!(result, DataItem::Date(1_709_296_200_000));
+
+        let expression = binary(
+            Expression::from("d"),
+            Operator::Greater,
+            Expression::from("e"),
+        );
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(result);
+
+        let expression = binary(
+            Expression::from("d"),
+            Operator::Equal,
+            Expression::from("e"),
+        );
+        let result: bool = expression.interpret(&vars).unwrap();
+        assert!(!result);
+
+        let expression = call(Function::Now, vec![]);
+        let result: DataItem = expression.interpret(&vars).unwrap();
+        assert!(result.get_date().is_some());
+    }
+
+    #[test]
+    fn doesnt_interpret_invalid_date_expressions() {
+        let vars = Variables::from_pairs(vec![("a", 3.0.into()), ("d", DataItem::Date(0))]);
+
+        let expression = call(Function::Year, vec![Expression::from("a")]);
+        let result: Result<f64> = expression.interpret(&vars);
+        assert!(result.is_err());
+
+        let expression = binary(
+            Expression::from("d"),
+            Operator::Greater,
+            Expression::from("a"),
+        );
+        let result: Result<bool> = expression.interpret(&vars);
+        assert!(result.is_err());
+
+        let expression = binary(Expression::from("d"), Operator::Sum, Expression::from(1.0));
+        let result: Result<DataItem> = expression.interpret(&vars);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn finds_variables() {
+        let expression = binary(
+            binary(Expression::from("a"), Operator::Mul, Expression::from(2.0)),
+            Operator::Sum,
+            call(
+                Function::Max,
+                vec![
+                    Expression::from("b"),
+                    unary(Expression::from("a"), Operator::Sub),
+                ],
+            ),
+        );
+        assert_eq!(
+            expression.variables().into_iter().collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        let expression = conditional(Expression::from("c"), text("a"), Expression::from("d"));
+        assert_eq!(
+            expression.variables().into_iter().collect::<Vec<_>>(),
+            vec!["c", "d"]
+        );
+
+        assert!(Expression::from(2.0).variables().is_empty());
+    }
+
+    #[test]
+    fn finds_non_deterministic_calls() {
+        let expression = call(Function::Max, vec![Expression::from("a"), 2.0.into()]);
+        assert!(expression.is_deterministic());
+
+        let expression = conditional(
+            Expression::from("c"),
+            call(Function::Now, Vec::new()),
+            Expression::from("d"),
+        );
+        assert!(!expression.is_deterministic());
+    }
 }
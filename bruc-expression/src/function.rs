@@ -0,0 +1,401 @@
+use std::fmt;
+
+use crate::data::DataItem;
+use crate::date::{self, DateTime};
+use crate::types::DataType;
+
+pub(crate) const ABS: &str = "abs";
+pub(crate) const CEIL: &str = "ceil";
+pub(crate) const FLOOR: &str = "floor";
+pub(crate) const ROUND: &str = "round";
+pub(crate) const SQRT: &str = "sqrt";
+pub(crate) const POW: &str = "pow";
+pub(crate) const EXP: &str = "exp";
+pub(crate) const LOG: &str = "log";
+pub(crate) const LOG10: &str = "log10";
+pub(crate) const MIN: &str = "min";
+pub(crate) const MAX: &str = "max";
+pub(crate) const CLAMP: &str = "clamp";
+pub(crate) const IS_VALID: &str = "isValid";
+pub(crate) const YEAR: &str = "year";
+pub(crate) const MONTH: &str = "month";
+pub(crate) const DATE: &str = "date";
+pub(crate) const HOURS: &str = "hours";
+pub(crate) const DATETIME: &str = "datetime";
+pub(crate) const NOW: &str = "now";
+
+/// `Function` collects the built-in functions that can be called in an expression.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Function {
+    Abs,
+    Ceil,
+    Floor,
+    Round,
+    Sqrt,
+    Pow,
+    Exp,
+    Log,
+    Log10,
+    Min,
+    Max,
+    Clamp,
+    IsValid,
+    Year,
+    Month,
+    Date,
+    Hours,
+    Datetime,
+    Now,
+}
+
+/// `Arity` defines the amount of arguments a `Function` accepts.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Arity {
+    Exact(usize),
+    Range(usize, usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    /// Check if a certain `count` of arguments is accepted.
+    pub fn accepts(&self, count: usize) -> bool {
+        match self {
+            Arity::Exact(exact) => count == *exact,
+            Arity::Range(min, max) => count >= *min && count <= *max,
+            Arity::AtLeast(min) => count >= *min,
+        }
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arity::Exact(exact) => write!(f, "{exact}"),
+            Arity::Range(min, max) => write!(f, "{min} to {max}"),
+            Arity::AtLeast(min) => write!(f, "at least {min}"),
+        }
+    }
+}
+
+impl Function {
+    /// Find the built-in function with a certain `name`.
+    pub fn from_name(name: &str) -> Option<Function> {
+        match name {
+            ABS => Some(Function::Abs),
+            CEIL => Some(Function::Ceil),
+            FLOOR => Some(Function::Floor),
+            ROUND => Some(Function::Round),
+            SQRT => Some(Function::Sqrt),
+            POW => Some(Function::Pow),
+            EXP => Some(Function::Exp),
+            LOG => Some(Function::Log),
+            LOG10 => Some(Function::Log10),
+            MIN => Some(Function::Min),
+            MAX => Some(Function::Max),
+            CLAMP => Some(Function::Clamp),
+            IS_VALID => Some(Function::IsValid),
+            YEAR => Some(Function::Year),
+            MONTH => Some(Function::Month),
+            DATE => Some(Function::Date),
+            HOURS => Some(Function::Hours),
+            DATETIME => Some(Function::Datetime),
+            NOW => Some(Function::Now),
+            _ => None,
+        }
+    }
+
+    /// Amount of arguments accepted by the function.
+    pub fn arity(&self) -> Arity {
+        match self {
+            Function::Abs
+            | Function::Ceil
+            | Function::Floor
+            | Function::Sqrt
+            | Function::Exp
+            | Function::Log
+            | Function::Log10
+            | Function::IsValid
+            | Function::Year
+            | Function::Month
+            | Function::Date
+            | Function::Hours => Arity::Exact(1),
+            Function::Round => Arity::Range(1, 2),
+            Function::Pow => Arity::Exact(2),
+            Function::Min | Function::Max => Arity::AtLeast(1),
+            Function::Clamp => Arity::Exact(3),
+            Function::Datetime => Arity::Range(2, 7),
+            Function::Now => Arity::Exact(0),
+        }
+    }
+
+    /// Type of the arguments accepted by the function. Arguments of any type are accepted, if no
+    /// type is defined.
+    pub fn parameter(&self) -> Option<DataType> {
+        match self {
+            Function::IsValid => None,
+            Function::Year | Function::Month | Function::Date | Function::Hours => {
+                Some(DataType::Date)
+            }
+            _ => Some(DataType::Number),
+        }
+    }
+
+    /// Type of the value returned by the function.
+    pub fn returns(&self) -> DataType {
+        match self {
+            Function::IsValid => DataType::Bool,
+            Function::Datetime | Function::Now => DataType::Date,
+            _ => DataType::Number,
+        }
+    }
+
+    /// Check if the function accepts and returns numbers only, so that it can be applied with
+    /// `Function::apply`.
+    pub fn is_numeric(&self) -> bool {
+        self.parameter() == Some(DataType::Number) && self.returns() == DataType::Number
+    }
+
+    /// Check if the function returns the same value for the same arguments, so that calls with
+    /// constant arguments can be folded.
+    pub fn is_deterministic(&self) -> bool {
+        !matches!(self, Function::Now)
+    }
+
+    /// Call the function with a list of arguments of any type. No value is returned, if the
+    /// amount of arguments is not accepted by the function's arity, or if the arguments are not
+    /// of the type accepted by the function.
+    pub(crate) fn call(&self, args: &[DataItem]) -> Option<DataItem> {
+        if !self.arity().accepts(args.len()) {
+            return None;
+        }
+
+        match self {
+            Function::IsValid => Some(DataItem::Bool(args[0].is_valid())),
+            Function::Year | Function::Month | Function::Date | Function::Hours => {
+                let date = DateTime::from_timestamp(*args[0].get_date()?);
+                let part = match self {
+                    Function::Year => date.year,
+                    Function::Month => date.month,
+                    Function::Date => date.day,
+                    _ => date.hours,
+                };
+
+                Some(DataItem::Number(part as f64))
+            }
+            Function::Datetime => {
+                let mut parts = [0, 0, 1, 0, 0, 0, 0];
+                for (part, arg) in parts.iter_mut().zip(args) {
+                    let value = arg.get_number().filter(|value| value.is_finite())?;
+                    *part = value.trunc() as i64;
+                }
+                let [year, month, day, hours, minutes, seconds, millis] = parts;
+
+                let date = DateTime {
+                    year,
+                    month,
+                    day,
+                    hours,
+                    minutes,
+                    seconds,
+                    millis,
+                };
+
+                // Dates that don't fit in a timestamp are null, like invalid dates
+                Some(date.timestamp().map_or(DataItem::Null, DataItem::Date))
+            }
+            Function::Now => Some(DataItem::Date(date::now())),
+            _ => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.get_number().copied())
+                    .collect::<Option<Vec<f64>>>()?;
+
+                self.apply(&args).map(DataItem::Number)
+            }
+        }
+    }
+
+    /// Apply the function to a list of numeric arguments. Functions that don't return a number,
+    /// or whose arity doesn't accept the amount of arguments, are not applied.
+    #[inline]
+    pub(crate) fn apply(&self, args: &[f64]) -> Option<f64> {
+        if !self.arity().accepts(args.len()) {
+            return None;
+        }
+
+        let result = match self {
+            Function::Abs => args[0].abs(),
+            Function::Ceil => args[0].ceil(),
+            Function::Floor => args[0].floor(),
+            Function::Round => match args.get(1) {
+                Some(digits) => {
+                    let factor = 10f64.powf(digits.trunc());
+                    (args[0] * factor).round() / factor
+                }
+                None => args[0].round(),
+            },
+            Function::Sqrt => args[0].sqrt(),
+            Function::Pow => args[0].powf(args[1]),
+            Function::Exp => args[0].exp(),
+            Function::Log => args[0].ln(),
+            Function::Log10 => args[0].log10(),
+            Function::Min => args.iter().copied().fold(f64::INFINITY, f64::min),
+            Function::Max => args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Function::Clamp => args[0].max(args[1]).min(args[2]),
+            Function::IsValid
+            | Function::Year
+            | Function::Month
+            | Function::Date
+            | Function::Hours
+            | Function::Datetime
+            | Function::Now => return None,
+        };
+
+        Some(result)
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Function::Abs => write!(f, "{ABS}"),
+            Function::Ceil => write!(f, "{CEIL}"),
+            Function::Floor => write!(f, "{FLOOR}"),
+            Function::Round => write!(f, "{ROUND}"),
+            Function::Sqrt => write!(f, "{SQRT}"),
+            Function::Pow => write!(f, "{POW}"),
+            Function::Exp => write!(f, "{EXP}"),
+            Function::Log => write!(f, "{LOG}"),
+            Function::Log10 => write!(f, "{LOG10}"),
+            Function::Min => write!(f, "{MIN}"),
+            Function::Max => write!(f, "{MAX}"),
+            Function::Clamp => write!(f, "{CLAMP}"),
+            Function::IsValid => write!(f, "{IS_VALID}"),
+            Function::Year => write!(f, "{YEAR}"),
+            Function::Month => write!(f, "{MONTH}"),
+            Function::Date => write!(f, "{DATE}"),
+            Function::Hours => write!(f, "{HOURS}"),
+            Function::Datetime => write!(f, "{DATETIME}"),
+            Function::Now => write!(f, "{NOW}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::DataItem;
+    use crate::date;
+    use crate::function::{Arity, Function};
+
+    #[test]
+    fn finds_functions_by_name() {
+        assert_eq!(Function::from_name("sqrt"), Some(Function::Sqrt));
+        assert_eq!(Function::from_name("log10"), Some(Function::Log10));
+        assert_eq!(Function::from_name("clamp"), Some(Function::Clamp));
+        assert_eq!(Function::from_name("isValid"), Some(Function::IsValid));
+        assert_eq!(Function::from_name("datetime"), Some(Function::Datetime));
+        assert_eq!(Function::from_name("foo"), None);
+        assert_eq!(Function::from_name("SQRT"), None);
+    }
+
+    #[test]
+    fn accepts_arguments_by_arity() {
+        assert!(Arity::Exact(1).accepts(1));
+        assert!(!Arity::Exact(1).accepts(2));
+
+        assert!(Arity::Range(1, 2).accepts(1));
+        assert!(Arity::Range(1, 2).accepts(2));
+        assert!(!Arity::Range(1, 2).accepts(3));
+
+        assert!(Arity::AtLeast(1).accepts(4));
+        assert!(!Arity::AtLeast(1).accepts(0));
+    }
+
+    #[test]
+    fn applies_functions() {
+        assert_eq!(Function::Abs.apply(&[-2.5]), Some(2.5));
+        assert_eq!(Function::Ceil.apply(&[2.1]), Some(3.0));
+        assert_eq!(Function::Floor.apply(&[2.9]), Some(2.0));
+        assert_eq!(Function::Round.apply(&[2.5]), Some(3.0));
+        assert_eq!(Function::Round.apply(&[2.346, 2.0]), Some(2.35));
+        assert_eq!(Function::Sqrt.apply(&[16.0]), Some(4.0));
+        assert_eq!(Function::Pow.apply(&[3.0, 2.0]), Some(9.0));
+        assert_eq!(Function::Exp.apply(&[0.0]), Some(1.0));
+        assert_eq!(Function::Log.apply(&[1.0]), Some(0.0));
+        assert_eq!(Function::Log10.apply(&[100.0]), Some(2.0));
+        assert_eq!(Function::Min.apply(&[3.0, 1.0, 2.0]), Some(1.0));
+        assert_eq!(Function::Max.apply(&[3.0, 1.0, 2.0]), Some(3.0));
+        assert_eq!(Function::Clamp.apply(&[5.0, 0.0, 2.0]), Some(2.0));
+        assert_eq!(Function::Clamp.apply(&[-5.0, 0.0, 2.0]), Some(0.0));
+        assert_eq!(Function::IsValid.apply(&[1.0]), None);
+    }
+
+    #[test]
+    fn doesnt_apply_functions_to_unaccepted_arguments() {
+        assert_eq!(Function::Abs.apply(&[]), None);
+        assert_eq!(Function::Pow.apply(&[3.0]), None);
+        assert_eq!(Function::Clamp.apply(&[5.0, 0.0]), None);
+        assert_eq!(Function::Round.apply(&[2.5, 1.0, 0.0]), None);
+        assert_eq!(Function::Max.call(&[]), None);
+        assert_eq!(Function::IsValid.call(&[]), None);
+        assert_eq!(Function::Year.call(&[]), None);
+        assert_eq!(Function::Datetime.call(&[2024.0.into()]), None);
+    }
+
+    #[test]
+    fn calls_date_functions() {
+        let date = [DataItem::Date(date::parse("2024-03-01T12:30:00Z").unwrap())];
+
+        assert_eq!(Function::Year.call(&date), Some(DataItem::Number(2024.0)));
+        assert_eq!(Function::Month.call(&date), Some(DataItem::Number(2.0)));
+        assert_eq!(Function::Date.call(&date), Some(DataItem::Number(1.0)));
+        assert_eq!(Function::Hours.call(&date), Some(DataItem::Number(12.0)));
+        assert_eq!(
+            Function::Datetime.call(&[
+                2024.0.into(),
+                2.0.into(),
+                1.0.into(),
+                12.0.into(),
+                30.0.into()
+            ]),
+            Some(date[0].clone())
+        );
+        assert_eq!(
+            Function::Datetime.call(&[2024.0.into(), 0.0.into()]),
+            Some(DataItem::Date(date::parse("2024-01-01").unwrap()))
+        );
+
+        assert_eq!(
+            Function::Datetime.call(&[1e18.into(), 0.0.into()]),
+            Some(DataItem::Null)
+        );
+        assert_eq!(
+            Function::Datetime.call(&[2024.0.into(), 0.0.into(), 1.0.into(), 1e18.into()]),
+            Some(DataItem::Null)
+        );
+        assert_eq!(
+            Function::Datetime.call(&[(-1e300).into(), 0.0.into()]),
+            Some(DataItem::Null)
+        );
+
+        assert_eq!(Function::Year.call(&[2024.0.into()]), None);
+        assert_eq!(Function::Datetime.call(&[2024.0.into(), "a".into()]), None);
+        assert!(matches!(
+            Function::Now.call(&[]),
+            Some(DataItem::Date(now)) if now > 0
+        ));
+    }
+
+    #[test]
+    fn calls_numeric_functions() {
+        assert_eq!(
+            Function::Max.call(&[1.0.into(), 3.0.into()]),
+            Some(DataItem::Number(3.0))
+        );
+        assert_eq!(Function::Max.call(&[1.0.into(), true.into()]), None);
+        assert_eq!(
+            Function::IsValid.call(&[DataItem::Null]),
+            Some(DataItem::Bool(false))
+        );
+    }
+}
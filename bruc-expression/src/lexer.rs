@@ -3,24 +3,47 @@ use std::iter::Peekable;
 use strizer::{StringTokenizer, TokenKind};
 
 use crate::symbols::{
-    Operator, Symbol, AND, CLOSE, DIV, EQUAL, FALSE, GREATER, LESS, MUL, NOT, OPEN, OR, SUB, SUM,
-    TRUE,
+    Operator, Span, Symbol, AND, CLOSE, COLON, COMMA, DIV, DOUBLE_QUOTE, EQUAL, ESCAPE, FALSE,
+    GREATER, LESS, MOD, MUL, NOT, NULL, OPEN, OR, QUESTION, SINGLE_QUOTE, SUB, SUM, TRUE,
 };
 
+/// `Lexer` splits the source text of an expression in symbols, together with the span of each
+/// symbol in the text. It stops at the first sequence of characters that is not a valid symbol.
 pub(crate) struct Lexer<'a> {
+    text: &'a str,
     tokenizer: Peekable<StringTokenizer<'a>>,
+    end: usize,
 }
 
 impl<'a> Lexer<'a> {
     pub(crate) fn new(text: &'a str) -> Lexer<'a> {
         Lexer {
+            text,
             tokenizer: StringTokenizer::new(
                 text,
                 &[
-                    AND, OR, NOT, GREATER, LESS, EQUAL, OPEN, CLOSE, SUM, SUB, MUL, DIV,
+                    AND,
+                    OR,
+                    NOT,
+                    GREATER,
+                    LESS,
+                    EQUAL,
+                    OPEN,
+                    CLOSE,
+                    COMMA,
+                    QUESTION,
+                    COLON,
+                    SUM,
+                    SUB,
+                    MUL,
+                    DIV,
+                    MOD,
+                    DOUBLE_QUOTE,
+                    SINGLE_QUOTE,
                 ],
             )
             .peekable(),
+            end: 0,
         }
     }
 
@@ -29,6 +52,7 @@ impl<'a> Lexer<'a> {
         match word {
             TRUE => Symbol::Boolean(true),
             FALSE => Symbol::Boolean(false),
+            NULL => Symbol::Null,
             _ => Symbol::Variable(word.to_string()),
         }
     }
@@ -38,93 +62,156 @@ impl<'a> Lexer<'a> {
         match character {
             AND => self.eat_and(),
             OR => self.eat_or(),
-            NOT => self.eat_ne(),
-            GREATER => self.eat_ge(),
-            LESS => self.eat_le(),
+            NOT => Some(self.eat_ne()),
+            GREATER => Some(self.eat_ge()),
+            LESS => Some(self.eat_le()),
             EQUAL => self.eat_eq(),
             SUM => Some(Lexer::eat_sum()),
             SUB => Some(Lexer::eat_sub()),
-            MUL => Some(Lexer::eat_mul()),
+            MUL => Some(self.eat_mul()),
             DIV => Some(Lexer::eat_div()),
+            MOD => Some(Lexer::eat_mod()),
             OPEN => Some(Symbol::Open),
             CLOSE => Some(Symbol::Close),
+            COMMA => Some(Symbol::Comma),
+            QUESTION => Some(Symbol::Question),
+            COLON => Some(Symbol::Colon),
+            DOUBLE_QUOTE | SINGLE_QUOTE => self.eat_text(character),
             _ => None,
         }
     }
 
-    fn symbol_from_number(number: f32) -> Symbol {
+    fn symbol_from_number(number: f64) -> Symbol {
         Symbol::Number(number)
     }
 
+    /// Consume the next token in case it's the `character`, extending the current symbol's span.
     #[inline]
-    fn eat_and(&mut self) -> Option<Symbol> {
-        let (token, _, _) = self.tokenizer.peek()?;
-
-        if token.is_character_equal(AND) {
-            self.tokenizer.next();
-            Some(Symbol::Operator(Operator::And))
-        } else {
-            None
+    fn eat_character(&mut self, character: char) -> bool {
+        match self
+            .tokenizer
+            .next_if(|(token, _, _)| token.is_character_equal(character))
+        {
+            Some((_, span, _)) => {
+                self.end = span.end;
+                true
+            }
+            None => false,
         }
     }
 
+    #[inline]
+    fn eat_and(&mut self) -> Option<Symbol> {
+        self.eat_character(AND)
+            .then_some(Symbol::Operator(Operator::And))
+    }
+
     #[inline]
     fn eat_or(&mut self) -> Option<Symbol> {
-        let (token, _, _) = self.tokenizer.peek()?;
+        self.eat_character(OR)
+            .then_some(Symbol::Operator(Operator::Or))
+    }
 
-        if token.is_character_equal(OR) {
-            self.tokenizer.next();
-            Some(Symbol::Operator(Operator::Or))
+    #[inline]
+    fn eat_ne(&mut self) -> Symbol {
+        if self.eat_character(EQUAL) {
+            Symbol::Operator(Operator::NotEqual)
         } else {
-            None
+            Symbol::Operator(Operator::Not)
         }
     }
 
     #[inline]
-    fn eat_ne(&mut self) -> Option<Symbol> {
-        let (token, _, _) = self.tokenizer.peek()?;
+    fn eat_eq(&mut self) -> Option<Symbol> {
+        self.eat_character(EQUAL)
+            .then_some(Symbol::Operator(Operator::Equal))
+    }
 
-        if token.is_character_equal(EQUAL) {
-            self.tokenizer.next();
-            Some(Symbol::Operator(Operator::NotEqual))
+    #[inline]
+    fn eat_ge(&mut self) -> Symbol {
+        if self.eat_character(EQUAL) {
+            Symbol::Operator(Operator::GreaterOrEqual)
         } else {
-            Some(Symbol::Operator(Operator::Not))
+            Symbol::Operator(Operator::Greater)
         }
     }
 
     #[inline]
-    fn eat_eq(&mut self) -> Option<Symbol> {
-        let (token, _, _) = self.tokenizer.peek()?;
-
-        if token.is_character_equal(EQUAL) {
-            self.tokenizer.next();
-            Some(Symbol::Operator(Operator::Equal))
+    fn eat_le(&mut self) -> Symbol {
+        if self.eat_character(EQUAL) {
+            Symbol::Operator(Operator::LessOrEqual)
         } else {
-            None
+            Symbol::Operator(Operator::Less)
         }
     }
 
+    /// Read a text literal enclosed by the `quote` character, starting right after the opening
+    /// quote. Escape sequences are resolved while reading.
     #[inline]
-    fn eat_ge(&mut self) -> Option<Symbol> {
-        let (token, _, _) = self.tokenizer.peek()?;
+    fn eat_text(&mut self, quote: char) -> Option<Symbol> {
+        let start = self.end;
+        let mut text = String::new();
+        let mut characters = self.text[start..].char_indices();
+
+        let end = loop {
+            let (index, character) = characters.next()?;
+
+            if character == ESCAPE {
+                let (_, escaped) = characters.next()?;
+                text.push(Lexer::unescape(escaped)?);
+            } else if character == quote {
+                break start + index + character.len_utf8();
+            } else {
+                text.push(character);
+            }
+        };
+
+        // Skip the tokens found by the tokenizer inside the quotes
+        while self
+            .tokenizer
+            .next_if(|(_, span, _)| span.start < end)
+            .is_some()
+        {}
+        self.end = end;
+
+        Some(Symbol::Text(text))
+    }
 
-        if token.is_character_equal(EQUAL) {
-            self.tokenizer.next();
-            Some(Symbol::Operator(Operator::GreaterOrEqual))
-        } else {
-            Some(Symbol::Operator(Operator::Greater))
+    /// Read the rest of a path whose fields are enclosed by quotes in brackets, e.g. `geo['lat']`,
+    /// starting right after the opening bracket. The quotes are kept in the path. No symbol is
+    /// read if a bracket isn't followed by a quoted field and a closing bracket.
+    fn eat_path(&mut self, start: usize) -> Option<Symbol> {
+        while self.text[..self.end].ends_with('[') {
+            let rest = &self.text[self.end..];
+            let quote = rest
+                .chars()
+                .next()
+                .filter(|c| matches!(*c, DOUBLE_QUOTE | SINGLE_QUOTE))?;
+            let end = self.end + rest[1..].find(quote)? + 2;
+            if !self.text[end..].starts_with(']') {
+                return None;
+            }
+
+            // Skip the tokens found by the tokenizer inside the quotes, up to the word which
+            // starts with the closing bracket
+            while let Some((_, span, _)) = self.tokenizer.next_if(|(_, span, _)| span.start <= end)
+            {
+                self.end = span.end;
+            }
         }
+
+        Some(Symbol::Variable(self.text[start..self.end].to_string()))
     }
 
     #[inline]
-    fn eat_le(&mut self) -> Option<Symbol> {
-        let (token, _, _) = self.tokenizer.peek()?;
-
-        if token.is_character_equal(EQUAL) {
-            self.tokenizer.next();
-            Some(Symbol::Operator(Operator::LessOrEqual))
-        } else {
-            Some(Symbol::Operator(Operator::Less))
+    fn unescape(character: char) -> Option<char> {
+        match character {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            ESCAPE | DOUBLE_QUOTE | SINGLE_QUOTE => Some(character),
+            _ => None,
         }
     }
 
@@ -136,40 +223,58 @@ impl<'a> Lexer<'a> {
         Symbol::Operator(Operator::Sub)
     }
 
-    fn eat_mul() -> Symbol {
-        Symbol::Operator(Operator::Mul)
+    #[inline]
+    fn eat_mul(&mut self) -> Symbol {
+        if self.eat_character(MUL) {
+            Symbol::Operator(Operator::Pow)
+        } else {
+            Symbol::Operator(Operator::Mul)
+        }
     }
 
     fn eat_div() -> Symbol {
         Symbol::Operator(Operator::Div)
     }
+
+    fn eat_mod() -> Symbol {
+        Symbol::Operator(Operator::Mod)
+    }
 }
 
 impl<'a> Iterator for Lexer<'a> {
-    type Item = Symbol;
+    type Item = (Symbol, Span);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let (token, _, slice) = self.tokenizer.next()?;
+        let (token, span, slice) = self.tokenizer.next()?;
+        let start = span.start;
+        self.end = span.end;
 
-        match token.kind() {
+        let symbol = match token.kind() {
             TokenKind::Character(character) => self.symbol_from_character(*character),
-            TokenKind::Word => slice.parse().map_or_else(
-                |_| Some(Lexer::symbol_from_word(slice)),
-                |number| Some(Lexer::symbol_from_number(number)),
-            ),
-        }
+            TokenKind::Word => match slice.parse() {
+                Ok(number) => Some(Lexer::symbol_from_number(number)),
+                Err(_) if slice.ends_with('[') => self.eat_path(start),
+                Err(_) => Some(Lexer::symbol_from_word(slice)),
+            },
+        }?;
+
+        Some((symbol, Span::new(start, self.end)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::lexer::Lexer;
-    use crate::symbols::{Operator, Symbol};
+    use crate::symbols::{Operator, Span, Symbol};
+
+    fn lex(text: &str) -> Vec<Symbol> {
+        Lexer::new(text).map(|(symbol, _)| symbol).collect()
+    }
 
     #[test]
     fn finds_all_boolean_symbols() {
-        let symbols: Vec<Symbol> = Lexer::new("true || false").collect();
+        let symbols = lex("true || false");
         assert_eq!(
             symbols,
             vec![
@@ -179,7 +284,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("true||false").collect();
+        let symbols = lex("true||false");
         assert_eq!(
             symbols,
             vec![
@@ -189,7 +294,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("true && false").collect();
+        let symbols = lex("true && false");
         assert_eq!(
             symbols,
             vec![
@@ -199,7 +304,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("true&&false").collect();
+        let symbols = lex("true&&false");
         assert_eq!(
             symbols,
             vec![
@@ -209,7 +314,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("true == false").collect();
+        let symbols = lex("true == false");
         assert_eq!(
             symbols,
             vec![
@@ -219,7 +324,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("true==false").collect();
+        let symbols = lex("true==false");
         assert_eq!(
             symbols,
             vec![
@@ -229,7 +334,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("true != false").collect();
+        let symbols = lex("true != false");
         assert_eq!(
             symbols,
             vec![
@@ -239,7 +344,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("true!=false").collect();
+        let symbols = lex("true!=false");
         assert_eq!(
             symbols,
             vec![
@@ -249,13 +354,13 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("!true").collect();
+        let symbols = lex("!true");
         assert_eq!(
             symbols,
             vec![Symbol::Operator(Operator::Not), Symbol::Boolean(true)]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("! true").collect();
+        let symbols = lex("! true");
         assert_eq!(
             symbols,
             vec![Symbol::Operator(Operator::Not), Symbol::Boolean(true)]
@@ -264,7 +369,7 @@ mod tests {
 
     #[test]
     fn finds_variable_in_boolean_expression() {
-        let symbols: Vec<Symbol> = Lexer::new("(foo && false)").collect();
+        let symbols = lex("(foo && false)");
 
         assert_eq!(
             symbols,
@@ -278,9 +383,222 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finds_text_symbols() {
+        let symbols = lex("category == \"fruit\"");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("category".to_string()),
+                Symbol::Operator(Operator::Equal),
+                Symbol::Text("fruit".to_string())
+            ]
+        );
+
+        let symbols = lex("'red apple'!=a");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Text("red apple".to_string()),
+                Symbol::Operator(Operator::NotEqual),
+                Symbol::Variable("a".to_string())
+            ]
+        );
+
+        let symbols = lex("(\"a && b\" == 'it\"s')");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Open,
+                Symbol::Text("a && b".to_string()),
+                Symbol::Operator(Operator::Equal),
+                Symbol::Text("it\"s".to_string()),
+                Symbol::Close
+            ]
+        );
+
+        let symbols = lex("\"\"");
+        assert_eq!(symbols, vec![Symbol::Text(String::new())]);
+    }
+
+    #[test]
+    fn finds_text_symbols_with_escape_sequences() {
+        let symbols = lex(r#""say \"hi\"" == 'it\'s'"#);
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Text("say \"hi\"".to_string()),
+                Symbol::Operator(Operator::Equal),
+                Symbol::Text("it's".to_string())
+            ]
+        );
+
+        let symbols = lex(r#""a\tb\nc\\d""#);
+        assert_eq!(symbols, vec![Symbol::Text("a\tb\nc\\d".to_string())]);
+    }
+
+    #[test]
+    fn stops_on_invalid_text_symbols() {
+        let symbols = lex("a == \"fruit");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("a".to_string()),
+                Symbol::Operator(Operator::Equal)
+            ]
+        );
+
+        let symbols = lex(r#""\q""#);
+        assert_eq!(symbols, vec![]);
+    }
+
+    #[test]
+    fn finds_function_call_symbols() {
+        let symbols = lex("round(a, 2)");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("round".to_string()),
+                Symbol::Open,
+                Symbol::Variable("a".to_string()),
+                Symbol::Comma,
+                Symbol::Number(2.0),
+                Symbol::Close
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_conditional_symbols() {
+        let symbols = lex("a > 10 ? a : 0");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("a".to_string()),
+                Symbol::Operator(Operator::Greater),
+                Symbol::Number(10.0),
+                Symbol::Question,
+                Symbol::Variable("a".to_string()),
+                Symbol::Colon,
+                Symbol::Number(0.0)
+            ]
+        );
+
+        let symbols = lex("a?'b:c':d");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("a".to_string()),
+                Symbol::Question,
+                Symbol::Text("b:c".to_string()),
+                Symbol::Colon,
+                Symbol::Variable("d".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_null_symbols() {
+        let symbols = lex("a != null");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("a".to_string()),
+                Symbol::Operator(Operator::NotEqual),
+                Symbol::Null
+            ]
+        );
+
+        let symbols = lex("nullable");
+        assert_eq!(symbols, vec![Symbol::Variable("nullable".to_string())]);
+    }
+
+    #[test]
+    fn finds_path_symbols() {
+        let symbols = lex("geo.lat + tags[0].size * 1.5");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("geo.lat".to_string()),
+                Symbol::Operator(Operator::Sum),
+                Symbol::Variable("tags[0].size".to_string()),
+                Symbol::Operator(Operator::Mul),
+                Symbol::Number(1.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_path_symbols_with_quoted_fields() {
+        let symbols = lex("geo['lat'] + a[\"b.c\"][0]['d e'].f * 'x'");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("geo['lat']".to_string()),
+                Symbol::Operator(Operator::Sum),
+                Symbol::Variable("a[\"b.c\"][0]['d e'].f".to_string()),
+                Symbol::Operator(Operator::Mul),
+                Symbol::Text("x".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_on_unterminated_path_symbols() {
+        assert_eq!(lex("a["), vec![]);
+        assert_eq!(lex("a['b"), vec![]);
+        assert_eq!(lex("a['b'"), vec![]);
+        assert_eq!(lex("a['b'] + c['d' + 1"), lex("a['b'] +"));
+    }
+
+    #[test]
+    fn finds_symbol_spans() {
+        let spans: Vec<Span> = Lexer::new("abc >= 'x y' && !(1)")
+            .map(|(_, span)| span)
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(0, 3),
+                Span::new(4, 6),
+                Span::new(7, 12),
+                Span::new(13, 15),
+                Span::new(16, 17),
+                Span::new(17, 18),
+                Span::new(18, 19),
+                Span::new(19, 20)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_arithmetic_symbols() {
+        let symbols = lex("a % 2 * 3");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("a".to_string()),
+                Symbol::Operator(Operator::Mod),
+                Symbol::Number(2.0),
+                Symbol::Operator(Operator::Mul),
+                Symbol::Number(3.0)
+            ]
+        );
+
+        let symbols = lex("a**2");
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol::Variable("a".to_string()),
+                Symbol::Operator(Operator::Pow),
+                Symbol::Number(2.0)
+            ]
+        );
+    }
+
     #[test]
     fn finds_all_number_symbols() {
-        let symbols: Vec<Symbol> = Lexer::new("3 > 1").collect();
+        let symbols = lex("3 > 1");
         assert_eq!(
             symbols,
             vec![
@@ -290,7 +608,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3>1").collect();
+        let symbols = lex("3>1");
         assert_eq!(
             symbols,
             vec![
@@ -300,7 +618,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3 >= 1").collect();
+        let symbols = lex("3 >= 1");
         assert_eq!(
             symbols,
             vec![
@@ -310,7 +628,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3>=1").collect();
+        let symbols = lex("3>=1");
         assert_eq!(
             symbols,
             vec![
@@ -320,7 +638,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3 < 1").collect();
+        let symbols = lex("3 < 1");
         assert_eq!(
             symbols,
             vec![
@@ -330,7 +648,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3<1").collect();
+        let symbols = lex("3<1");
         assert_eq!(
             symbols,
             vec![
@@ -340,7 +658,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3 <= 1").collect();
+        let symbols = lex("3 <= 1");
         assert_eq!(
             symbols,
             vec![
@@ -350,7 +668,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3<=1").collect();
+        let symbols = lex("3<=1");
         assert_eq!(
             symbols,
             vec![
@@ -360,7 +678,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3 == 1").collect();
+        let symbols = lex("3 == 1");
         assert_eq!(
             symbols,
             vec![
@@ -370,7 +688,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3==1").collect();
+        let symbols = lex("3==1");
         assert_eq!(
             symbols,
             vec![
@@ -380,7 +698,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3 != 1").collect();
+        let symbols = lex("3 != 1");
         assert_eq!(
             symbols,
             vec![
@@ -390,7 +708,7 @@ mod tests {
             ]
         );
 
-        let symbols: Vec<Symbol> = Lexer::new("3!=1").collect();
+        let symbols = lex("3!=1");
         assert_eq!(
             symbols,
             vec![
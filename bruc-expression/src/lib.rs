@@ -2,12 +2,17 @@ use crate::error::Result;
 use crate::expr::Expression;
 use crate::parser::Parser;
 
+pub mod compile;
 pub mod data;
+pub mod date;
 pub mod error;
 pub mod expr;
+pub mod function;
 mod lexer;
 mod parser;
+pub mod path;
 pub mod symbols;
+pub mod types;
 pub mod vars;
 
 #[cfg(feature = "serde")]
@@ -1,47 +1,69 @@
-use std::iter::Peekable;
+use std::iter::{Fuse, Peekable};
 
-use crate::error::{Error, ParseError, Result};
-use crate::expr::{Cons, Expression};
+use crate::error::{Error, Expected, Location, ParseError, Result};
+use crate::expr::{Call, Cons, Expression};
+use crate::function::Function;
 use crate::lexer::Lexer;
-use crate::symbols::{Operator, Symbol};
+use crate::symbols::{Operator, Span, Symbol};
 
-fn prefix_binding_power(operator: Operator) -> Result<u8> {
+fn prefix_binding_power(operator: Operator) -> Option<u8> {
     match operator {
-        Operator::Sum | Operator::Sub => Ok(11),
-        Operator::Not => Ok(12),
-        _ => Err(Error::Parse(ParseError::BindingPowerMissing)),
+        Operator::Sum | Operator::Sub => Some(13),
+        Operator::Not => Some(14),
+        _ => None,
     }
 }
 
-fn infix_binding_power(operator: Operator) -> Result<(u8, u8)> {
+/// Binding power of the infix operators. The power operator `**` binds tighter than the prefix
+/// operators, and it's right associative, so that `-a ** b ** c` is parsed as `-(a ** (b ** c))`.
+fn infix_binding_power(operator: Operator) -> Option<(u8, u8)> {
     match operator {
-        Operator::Or => Ok((1, 2)),
-        Operator::And => Ok((3, 4)),
+        Operator::Or => Some((3, 4)),
+        Operator::And => Some((5, 6)),
         Operator::Equal
         | Operator::NotEqual
         | Operator::Greater
         | Operator::GreaterOrEqual
         | Operator::Less
-        | Operator::LessOrEqual => Ok((5, 6)),
-        Operator::Sum | Operator::Sub => Ok((7, 8)),
-        Operator::Mul | Operator::Div => Ok((9, 10)),
-        Operator::Not => Err(Error::Parse(ParseError::BindingPowerMissing)),
+        | Operator::LessOrEqual => Some((7, 8)),
+        Operator::Sum | Operator::Sub => Some((9, 10)),
+        Operator::Mul | Operator::Div | Operator::Mod => Some((11, 12)),
+        Operator::Pow => Some((16, 15)),
+        Operator::Not => None,
     }
 }
 
+/// Binding power of the conditional `?:` operator. It binds the loosest, and it's right
+/// associative, so that `a ? b : c ? d : e` is parsed as `a ? b : (c ? d : e)`.
+const CONDITIONAL_BINDING_POWER: (u8, u8) = (2, 1);
+
 pub(crate) struct Parser<'a> {
-    lexer: Peekable<Lexer<'a>>,
+    text: &'a str,
+    lexer: Peekable<Fuse<Lexer<'a>>>,
+    end: usize,
 }
 
 impl<'a> Parser<'a> {
     pub(crate) fn new(input: &'a str) -> Parser<'a> {
         Parser {
-            lexer: Lexer::new(input).peekable(),
+            text: input,
+            lexer: Lexer::new(input).fuse().peekable(),
+            end: 0,
         }
     }
 
+    /// Parse the whole input as a single expression.
     pub(crate) fn parse(&mut self) -> Result<Expression> {
-        self.expression(0)
+        let expression = self.expression(0)?;
+
+        if self.lexer.peek().is_some() {
+            return Err(self.unexpected(Expected::Operator));
+        }
+        if let Some(error) = self.invalid_token() {
+            return Err(error);
+        }
+
+        Ok(expression)
     }
 
     #[inline]
@@ -49,12 +71,20 @@ impl<'a> Parser<'a> {
         let mut left = self.factor()?;
 
         loop {
-            let infix: Option<(Operator, u8)> = self
-                .lexer
-                .peek()
-                .and_then(Symbol::operator)
-                .and_then(|operator| {
-                    if let Ok((left_bp, right_bp)) = infix_binding_power(operator) {
+            if self.peek() == Some(&Symbol::Question) {
+                let (left_bp, right_bp) = CONDITIONAL_BINDING_POWER;
+                if left_bp < min_binding_power {
+                    break;
+                }
+
+                self.next();
+                left = self.conditional(left, right_bp)?;
+                continue;
+            }
+
+            let infix: Option<(Operator, u8)> =
+                self.peek().and_then(Symbol::operator).and_then(|operator| {
+                    if let Some((left_bp, right_bp)) = infix_binding_power(operator) {
                         if left_bp < min_binding_power {
                             None
                         } else {
@@ -66,7 +96,7 @@ impl<'a> Parser<'a> {
                 });
 
             if let Some((operator, bp)) = infix {
-                self.lexer.next();
+                self.next();
                 let right = self.expression(bp)?;
 
                 left = Expression::from(Cons::Binary(operator, (left, right)));
@@ -78,37 +108,191 @@ impl<'a> Parser<'a> {
         Ok(left)
     }
 
+    /// Parse both branches of a conditional expression, once its condition and the `?` symbol
+    /// have been read.
+    #[inline]
+    fn conditional(&mut self, condition: Expression, bp: u8) -> Result<Expression> {
+        let then = self.expression(0)?;
+
+        if !self.next_if_eq(&Symbol::Colon) {
+            return Err(self.unexpected(Expected::Colon));
+        }
+
+        let otherwise = self.expression(bp)?;
+
+        Ok(Expression::from(Cons::Conditional(
+            condition,
+            (then, otherwise),
+        )))
+    }
+
     #[inline]
     fn factor(&mut self) -> Result<Expression> {
-        let symbol = self
-            .lexer
-            .next()
-            .ok_or(Error::Parse(ParseError::InvalidExpression))?;
+        let Some((symbol, span)) = self.next() else {
+            return Err(self.unexpected(Expected::Expression));
+        };
 
         match symbol {
-            Symbol::Boolean(_) | Symbol::Number(_) | Symbol::Variable(_) => {
+            Symbol::Variable(name) => {
+                if self.next_if_eq(&Symbol::Open) {
+                    self.call(name, span)
+                } else {
+                    Ok(Expression::Atom(Symbol::Variable(name)))
+                }
+            }
+            Symbol::Boolean(_) | Symbol::Number(_) | Symbol::Text(_) | Symbol::Null => {
                 Ok(Expression::Atom(symbol))
             }
             Symbol::Open => {
-                let expression = self.parse()?;
-                self.lexer.next();
+                let expression = self.expression(0)?;
+
+                if !self.next_if_eq(&Symbol::Close) {
+                    return Err(self.unexpected(Expected::Close));
+                }
 
                 Ok(expression)
             }
             Symbol::Operator(operator) => {
-                let bp = prefix_binding_power(operator)?;
+                let bp = prefix_binding_power(operator).ok_or_else(|| {
+                    self.error(
+                        ParseError::BindingPowerMissing,
+                        span,
+                        true,
+                        Some(Expected::Expression),
+                    )
+                })?;
                 let root = self.expression(bp)?;
 
                 Ok(Expression::from(Cons::Unary(operator, root)))
             }
-            Symbol::Close => Err(Error::Parse(ParseError::InvalidExpression)),
+            Symbol::Close | Symbol::Comma | Symbol::Question | Symbol::Colon => Err(self.error(
+                ParseError::InvalidExpression,
+                span,
+                true,
+                Some(Expected::Expression),
+            )),
+        }
+    }
+
+    /// Parse the arguments of a function call, once the function's name and the opening
+    /// parenthesis have been read.
+    #[inline]
+    fn call(&mut self, name: String, span: Span) -> Result<Expression> {
+        let Some(function) = Function::from_name(&name) else {
+            return Err(self.error(ParseError::UnknownFunction(name), span, false, None));
+        };
+
+        let mut args = Vec::new();
+
+        if !self.next_if_eq(&Symbol::Close) {
+            loop {
+                args.push(self.expression(0)?);
+
+                if self.next_if_eq(&Symbol::Comma) {
+                    continue;
+                }
+                if self.next_if_eq(&Symbol::Close) {
+                    break;
+                }
+
+                return Err(self.unexpected(Expected::CommaOrClose));
+            }
+        }
+
+        if !function.arity().accepts(args.len()) {
+            return Err(self.error(
+                ParseError::InvalidArguments(function, args.len()),
+                Span::new(span.start, self.end),
+                false,
+                None,
+            ));
+        }
+
+        Ok(Expression::from(Call::new(function, args)))
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<&Symbol> {
+        self.lexer.peek().map(|(symbol, _)| symbol)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<(Symbol, Span)> {
+        let (symbol, span) = self.lexer.next()?;
+        self.end = span.end;
+
+        Some((symbol, span))
+    }
+
+    #[inline]
+    fn next_if_eq(&mut self, expected: &Symbol) -> bool {
+        match self.lexer.next_if(|(symbol, _)| symbol == expected) {
+            Some((_, span)) => {
+                self.end = span.end;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Build the error for the next symbol, which is not the `expected` one. The error points to
+    /// the end of the input when there are no symbols left.
+    fn unexpected(&mut self, expected: Expected) -> Error {
+        if let Some(error) = self.invalid_token() {
+            return error;
+        }
+
+        match self.lexer.peek() {
+            Some((_, span)) => {
+                let span = *span;
+                self.error(ParseError::InvalidExpression, span, true, Some(expected))
+            }
+            None => {
+                let span = Span::new(self.text.len(), self.text.len());
+                self.error(ParseError::InvalidExpression, span, false, Some(expected))
+            }
+        }
+    }
+
+    /// Check if the lexer stopped before the end of the input, because it found a sequence of
+    /// characters that is not a valid symbol.
+    fn invalid_token(&mut self) -> Option<Error> {
+        if self.lexer.peek().is_some() {
+            return None;
+        }
+
+        let rest = &self.text[self.end..];
+        let start = self.end + (rest.len() - rest.trim_start().len());
+        if start == self.text.len() {
+            return None;
         }
+
+        let end = self.text[start..]
+            .find(char::is_whitespace)
+            .map_or(self.text.len(), |index| start + index);
+
+        Some(self.error(ParseError::InvalidToken, Span::new(start, end), true, None))
+    }
+
+    #[inline]
+    fn error(
+        &self,
+        error: ParseError,
+        span: Span,
+        with_token: bool,
+        expected: Option<Expected>,
+    ) -> Error {
+        let token = with_token.then(|| &self.text[span.start..span.end]);
+        Error::Parse(error, Location::new(self.text, span, token, expected))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::{Error, Expected, ParseError};
+    use crate::function::Function;
     use crate::parser::Parser;
+    use crate::symbols::Span;
 
     #[test]
     fn parse_atoms() {
@@ -132,6 +316,24 @@ mod tests {
 
         let expression = Parser::new("a + b * c / d + e").parse().unwrap();
         assert_eq!(expression.to_string(), "(+ (+ a (/ (* b c) d)) e)");
+
+        let expression = Parser::new("a % b * c + d").parse().unwrap();
+        assert_eq!(expression.to_string(), "(+ (* (% a b) c) d)");
+    }
+
+    #[test]
+    fn parse_power() {
+        let expression = Parser::new("a ** b ** c").parse().unwrap();
+        assert_eq!(expression.to_string(), "(** a (** b c))");
+
+        let expression = Parser::new("2 * a ** 2").parse().unwrap();
+        assert_eq!(expression.to_string(), "(* 2 (** a 2))");
+
+        let expression = Parser::new("-a ** 2").parse().unwrap();
+        assert_eq!(expression.to_string(), "(- (** a 2))");
+
+        let expression = Parser::new("a ** -2").parse().unwrap();
+        assert_eq!(expression.to_string(), "(** a (- 2))");
     }
 
     #[test]
@@ -197,6 +399,217 @@ mod tests {
         assert_eq!(expression.to_string(), "(&& (== (+ a b) c) d)")
     }
 
+    #[test]
+    fn parse_text() {
+        let expression = Parser::new("\"fruit\"").parse().unwrap();
+        assert_eq!(expression.to_string(), "\"fruit\"");
+
+        let expression = Parser::new("category == 'fruit'").parse().unwrap();
+        assert_eq!(expression.to_string(), "(== category \"fruit\")");
+
+        let expression = Parser::new("a < \"b\" && c != 'say \\'hi\\''")
+            .parse()
+            .unwrap();
+        assert_eq!(
+            expression.to_string(),
+            "(&& (< a \"b\") (!= c \"say 'hi'\"))"
+        );
+    }
+
+    #[test]
+    fn parse_function_calls() {
+        let expression = Parser::new("sqrt(a)").parse().unwrap();
+        assert_eq!(expression.to_string(), "(sqrt a)");
+
+        let expression = Parser::new("abs(b - c)").parse().unwrap();
+        assert_eq!(expression.to_string(), "(abs (- b c))");
+
+        let expression = Parser::new("round(y, 2) + 1").parse().unwrap();
+        assert_eq!(expression.to_string(), "(+ (round y 2) 1)");
+
+        let expression = Parser::new("-pow(a, 2) * max(a, min(b, c))")
+            .parse()
+            .unwrap();
+        assert_eq!(
+            expression.to_string(),
+            "(* (- (pow a 2)) (max a (min b c)))"
+        );
+
+        let expression = Parser::new("clamp((a), 0, 10) > 2").parse().unwrap();
+        assert_eq!(expression.to_string(), "(> (clamp a 0 10) 2)");
+
+        let expression = Parser::new("isValid(a) && a != null").parse().unwrap();
+        assert_eq!(expression.to_string(), "(&& (isValid a) (!= a null))");
+    }
+
+    #[test]
+    fn doesnt_parse_invalid_function_calls() {
+        let result = Parser::new("foo(a)").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(ParseError::UnknownFunction(name), _)) if name == "foo"
+        ));
+
+        let result = Parser::new("sqrt(a, b)").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(
+                ParseError::InvalidArguments(Function::Sqrt, 2),
+                _
+            ))
+        ));
+
+        let result = Parser::new("pow(a)").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(
+                ParseError::InvalidArguments(Function::Pow, 1),
+                _
+            ))
+        ));
+
+        let result = Parser::new("max()").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(
+                ParseError::InvalidArguments(Function::Max, 0),
+                _
+            ))
+        ));
+
+        let result = Parser::new("min(a, b").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(ParseError::InvalidExpression, _))
+        ));
+    }
+
+    #[test]
+    fn parse_conditional() {
+        let expression = Parser::new("a > 10 ? a : 0").parse().unwrap();
+        assert_eq!(expression.to_string(), "(? (> a 10) a 0)");
+
+        let expression = Parser::new("a || b ? c + 1 : d * 2").parse().unwrap();
+        assert_eq!(expression.to_string(), "(? (|| a b) (+ c 1) (* d 2))");
+
+        let expression = Parser::new("a ? b : c ? d : e").parse().unwrap();
+        assert_eq!(expression.to_string(), "(? a b (? c d e))");
+
+        let expression = Parser::new("a ? b ? c : d : e").parse().unwrap();
+        assert_eq!(expression.to_string(), "(? a (? b c d) e)");
+
+        let expression = Parser::new("(a ? 1 : 2) + 3").parse().unwrap();
+        assert_eq!(expression.to_string(), "(+ (? a 1 2) 3)");
+
+        let expression = Parser::new("max(a ? b : c, 1)").parse().unwrap();
+        assert_eq!(expression.to_string(), "(max (? a b c) 1)");
+    }
+
+    #[test]
+    fn doesnt_parse_invalid_conditional() {
+        let result = Parser::new("a ? b").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(ParseError::InvalidExpression, _))
+        ));
+
+        let result = Parser::new("a ? b c").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(ParseError::InvalidExpression, _))
+        ));
+
+        let result = Parser::new("? a : b").parse();
+        assert!(matches!(
+            result,
+            Err(Error::Parse(ParseError::InvalidExpression, _))
+        ));
+    }
+
+    #[test]
+    fn doesnt_parse_invalid_expressions() {
+        let result = Parser::new("a >> 2").parse();
+        let Err(Error::Parse(ParseError::BindingPowerMissing, location)) = result else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert_eq!(location.span, Span::new(3, 4));
+        assert_eq!(location.token.as_deref(), Some(">"));
+        assert_eq!(location.expected, Some(Expected::Expression));
+
+        let result = Parser::new("a + ").parse();
+        let Err(Error::Parse(ParseError::InvalidExpression, location)) = result else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert_eq!(location.span, Span::new(4, 4));
+        assert_eq!(location.token, None);
+        assert_eq!(location.expected, Some(Expected::Expression));
+
+        let result = Parser::new("(a + b").parse();
+        let Err(Error::Parse(ParseError::InvalidExpression, location)) = result else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert_eq!(location.expected, Some(Expected::Close));
+
+        let result = Parser::new("a b").parse();
+        let Err(Error::Parse(ParseError::InvalidExpression, location)) = result else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert_eq!(location.span, Span::new(2, 3));
+        assert_eq!(location.token.as_deref(), Some("b"));
+        assert_eq!(location.expected, Some(Expected::Operator));
+
+        let result = Parser::new("a = 2").parse();
+        let Err(Error::Parse(ParseError::InvalidToken, location)) = result else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert_eq!(location.span, Span::new(2, 3));
+        assert_eq!(location.token.as_deref(), Some("="));
+
+        let result = Parser::new("a == 'fruit").parse();
+        let Err(Error::Parse(ParseError::InvalidToken, location)) = result else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert_eq!(location.span, Span::new(5, 11));
+
+        for text in ["a[", "a['b", "a['b'"] {
+            let result = Parser::new(text).parse();
+            let Err(Error::Parse(ParseError::InvalidToken, location)) = result else {
+                panic!("unexpected result: {result:?}");
+            };
+            assert_eq!(location.span, Span::new(0, text.len()));
+        }
+    }
+
+    #[test]
+    fn displays_parse_error_location() {
+        let error = Parser::new("a >> 2").parse().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "ParseError::BindingPowerMissing at 1:4: unexpected `>`, expected an expression\n\
+             a >> 2\n   ^"
+        );
+
+        let error = Parser::new("a > 1 && b <").parse().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "ParseError::InvalidExpression at 1:13: unexpected end of input, expected an \
+             expression\na > 1 && b <\n            ^"
+        );
+
+        let error = Parser::new("1 + foo(a)").parse().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "ParseError::UnknownFunction: foo at 1:5\n1 + foo(a)\n    ^^^"
+        );
+
+        let error = Parser::new("sqrt(a, b)").parse().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "ParseError::InvalidArguments: sqrt expects 1 arguments, found 2 at 1:1\n\
+             sqrt(a, b)\n^^^^^^^^^^"
+        );
+    }
+
     #[test]
     fn parse_open() {
         let expression = Parser::new("(a)").parse().unwrap();
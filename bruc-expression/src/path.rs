@@ -0,0 +1,220 @@
+use std::fmt::Write;
+
+use crate::data::DataItem;
+
+/// `Segment` is a step of a path to a nested data item, either a field of an object or an index
+/// of an array.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Segment<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Split a path to a nested data item, e.g. `geo.lat`, `tags[0]` or `geo['lat']`, into its
+/// segments. The path always starts with a field. Fields in brackets are enclosed by single or
+/// double quotes, with no escape sequences, so a field can't contain both kinds of quotes. No
+/// segments are returned if the path is malformed.
+pub fn segments(path: &str) -> Option<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+
+    let end = rest.find(['.', '[']).unwrap_or(rest.len());
+    if end == 0 {
+        return None;
+    }
+    segments.push(Segment::Field(&rest[..end]));
+    rest = &rest[end..];
+
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix('.') {
+            let end = tail.find(['.', '[']).unwrap_or(tail.len());
+            if end == 0 {
+                return None;
+            }
+            segments.push(Segment::Field(&tail[..end]));
+            rest = &tail[end..];
+        } else {
+            let tail = rest.strip_prefix('[')?;
+            let (segment, tail) = match tail.chars().next()? {
+                quote @ ('"' | '\'') => {
+                    let (field, tail) = tail[1..].split_once(quote)?;
+                    (Segment::Field(field), tail.strip_prefix(']')?)
+                }
+                _ => {
+                    let (index, tail) = tail.split_once(']')?;
+                    (Segment::Index(index.parse().ok()?), tail)
+                }
+            };
+            segments.push(segment);
+            rest = tail;
+        }
+    }
+
+    Some(segments)
+}
+
+/// Field at the root of a path to a nested data item, e.g. `geo` for `geo.lat`.
+pub fn root(path: &str) -> &str {
+    match path.find(['.', '[']) {
+        Some(end) if end > 0 => &path[..end],
+        _ => path,
+    }
+}
+
+/// Write a path in its canonical form, where fields are separated by dots, unless they contain
+/// characters of the path syntax, and indices are enclosed in brackets.
+pub fn normalize(path: &str) -> Option<String> {
+    let segments = segments(path)?;
+    let (Segment::Field(root), rest) = segments.split_first()? else {
+        return None;
+    };
+
+    let mut normalized = root.to_string();
+    for segment in rest {
+        append(&mut normalized, *segment);
+    }
+
+    Some(normalized)
+}
+
+/// Append a segment to a path in its canonical form. Fields with characters of the path syntax
+/// are enclosed by double quotes, or by single quotes if they contain double quotes.
+pub fn append(path: &mut String, segment: Segment) {
+    // Writing to a string doesn't fail
+    let _ = match segment {
+        Segment::Field(field) if field.contains('"') => write!(path, "['{field}']"),
+        Segment::Field(field) if field.contains(['.', '[', ']', '\'']) => {
+            write!(path, "[\"{field}\"]")
+        }
+        Segment::Field(field) => write!(path, ".{field}"),
+        Segment::Index(index) => write!(path, "[{index}]"),
+    };
+}
+
+/// Resolve a path to a nested data item, with the help of a `field` lookup for the root fields.
+/// Fields whose name matches the whole path are preferred over nested items.
+pub fn resolve<'a>(
+    path: &str,
+    field: impl Fn(&str) -> Option<&'a DataItem>,
+) -> Option<&'a DataItem> {
+    if let Some(item) = field(path) {
+        return Some(item);
+    }
+
+    let segments = segments(path)?;
+    let (Segment::Field(root), rest) = segments.split_first()? else {
+        return None;
+    };
+    if rest.is_empty() {
+        return None;
+    }
+
+    rest.iter()
+        .try_fold(field(root)?, |item, segment| match (segment, item) {
+            (Segment::Field(name), DataItem::Object(object)) => object.get(*name),
+            (Segment::Index(index), DataItem::Array(array)) => array.get(*index),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::data::DataItem;
+    use crate::path::{normalize, resolve, root, segments, Segment};
+
+    #[test]
+    fn splits_paths() {
+        assert_eq!(segments("a"), Some(vec![Segment::Field("a")]));
+        assert_eq!(
+            segments("geo.lat"),
+            Some(vec![Segment::Field("geo"), Segment::Field("lat")])
+        );
+        assert_eq!(
+            segments("tags[0].name"),
+            Some(vec![
+                Segment::Field("tags"),
+                Segment::Index(0),
+                Segment::Field("name")
+            ])
+        );
+        assert_eq!(
+            segments("a['b.c'][\"d\"]"),
+            Some(vec![
+                Segment::Field("a"),
+                Segment::Field("b.c"),
+                Segment::Field("d")
+            ])
+        );
+    }
+
+    #[test]
+    fn doesnt_split_malformed_paths() {
+        assert_eq!(segments(""), None);
+        assert_eq!(segments(".a"), None);
+        assert_eq!(segments("a..b"), None);
+        assert_eq!(segments("a["), None);
+        assert_eq!(segments("a[x]"), None);
+        assert_eq!(segments("a['b"), None);
+        assert_eq!(segments("a[0]b"), None);
+    }
+
+    #[test]
+    fn finds_roots_of_paths() {
+        assert_eq!(root("a"), "a");
+        assert_eq!(root("geo.lat"), "geo");
+        assert_eq!(root("tags[0]"), "tags");
+    }
+
+    #[test]
+    fn normalizes_paths() {
+        assert_eq!(normalize("geo[\"lat\"]"), Some("geo.lat".to_string()));
+        assert_eq!(normalize("tags[0].name"), Some("tags[0].name".to_string()));
+        assert_eq!(normalize("a[\"b.c\"]"), Some("a[\"b.c\"]".to_string()));
+        assert_eq!(normalize("a['b\"c']"), Some("a['b\"c']".to_string()));
+        assert_eq!(normalize("a.b\"c"), Some("a['b\"c']".to_string()));
+    }
+
+    #[test]
+    fn normalizes_paths_to_the_same_segments() {
+        for path in [
+            "geo.lat",
+            "tags[0].name",
+            "a['b.c'][1]",
+            "a[\"b'c\"]",
+            "a['b\"c'].d",
+            "a.b\"c",
+            "a.b'c",
+            "a[\"[b]\"]['\"']",
+        ] {
+            let normalized = normalize(path).unwrap();
+            assert_eq!(segments(&normalized), segments(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let item = DataItem::Object(BTreeMap::from([
+            ("lat".to_string(), DataItem::Number(1.0)),
+            (
+                "tags".to_string(),
+                DataItem::Array(vec![DataItem::from("a"), DataItem::from("b")]),
+            ),
+        ]));
+        let flat = DataItem::Number(2.0);
+        let field = |name: &str| match name {
+            "geo" => Some(&item),
+            "geo.lon" => Some(&flat),
+            _ => None,
+        };
+
+        assert_eq!(resolve("geo.lat", field), Some(&DataItem::Number(1.0)));
+        assert_eq!(resolve("geo.tags[1]", field), Some(&DataItem::from("b")));
+        assert_eq!(resolve("geo.lon", field), Some(&DataItem::Number(2.0)));
+        assert_eq!(resolve("geo.tags[2]", field), None);
+        assert_eq!(resolve("geo.lat.value", field), None);
+        assert_eq!(resolve("geo[0]", field), None);
+        assert_eq!(resolve("missing.lat", field), None);
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use serde::de::{Unexpected, Visitor};
+use serde::de::Visitor;
 use serde::{Deserialize, Deserializer};
 
 use crate::expr::Expression;
@@ -25,9 +25,7 @@ where
                 self,
                 value: &'a str,
             ) -> Result<Self::Value, E> {
-                PredicateParser::new(value)
-                    .parse()
-                    .map_err(|_| E::invalid_value(Unexpected::Other(value), &"valid predicate"))
+                PredicateParser::new(value).parse().map_err(E::custom)
             }
         }
 
@@ -2,6 +2,7 @@ use std::fmt;
 
 pub(crate) const TRUE: &str = "true";
 pub(crate) const FALSE: &str = "false";
+pub(crate) const NULL: &str = "null";
 pub(crate) const AND: char = '&';
 pub(crate) const OR: char = '|';
 pub(crate) const NOT: char = '!';
@@ -10,10 +11,30 @@ pub(crate) const LESS: char = '<';
 pub(crate) const EQUAL: char = '=';
 pub(crate) const OPEN: char = '(';
 pub(crate) const CLOSE: char = ')';
+pub(crate) const COMMA: char = ',';
+pub(crate) const QUESTION: char = '?';
+pub(crate) const COLON: char = ':';
 pub(crate) const SUM: char = '+';
 pub(crate) const SUB: char = '-';
 pub(crate) const MUL: char = '*';
 pub(crate) const DIV: char = '/';
+pub(crate) const MOD: char = '%';
+pub(crate) const DOUBLE_QUOTE: char = '"';
+pub(crate) const SINGLE_QUOTE: char = '\'';
+pub(crate) const ESCAPE: char = '\\';
+
+/// `Span` delimits the byte offsets of a symbol in the source text of an expression.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+}
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Operator {
@@ -24,6 +45,8 @@ pub enum Operator {
     Sub,
     Mul,
     Div,
+    Mod,
+    Pow,
     Equal,
     NotEqual,
     Greater,
@@ -39,6 +62,8 @@ impl fmt::Display for Operator {
             Operator::Sub => write!(f, "-"),
             Operator::Mul => write!(f, "*"),
             Operator::Div => write!(f, "/"),
+            Operator::Mod => write!(f, "%"),
+            Operator::Pow => write!(f, "**"),
             Operator::And => write!(f, "&&"),
             Operator::Or => write!(f, "||"),
             Operator::Equal => write!(f, "=="),
@@ -57,8 +82,13 @@ pub enum Symbol {
     Operator(Operator),
     Open,
     Close,
-    Number(f32),
+    Comma,
+    Question,
+    Colon,
+    Number(f64),
     Boolean(bool),
+    Text(String),
+    Null,
     Variable(String),
 }
 
@@ -82,6 +112,8 @@ impl fmt::Display for Symbol {
                 Operator::Sub => write!(f, "{SUB}"),
                 Operator::Mul => write!(f, "{MUL}"),
                 Operator::Div => write!(f, "{DIV}"),
+                Operator::Mod => write!(f, "{MOD}"),
+                Operator::Pow => write!(f, "{MUL}{MUL}"),
                 Operator::Equal => write!(f, "{EQUAL}"),
                 Operator::NotEqual => write!(f, "{NOT}{EQUAL}"),
                 Operator::Greater => write!(f, "{GREATER}"),
@@ -91,8 +123,25 @@ impl fmt::Display for Symbol {
             },
             Symbol::Open => write!(f, "{OPEN}"),
             Symbol::Close => write!(f, "{CLOSE}"),
+            Symbol::Comma => write!(f, "{COMMA}"),
+            Symbol::Question => write!(f, "{QUESTION}"),
+            Symbol::Colon => write!(f, "{COLON}"),
             Symbol::Number(number) => write!(f, "{number}"),
             Symbol::Boolean(boolean) => write!(f, "{boolean}"),
+            Symbol::Text(text) => {
+                write!(f, "{DOUBLE_QUOTE}")?;
+                for character in text.chars() {
+                    match character {
+                        DOUBLE_QUOTE | ESCAPE => write!(f, "{ESCAPE}{character}")?,
+                        '\n' => write!(f, "{ESCAPE}n")?,
+                        '\t' => write!(f, "{ESCAPE}t")?,
+                        '\r' => write!(f, "{ESCAPE}r")?,
+                        _ => write!(f, "{character}")?,
+                    }
+                }
+                write!(f, "{DOUBLE_QUOTE}")
+            }
+            Symbol::Null => write!(f, "{NULL}"),
             Symbol::Variable(variable) => write!(f, "{variable}"),
         }
     }
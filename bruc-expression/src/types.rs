@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::data::DataItem;
+use crate::error::{Error, Result, TypeError};
+use crate::expr::{Call, Cons, Expression};
+use crate::path::{self, Segment};
+use crate::symbols::{Operator, Symbol};
+
+/// `DataType` defines the type of a `DataItem`, or the type an expression evaluates to.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum DataType {
+    Bool,
+    Number,
+    Text,
+    Date,
+    Array,
+    Object,
+    Null,
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::Bool => write!(f, "bool"),
+            DataType::Number => write!(f, "number"),
+            DataType::Text => write!(f, "text"),
+            DataType::Date => write!(f, "date"),
+            DataType::Array => write!(f, "array"),
+            DataType::Object => write!(f, "object"),
+            DataType::Null => write!(f, "null"),
+        }
+    }
+}
+
+impl From<&DataItem> for DataType {
+    fn from(item: &DataItem) -> Self {
+        match item {
+            DataItem::Bool(_) => DataType::Bool,
+            DataItem::Number(_) => DataType::Number,
+            DataItem::Text(_) => DataType::Text,
+            DataItem::Date(_) => DataType::Date,
+            DataItem::Array(_) => DataType::Array,
+            DataItem::Object(_) => DataType::Object,
+            DataItem::Null => DataType::Null,
+        }
+    }
+}
+
+/// `Schema` maps field names to the `DataType` of their values.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Schema {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    instance: HashMap<String, DataType>,
+}
+
+impl Schema {
+    pub fn new() -> Schema {
+        Schema {
+            instance: HashMap::new(),
+        }
+    }
+
+    pub fn from_pairs(pairs: Vec<(&str, DataType)>) -> Schema {
+        let mut schema = Schema::new();
+        for (key, data_type) in pairs {
+            schema.insert(key, data_type);
+        }
+        schema
+    }
+
+    /// Find the type of a field. Paths to nested items are looked up in their canonical form,
+    /// see `path::normalize`.
+    pub fn find(&self, key: &str) -> Option<DataType> {
+        self.instance.get(key).copied().or_else(|| {
+            let key = path::normalize(key)?;
+            self.instance.get(&key).copied()
+        })
+    }
+
+    pub fn insert(&mut self, key: &str, data_type: DataType) {
+        self.instance.insert(key.to_string(), data_type);
+    }
+
+    /// Infer the type of a field from one of its items, together with the types of the nested
+    /// items of arrays and objects, which are keyed by their canonical path. The first type found
+    /// for a field is kept, while null types are replaced once another type is found.
+    pub fn infer(&mut self, key: &str, item: &DataItem) {
+        if matches!(self.find(key), None | Some(DataType::Null)) {
+            self.insert(key, DataType::from(item));
+        }
+
+        match item {
+            DataItem::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let mut path = key.to_string();
+                    path::append(&mut path, Segment::Index(index));
+                    self.infer(&path, item);
+                }
+            }
+            DataItem::Object(object) => {
+                for (name, item) in object {
+                    let mut path = key.to_string();
+                    path::append(&mut path, Segment::Field(name));
+                    self.infer(&path, item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instance.is_empty()
+    }
+
+    /// Names of the fields defined in the schema.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.instance.keys().map(String::as_str)
+    }
+}
+
+/// `Typed` resolves the `DataType` of an expression without interpreting it, by checking that
+/// its variables and operands are used with compatible types.
+pub trait Typed {
+    fn check(&self, schema: &Schema) -> Result<DataType>;
+}
+
+impl Typed for Expression {
+    fn check(&self, schema: &Schema) -> Result<DataType> {
+        match self {
+            Expression::Atom(symbol) => match symbol {
+                Symbol::Boolean(_) => Ok(DataType::Bool),
+                Symbol::Number(_) => Ok(DataType::Number),
+                Symbol::Text(_) => Ok(DataType::Text),
+                Symbol::Null => Ok(DataType::Null),
+                Symbol::Variable(name) => schema
+                    .find(name)
+                    .ok_or_else(|| Error::Type(TypeError::UnknownVariable(name.clone()))),
+                _ => Err(Error::Type(TypeError::InvalidAtom(symbol.clone()))),
+            },
+            Expression::Cons(cons) => cons.check(schema),
+            Expression::Call(call) => call.check(schema),
+        }
+    }
+}
+
+impl Typed for Cons {
+    fn check(&self, schema: &Schema) -> Result<DataType> {
+        match self {
+            Cons::Binary(operator, (left, right)) => {
+                let left_type = left.check(schema)?;
+                let right_type = right.check(schema)?;
+
+                let result = match (operator, left_type, right_type) {
+                    (Operator::And | Operator::Or, DataType::Bool, DataType::Bool) => {
+                        Some(DataType::Bool)
+                    }
+                    (Operator::Equal | Operator::NotEqual, left_type, right_type)
+                        if left_type == right_type
+                            || left_type == DataType::Null
+                            || right_type == DataType::Null =>
+                    {
+                        Some(DataType::Bool)
+                    }
+                    (
+                        Operator::Greater
+                        | Operator::GreaterOrEqual
+                        | Operator::Less
+                        | Operator::LessOrEqual,
+                        DataType::Number,
+                        DataType::Number,
+                    )
+                    | (
+                        Operator::Greater
+                        | Operator::GreaterOrEqual
+                        | Operator::Less
+                        | Operator::LessOrEqual,
+                        DataType::Text,
+                        DataType::Text,
+                    )
+                    | (
+                        Operator::Greater
+                        | Operator::GreaterOrEqual
+                        | Operator::Less
+                        | Operator::LessOrEqual,
+                        DataType::Date,
+                        DataType::Date,
+                    ) => Some(DataType::Bool),
+                    (Operator::Sum, DataType::Text, DataType::Text) => Some(DataType::Text),
+                    (
+                        Operator::Sum
+                        | Operator::Sub
+                        | Operator::Mul
+                        | Operator::Div
+                        | Operator::Mod
+                        | Operator::Pow,
+                        DataType::Number,
+                        DataType::Number,
+                    ) => Some(DataType::Number),
+                    _ => None,
+                };
+
+                result.ok_or(Error::Type(TypeError::InvalidOperands(
+                    *operator, left_type, right_type,
+                )))
+            }
+            Cons::Unary(operator, root) => {
+                let root_type = root.check(schema)?;
+
+                match (operator, root_type) {
+                    (Operator::Not, DataType::Bool) => Ok(DataType::Bool),
+                    (Operator::Sum | Operator::Sub, DataType::Number) => Ok(DataType::Number),
+                    _ => Err(Error::Type(TypeError::InvalidOperand(*operator, root_type))),
+                }
+            }
+            Cons::Conditional(condition, (then, otherwise)) => {
+                let condition_type = condition.check(schema)?;
+                if condition_type != DataType::Bool {
+                    return Err(Error::Type(TypeError::InvalidCondition(condition_type)));
+                }
+
+                // A null branch takes the type of the other branch
+                let then_type = then.check(schema)?;
+                let otherwise_type = otherwise.check(schema)?;
+                match (then_type, otherwise_type) {
+                    (DataType::Null, otherwise_type) => Ok(otherwise_type),
+                    (then_type, DataType::Null) => Ok(then_type),
+                    (then_type, otherwise_type) if then_type == otherwise_type => Ok(then_type),
+                    (then_type, otherwise_type) => Err(Error::Type(TypeError::MismatchedBranches(
+                        then_type,
+                        otherwise_type,
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+impl Typed for Call {
+    fn check(&self, schema: &Schema) -> Result<DataType> {
+        for arg in &self.args {
+            let arg_type = arg.check(schema)?;
+            let expected = self.function.parameter();
+            if expected.is_some_and(|expected| expected != arg_type) {
+                return Err(Error::Type(TypeError::InvalidArgument(
+                    self.function,
+                    arg_type,
+                )));
+            }
+        }
+
+        Ok(self.function.returns())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::data::DataItem;
+    use crate::error::{Error, TypeError};
+    use crate::function::Function;
+    use crate::symbols::Operator;
+    use crate::types::{DataType, Schema, Typed};
+    use crate::PredicateParser;
+
+    fn check(input: &str, schema: &Schema) -> crate::error::Result<DataType> {
+        PredicateParser::new(input).parse().unwrap().check(schema)
+    }
+
+    fn schema() -> Schema {
+        Schema::from_pairs(vec![
+            ("a", DataType::Number),
+            ("b", DataType::Bool),
+            ("c", DataType::Text),
+            ("t", DataType::Date),
+        ])
+    }
+
+    #[test]
+    fn checks_atoms() {
+        assert_eq!(check("1", &schema()).unwrap(), DataType::Number);
+        assert_eq!(check("true", &schema()).unwrap(), DataType::Bool);
+        assert_eq!(check("'fruit'", &schema()).unwrap(), DataType::Text);
+        assert_eq!(check("c", &schema()).unwrap(), DataType::Text);
+    }
+
+    #[test]
+    fn checks_operations() {
+        assert_eq!(check("a * 2 + -a", &schema()).unwrap(), DataType::Number);
+        assert_eq!(
+            check("a % 2 + a ** 2", &schema()).unwrap(),
+            DataType::Number
+        );
+        assert_eq!(check("a > 2 && !b", &schema()).unwrap(), DataType::Bool);
+        assert_eq!(
+            check("c == 'x' || c < 'y'", &schema()).unwrap(),
+            DataType::Bool
+        );
+        assert_eq!(check("c + 's'", &schema()).unwrap(), DataType::Text);
+        assert_eq!(check("b ? c : 'none'", &schema()).unwrap(), DataType::Text);
+        assert_eq!(
+            check("round(sqrt(a), 2)", &schema()).unwrap(),
+            DataType::Number
+        );
+    }
+
+    #[test]
+    fn checks_null_operations() {
+        assert_eq!(check("null", &schema()).unwrap(), DataType::Null);
+        assert_eq!(check("a == null", &schema()).unwrap(), DataType::Bool);
+        assert_eq!(check("null != c", &schema()).unwrap(), DataType::Bool);
+        assert_eq!(check("isValid(c)", &schema()).unwrap(), DataType::Bool);
+        assert_eq!(check("b ? null : a", &schema()).unwrap(), DataType::Number);
+        assert!(matches!(
+            check("a + null", &schema()),
+            Err(Error::Type(TypeError::InvalidOperands(
+                Operator::Sum,
+                DataType::Number,
+                DataType::Null
+            )))
+        ));
+    }
+
+    #[test]
+    fn checks_path_variables() {
+        let mut schema = schema();
+        schema.infer(
+            "geo",
+            &DataItem::Object(BTreeMap::from([
+                ("lat".to_string(), 1.0.into()),
+                ("tags".to_string(), DataItem::Array(vec!["a".into()])),
+                ("a.b".to_string(), true.into()),
+            ])),
+        );
+
+        assert_eq!(check("geo", &schema).unwrap(), DataType::Object);
+        assert_eq!(check("geo.lat * 2", &schema).unwrap(), DataType::Number);
+        assert_eq!(
+            check("geo.tags[0] == 'a'", &schema).unwrap(),
+            DataType::Bool
+        );
+        assert_eq!(check("geo['a.b']", &schema).unwrap(), DataType::Bool);
+        assert!(matches!(
+            check("geo.lon", &schema),
+            Err(Error::Type(TypeError::UnknownVariable(name))) if name == "geo.lon"
+        ));
+    }
+
+    #[test]
+    fn infers_nested_types() {
+        let mut schema = Schema::new();
+        schema.infer("a", &DataItem::Null);
+        schema.infer("b", &DataItem::Array(vec![DataItem::Null, 1.0.into()]));
+        schema.infer("a", &DataItem::Array(vec![true.into()]));
+        schema.infer("b", &DataItem::Array(vec!["x".into(), 2.0.into()]));
+
+        assert_eq!(
+            schema,
+            Schema::from_pairs(vec![
+                ("a", DataType::Array),
+                ("a[0]", DataType::Bool),
+                ("b", DataType::Array),
+                ("b[0]", DataType::Text),
+                ("b[1]", DataType::Number),
+            ])
+        );
+    }
+
+    #[test]
+    fn checks_date_operations() {
+        assert_eq!(check("t", &schema()).unwrap(), DataType::Date);
+        assert_eq!(
+            check("year(t) + month(t)", &schema()).unwrap(),
+            DataType::Number
+        );
+        assert_eq!(
+            check("t > datetime(2024, a)", &schema()).unwrap(),
+            DataType::Bool
+        );
+        assert_eq!(check("b ? now() : t", &schema()).unwrap(), DataType::Date);
+        assert!(matches!(
+            check("year(a)", &schema()),
+            Err(Error::Type(TypeError::InvalidArgument(
+                Function::Year,
+                DataType::Number
+            )))
+        ));
+        assert!(matches!(
+            check("datetime(t, 1)", &schema()),
+            Err(Error::Type(TypeError::InvalidArgument(
+                Function::Datetime,
+                DataType::Date
+            )))
+        ));
+        assert!(matches!(
+            check("t + 1", &schema()),
+            Err(Error::Type(TypeError::InvalidOperands(
+                Operator::Sum,
+                DataType::Date,
+                DataType::Number
+            )))
+        ));
+    }
+
+    #[test]
+    fn doesnt_check_invalid_operations() {
+        assert!(matches!(
+            check("a + b", &schema()),
+            Err(Error::Type(TypeError::InvalidOperands(
+                Operator::Sum,
+                DataType::Number,
+                DataType::Bool
+            )))
+        ));
+        assert!(matches!(
+            check("c % 2", &schema()),
+            Err(Error::Type(TypeError::InvalidOperands(
+                Operator::Mod,
+                DataType::Text,
+                DataType::Number
+            )))
+        ));
+        assert!(matches!(
+            check("a == c", &schema()),
+            Err(Error::Type(TypeError::InvalidOperands(
+                Operator::Equal,
+                DataType::Number,
+                DataType::Text
+            )))
+        ));
+        assert!(matches!(
+            check("!a", &schema()),
+            Err(Error::Type(TypeError::InvalidOperand(
+                Operator::Not,
+                DataType::Number
+            )))
+        ));
+        assert!(matches!(
+            check("a ? 1 : 2", &schema()),
+            Err(Error::Type(TypeError::InvalidCondition(DataType::Number)))
+        ));
+        assert!(matches!(
+            check("b ? 1 : c", &schema()),
+            Err(Error::Type(TypeError::MismatchedBranches(
+                DataType::Number,
+                DataType::Text
+            )))
+        ));
+        assert!(matches!(
+            check("abs(c)", &schema()),
+            Err(Error::Type(TypeError::InvalidArgument(
+                Function::Abs,
+                DataType::Text
+            )))
+        ));
+    }
+
+    #[test]
+    fn doesnt_check_unknown_variables() {
+        assert!(matches!(
+            check("a + d", &schema()),
+            Err(Error::Type(TypeError::UnknownVariable(name))) if name == "d"
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::data::{DataItem, DataSource};
+use crate::path;
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
@@ -49,6 +50,6 @@ impl Default for Variables {
 
 impl DataSource for Variables {
     fn get(&self, key: &str) -> Option<&DataItem> {
-        self.find(key)
+        path::resolve(key, |field| self.find(field))
     }
 }
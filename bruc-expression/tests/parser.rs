@@ -1,4 +1,7 @@
+use std::collections::BTreeMap;
+
 use bruc_expression::data::DataItem;
+use bruc_expression::date;
 use bruc_expression::expr::Interpretable;
 use bruc_expression::vars::Variables;
 use bruc_expression::PredicateParser;
@@ -41,6 +44,23 @@ fn interprets_multiple_numeric_expressions() {
     assert!(!result);
 }
 
+#[test]
+fn interprets_modulo_and_power_expressions_with_vars() {
+    let expression = PredicateParser::new("a % 2 == 1 && a ** 2 > 8")
+        .parse()
+        .unwrap();
+    let vars = Variables::from_pairs(vec![("a", 3.0.into())]);
+
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(result);
+
+    let expression = PredicateParser::new("10 / a").parse().unwrap();
+    let vars = Variables::from_pairs(vec![("a", 0.0.into())]);
+
+    let result: Result<f64, _> = expression.interpret(&vars);
+    assert!(result.is_err());
+}
+
 #[test]
 fn interprets_multiple_boolean_expressions() {
     let expression = PredicateParser::new("(a || true) && ((a || true) && (a && true))")
@@ -51,3 +71,105 @@ fn interprets_multiple_boolean_expressions() {
     let result: bool = expression.interpret(&vars).unwrap();
     assert!(!result);
 }
+
+#[test]
+fn interprets_text_expressions_with_vars() {
+    let vars = Variables::from_pairs(vec![
+        ("category", DataItem::Text("fruit".to_string())),
+        ("name", DataItem::Text("apple".to_string())),
+    ]);
+    let expression = PredicateParser::new("category == \"fruit\" && name < 'banana'")
+        .parse()
+        .unwrap();
+
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(result);
+}
+
+#[test]
+fn interprets_function_calls_with_vars() {
+    let vars = Variables::from_pairs(vec![
+        ("a", DataItem::Number(16.0)),
+        ("b", DataItem::Number(-3.0)),
+    ]);
+    let expression = PredicateParser::new("sqrt(a) + abs(b) * pow(2, 2)")
+        .parse()
+        .unwrap();
+
+    let result: f64 = expression.interpret(&vars).unwrap();
+    assert_eq!(result, 16.0);
+}
+
+#[test]
+fn interprets_conditional_expressions_with_vars() {
+    let vars = Variables::from_pairs(vec![
+        ("a", DataItem::Number(4.0)),
+        ("category", DataItem::Text("fruit".to_string())),
+    ]);
+    let expression = PredicateParser::new("category == 'fruit' ? a * 2 : a > 2 ? 1 : 0")
+        .parse()
+        .unwrap();
+
+    let result: f64 = expression.interpret(&vars).unwrap();
+    assert_eq!(result, 8.0);
+}
+
+#[test]
+fn interprets_null_expressions_with_vars() {
+    let expression = PredicateParser::new("isValid(a) && a != null")
+        .parse()
+        .unwrap();
+
+    let vars = Variables::from_pairs(vec![("a", 3.0.into())]);
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(result);
+
+    let vars = Variables::from_pairs(vec![("a", DataItem::Null)]);
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(!result);
+
+    let result: bool = expression.interpret(&Variables::new()).unwrap();
+    assert!(!result);
+}
+
+#[test]
+fn interprets_date_expressions_with_vars() {
+    let expression = PredicateParser::new("year(d) == 2024 && d >= datetime(2024, 2)")
+        .parse()
+        .unwrap();
+
+    let vars = Variables::from_pairs(vec![(
+        "d",
+        DataItem::Date(date::parse("2024-03-01T12:30:00Z").unwrap()),
+    )]);
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(result);
+
+    let vars = Variables::from_pairs(vec![(
+        "d",
+        DataItem::Date(date::parse("2024-02-29").unwrap()),
+    )]);
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(!result);
+}
+
+#[test]
+fn interprets_path_expressions_with_vars() {
+    let expression = PredicateParser::new("geo.lat > 2 && tags[0] == 'fruit'")
+        .parse()
+        .unwrap();
+
+    let vars = Variables::from_pairs(vec![
+        (
+            "geo",
+            DataItem::Object(BTreeMap::from([("lat".to_string(), 3.0.into())])),
+        ),
+        ("tags", DataItem::Array(vec!["fruit".into()])),
+    ]);
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(result);
+
+    let vars = Variables::from_pairs(vec![("geo.lat", 1.0.into())]);
+    let result: bool = expression.interpret(&vars).unwrap();
+    assert!(!result);
+}
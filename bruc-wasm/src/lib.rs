@@ -1,7 +1,11 @@
 use async_std::stream::StreamExt;
+use bruc_core::loader::{DataLoader, LoadError, LoadFuture};
 use bruc_core::render::svg::SvgRenderer;
+use bruc_core::spec::transform::error::Error as TransformError;
 use bruc_core::View;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response;
 
 #[wasm_bindgen]
 pub struct Bruc {
@@ -11,25 +15,54 @@ pub struct Bruc {
 #[wasm_bindgen]
 impl Bruc {
     #[wasm_bindgen]
-    pub fn build(specification: String) -> Self {
-        let specification = serde_json::from_str(specification.as_str()).unwrap();
-        let view = View::build(specification);
+    pub fn build(specification: String) -> Result<Bruc, JsValue> {
+        let specification = serde_json::from_str(specification.as_str()).map_err(js_error)?;
+        let view = View::build_with_loader(specification, FetchLoader).map_err(js_error)?;
 
-        Bruc { view }
+        Ok(Bruc { view })
     }
 
     #[wasm_bindgen(js_name = setData)]
-    pub async fn set_data(&mut self, name: String, values: Vec<JsValue>) {
+    pub async fn set_data(&mut self, name: String, values: Vec<JsValue>) -> Result<(), JsValue> {
         let values = values
             .into_iter()
-            .map(|value| serde_wasm_bindgen::from_value(value).unwrap())
-            .collect();
+            .map(serde_wasm_bindgen::from_value)
+            .collect::<Result<_, _>>()?;
 
         self.view.set_data(&name, values).await;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn insert(&mut self, name: String, values: Vec<JsValue>) -> Result<(), JsValue> {
+        let values = values
+            .into_iter()
+            .map(serde_wasm_bindgen::from_value)
+            .collect::<Result<_, _>>()?;
+
+        self.view.insert(&name, values).await;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = setDataCsv)]
+    pub async fn set_data_csv(
+        &mut self,
+        name: String,
+        text: String,
+        format: JsValue,
+    ) -> Result<(), JsValue> {
+        let format = serde_wasm_bindgen::from_value(format)?;
+
+        self.view
+            .set_data_csv(&name, &text, &format)
+            .await
+            .map_err(|error| js_error(TransformError::from(error)))
     }
 
     #[wasm_bindgen(js_name = renderAsSvg)]
-    pub async fn render_as_svg(&mut self, selector: &str) {
+    pub async fn render_as_svg(&mut self, selector: &str) -> Result<(), JsValue> {
         let window = web_sys::window().expect("No global window");
         let document = window.document().expect("No document on window");
 
@@ -39,12 +72,56 @@ impl Bruc {
 
         let element = element.expect("Element not present in document.");
 
-        let mut render_result = self.view.render(SvgRenderer).await;
+        let mut render_result = self
+            .view
+            .render(SvgRenderer::new())
+            .await
+            .map_err(js_error)?;
 
         wasm_bindgen_futures::spawn_local(async move {
             while let Some(content) = render_result.next().await {
                 element.set_inner_html(&content);
             }
         });
+
+        Ok(())
+    }
+}
+
+/// Convert an error into a JavaScript value with the error's message.
+fn js_error(error: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&error.to_string())
+}
+
+/// `FetchLoader` loads data with the browser's `fetch` API.
+#[derive(Debug)]
+struct FetchLoader;
+
+impl DataLoader for FetchLoader {
+    fn load<'a>(&'a self, url: &'a str) -> LoadFuture<'a> {
+        Box::pin(async move {
+            let failed = |error: JsValue| LoadError::Failed(url.to_string(), format!("{error:?}"));
+
+            let window = web_sys::window().expect("No global window");
+            let response: Response = JsFuture::from(window.fetch_with_str(url))
+                .await
+                .and_then(|response| response.dyn_into())
+                .map_err(failed)?;
+
+            if response.status() == 404 {
+                return Err(LoadError::NotFound(url.to_string()));
+            }
+            if !response.ok() {
+                return Err(LoadError::Failed(url.to_string(), response.status_text()));
+            }
+
+            let text = JsFuture::from(response.text().map_err(failed)?)
+                .await
+                .map_err(failed)?;
+
+            text.as_string().ok_or_else(|| {
+                LoadError::Failed(url.to_string(), "Response is not text.".to_string())
+            })
+        })
     }
 }